@@ -44,6 +44,73 @@ pub mod counter {
         msg!("label: {:?}, nonce: {}", &label[..4], nonce);
         Ok(())
     }
+
+    pub fn step(
+        ctx: Context<Set>,
+        #[decoder(enum = "StepDirection")] direction: u8,
+        #[decoder(display = "amount(decimals = 6)")] amount: u64,
+        #[decoder(display = "pubkey")] referrer: [u8; 32],
+    ) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        if direction == StepDirection::Down as u8 {
+            counter.count = counter.count.saturating_sub(amount);
+        } else {
+            counter.count = counter.count.saturating_add(amount);
+        }
+        msg!("referrer: {}", Pubkey::new_from_array(referrer));
+        Ok(())
+    }
+
+    pub fn adjust(ctx: Context<Set>, adjustment: Adjustment) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.count = match adjustment {
+            Adjustment::Reset => 0,
+            Adjustment::Add(amount) => counter.count.saturating_add(amount),
+            Adjustment::Scale {
+                numerator,
+                denominator,
+            } => counter.count.saturating_mul(numerator) / denominator.max(1),
+        };
+        Ok(())
+    }
+
+    /// Takes `v1::Adjustment`, which shares its name with the crate root's
+    /// `Adjustment`; the decoder must render the v1 variants
+    pub fn adjust_v1(ctx: Context<Set>, adjustment: v1::Adjustment) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.count = match adjustment {
+            v1::Adjustment::Reset => 0,
+            v1::Adjustment::Add(amount) => counter.count.saturating_add(amount),
+        };
+        Ok(())
+    }
+}
+
+/// Direction of a `step`, passed as its u8 discriminant
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepDirection {
+    Up,
+    Down,
+}
+
+/// Change applied by `adjust`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Adjustment {
+    Reset,
+    Add(u64),
+    Scale { numerator: u64, denominator: u64 },
+}
+
+/// Earlier version of the `adjust` argument
+pub mod v1 {
+    use super::*;
+
+    /// `Adjustment` before `Scale` was added
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Adjustment {
+        Reset,
+        Add(u64),
+    }
 }
 
 #[account]
@@ -8,8 +8,9 @@ use quote::{format_ident, quote};
 use syn::ItemMod;
 
 use crate::{
+    builder::field_display_code,
     crate_context::CrateContext,
-    parsing::ModuleDecoderArgs,
+    parsing::{parse_field_display, FieldDisplay, ModuleDecoderArgs},
     utils::{compute_anchor_discriminator, to_pascal_case},
 };
 
@@ -17,6 +18,10 @@ use crate::{
 struct ParamInfo {
     name: syn::Ident,
     ty: syn::Type,
+    /// Display override from `#[decoder(...)]`
+    display: Option<FieldDisplay>,
+    /// Definition of the argument's enum type, if it is declared in this crate
+    enum_def: Option<syn::ItemEnum>,
 }
 
 /// Information about an instruction extracted from the module.
@@ -43,7 +48,7 @@ pub fn instruction_decoder_attr(
     attr: TokenStream2,
     item: TokenStream2,
 ) -> syn::Result<TokenStream2> {
-    let module: ItemMod = syn::parse2(item.clone())?;
+    let mut module: ItemMod = syn::parse2(item)?;
 
     // Parse attribute arguments
     let mut args = ModuleDecoderArgs::parse(attr)?;
@@ -54,9 +59,12 @@ pub fn instruction_decoder_attr(
     // Extract function info from the module
     let instructions = extract_instruction_info(&module)?;
 
+    // `#[decoder(...)]` is only meaningful to this macro, so remove it before Anchor sees the module
+    strip_decoder_attrs(&mut module);
+
     if instructions.is_empty() {
         // No functions found, just return the module as-is
-        return Ok(item);
+        return Ok(quote! { #module });
     }
 
     let module_name = &module.ident;
@@ -68,12 +76,6 @@ pub fn instruction_decoder_attr(
     // Generate match arms for each instruction
     let match_arms = generate_match_arms(&instructions);
 
-    // Generate params structs for all instructions that have params
-    let params_structs: Vec<TokenStream2> = instructions
-        .iter()
-        .filter_map(|info| generate_params_struct(&info.name, &info.params))
-        .collect();
-
     // Get program ID and name
     let program_id_source = args.program_id_source();
     let program_id_impl = program_id_source.program_id_impl();
@@ -85,12 +87,6 @@ pub fn instruction_decoder_attr(
         /// Generated InstructionDecoder for the program module (off-chain only)
         pub struct #decoder_name;
 
-        // Generated params structs for deserialization (off-chain only)
-        #(
-            #[cfg(not(target_os = "solana"))]
-            #params_structs
-        )*
-
         #[cfg(not(target_os = "solana"))]
         impl light_instruction_decoder::InstructionDecoder for #decoder_name {
             #program_id_impl
@@ -99,6 +95,10 @@ pub fn instruction_decoder_attr(
                 #program_name
             }
 
+            fn discriminator_size(&self) -> Option<usize> {
+                Some(8)
+            }
+
             fn decode(
                 &self,
                 data: &[u8],
@@ -120,7 +120,7 @@ pub fn instruction_decoder_attr(
 
     // Return the original module plus the generated decoder
     Ok(quote! {
-        #item
+        #module
         #decoder_impl
     })
 }
@@ -134,31 +134,54 @@ fn generate_match_arms(instructions: &[InstructionInfo]) -> Vec<TokenStream2> {
             let discriminator = compute_anchor_discriminator(&info.name);
             let disc_array = discriminator.iter();
 
-            // Generate params decoding code using the generated DecoderParams struct
+            // Deserialize parameters one by one (as Anchor does), keeping the ones
+            // decoded before a failure and marking the failing one
             let fields_code = if info.params.is_empty() {
                 quote! { Vec::new() }
             } else {
-                let params_struct_name = format_ident!("{}DecoderParams", pascal_name);
-                // Generate field accessors for each parameter with their field names
                 let field_pushes: Vec<TokenStream2> = info.params.iter().map(|param| {
-                    let field_name = &param.name;
-                    let field_name_str = field_name.to_string();
+                    let field_name_str = param.name.to_string();
+                    let ty = &param.ty;
+                    let field_code = match (&param.display, &param.enum_def) {
+                        (Some(display), _) => {
+                            let value_code = field_display_code(display, quote!(value));
+                            quote! {
+                                light_instruction_decoder::DecodedField::new(#field_name_str, #value_code)
+                            }
+                        }
+                        (None, Some(item_enum)) => enum_field_code(&field_name_str, ty, item_enum),
+                        (None, None) => quote! {
+                            light_instruction_decoder::DecodedField::new(
+                                #field_name_str,
+                                format!("{:#?}", value),
+                            )
+                        },
+                    };
                     quote! {
-                        fields.push(light_instruction_decoder::DecodedField::new(
-                            #field_name_str,
-                            format!("{:#?}", params.#field_name),
-                        ));
+                        let offset = data.len() - cursor.len();
+                        match <#ty as borsh::BorshDeserialize>::deserialize(&mut cursor) {
+                            Ok(value) => fields.push(#field_code),
+                            Err(err) => {
+                                let reason = if cursor.is_empty() {
+                                    "unexpected end of input".to_string()
+                                } else {
+                                    err.to_string()
+                                };
+                                fields.push(light_instruction_decoder::DecodedField::decode_error(
+                                    #field_name_str,
+                                    offset,
+                                    reason,
+                                ));
+                                break 'fields;
+                            }
+                        }
                     }
                 }).collect();
                 quote! {
                     let mut fields = Vec::new();
-                    if let Ok(params) = <#params_struct_name as borsh::BorshDeserialize>::try_from_slice(remaining) {
+                    let mut cursor: &[u8] = remaining;
+                    'fields: {
                         #(#field_pushes)*
-                    } else if !remaining.is_empty() {
-                        fields.push(light_instruction_decoder::DecodedField::new(
-                            "data_len",
-                            remaining.len().to_string(),
-                        ));
                     }
                     fields
                 }
@@ -178,6 +201,7 @@ fn generate_match_arms(instructions: &[InstructionInfo]) -> Vec<TokenStream2> {
                     }
                 }
             } else {
+                let account_count = account_names.len();
                 quote! {
                     [#(#disc_array),*] => {
                         let remaining = &data[8..];
@@ -186,7 +210,7 @@ fn generate_match_arms(instructions: &[InstructionInfo]) -> Vec<TokenStream2> {
                             #pascal_name,
                             fields,
                             vec![#(#account_names.to_string()),*],
-                        ))
+                        ).with_expected_accounts(#account_count))
                     }
                 }
             }
@@ -194,10 +218,58 @@ fn generate_match_arms(instructions: &[InstructionInfo]) -> Vec<TokenStream2> {
         .collect()
 }
 
+/// Generate a field rendering the enum `value` as its variant name.
+///
+/// Data of tuple and struct variants becomes child fields (named `0`, `1`, ... for
+/// tuple variants), each rendered with `Debug`.
+fn enum_field_code(field_name: &str, ty: &syn::Type, item_enum: &syn::ItemEnum) -> TokenStream2 {
+    let arms = item_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let variant_name = ident.to_string();
+        let bindings: Vec<syn::Ident> = (0..variant.fields.len())
+            .map(|i| format_ident!("field_{}", i))
+            .collect();
+        let (pattern, child_names): (TokenStream2, Vec<String>) = match &variant.fields {
+            syn::Fields::Named(fields) => {
+                let names: Vec<&syn::Ident> = fields
+                    .named
+                    .iter()
+                    .filter_map(|f| f.ident.as_ref())
+                    .collect();
+                (
+                    quote! { #ty::#ident { #(#names: #bindings),* } },
+                    names.iter().map(|name| name.to_string()).collect(),
+                )
+            }
+            syn::Fields::Unnamed(_) => (
+                quote! { #ty::#ident(#(#bindings),*) },
+                (0..bindings.len()).map(|i| i.to_string()).collect(),
+            ),
+            syn::Fields::Unit => (quote! { #ty::#ident }, Vec::new()),
+        };
+        quote! {
+            #pattern => light_instruction_decoder::DecodedField {
+                name: #field_name.to_string(),
+                value: #variant_name.to_string(),
+                children: vec![#(light_instruction_decoder::DecodedField::new(
+                    #child_names,
+                    format!("{:?}", #bindings),
+                )),*],
+            },
+        }
+    });
+    quote! {
+        match &value {
+            #(#arms)*
+        }
+    }
+}
+
 /// Extract public function information from an Anchor program module.
 fn extract_instruction_info(module: &ItemMod) -> syn::Result<Vec<InstructionInfo>> {
     // Parse entire crate to find Accounts structs
     let crate_ctx = CrateContext::parse_from_manifest()?;
+    let module_path = crate_ctx.module_path(&module.ident.to_string());
 
     let mut instructions = Vec::new();
 
@@ -218,7 +290,7 @@ fn extract_instruction_info(module: &ItemMod) -> syn::Result<Vec<InstructionInfo
                     };
 
                     // Extract all parameters after Context
-                    let params = extract_all_params(&func.sig);
+                    let params = extract_all_params(&func.sig, &crate_ctx, module_path)?;
 
                     instructions.push(InstructionInfo {
                         name,
@@ -271,7 +343,14 @@ fn extract_type_name(ty: &syn::Type) -> Option<String> {
 /// This mirrors how Anchor generates its instruction structs - iterating
 /// all args after Context and generating a struct field for each.
 /// We generate our own struct with Debug derive for decoding.
-fn extract_all_params(sig: &syn::Signature) -> Vec<ParamInfo> {
+///
+/// Arguments whose type resolves to an enum declared in the crate are rendered by
+/// variant name (see [`enum_field_code`]).
+fn extract_all_params(
+    sig: &syn::Signature,
+    crate_ctx: &CrateContext,
+    module_path: Option<&str>,
+) -> syn::Result<Vec<ParamInfo>> {
     let mut params = Vec::new();
     let mut found_context = false;
 
@@ -290,37 +369,58 @@ fn extract_all_params(sig: &syn::Signature) -> Vec<ParamInfo> {
                     params.push(ParamInfo {
                         name: pat_ident.ident.clone(),
                         ty: (*pat_type.ty).clone(),
+                        display: parse_field_display(&pat_type.attrs)?,
+                        enum_def: find_enum(&pat_type.ty, crate_ctx, module_path),
                     });
                 }
             }
         }
     }
-    params
+    Ok(params)
 }
 
-/// Generate a params struct for an instruction with Debug and BorshDeserialize.
+/// Find the crate enum a parameter type refers to.
 ///
-/// Returns None if the instruction has no parameters.
-fn generate_params_struct(instruction_name: &str, params: &[ParamInfo]) -> Option<TokenStream2> {
-    if params.is_empty() {
+/// Only plain paths without generic arguments (`Direction`, `crate::state::Direction`)
+/// are resolved, from the program module `module_path` (see
+/// [`CrateContext::resolve_enum`]); enums without variants are ignored. Types that
+/// don't resolve to a local enum keep the `Debug` rendering.
+fn find_enum(
+    ty: &syn::Type,
+    crate_ctx: &CrateContext,
+    module_path: Option<&str>,
+) -> Option<syn::ItemEnum> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some()
+        || type_path
+            .path
+            .segments
+            .iter()
+            .any(|segment| !segment.arguments.is_empty())
+    {
         return None;
     }
+    crate_ctx
+        .resolve_enum(module_path?, &type_path.path)
+        .filter(|item_enum| !item_enum.variants.is_empty())
+        .cloned()
+}
 
-    let struct_name = format_ident!("{}DecoderParams", to_pascal_case(instruction_name));
-
-    let fields: Vec<TokenStream2> = params
-        .iter()
-        .map(|param| {
-            let name = &param.name;
-            let ty = &param.ty;
-            quote! { pub #name: #ty }
-        })
-        .collect();
-
-    Some(quote! {
-        #[derive(Debug, borsh::BorshDeserialize)]
-        struct #struct_name {
-            #(#fields),*
+/// Remove `#[decoder(...)]` attributes from the arguments of all module functions.
+fn strip_decoder_attrs(module: &mut ItemMod) {
+    if let Some((_, items)) = module.content.as_mut() {
+        for item in items {
+            if let syn::Item::Fn(func) = item {
+                for input in func.sig.inputs.iter_mut() {
+                    if let syn::FnArg::Typed(pat_type) = input {
+                        pat_type
+                            .attrs
+                            .retain(|attr| !attr.path().is_ident("decoder"));
+                    }
+                }
+            }
         }
-    })
+    }
 }
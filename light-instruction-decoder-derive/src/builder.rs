@@ -10,10 +10,10 @@ use syn::Fields;
 use crate::{
     crate_context::CrateContext,
     parsing::{
-        parse_explicit_discriminator, ExplicitDiscriminator, InstructionDecoderArgs,
-        VariantDecoderArgs,
+        parse_explicit_discriminator, parse_field_display, DiscriminatorScheme,
+        ExplicitDiscriminator, FieldDisplay, InstructionDecoderArgs, VariantDecoderArgs,
     },
-    utils::{compute_anchor_discriminator, to_snake_case},
+    utils::{compute_anchor_discriminator, compute_sighash_discriminator, to_snake_case},
 };
 
 /// Builder for generating InstructionDecoder implementations.
@@ -137,7 +137,7 @@ impl<'a> InstructionDecoderBuilder<'a> {
         // Generate the body code based on whether we have a dynamic resolver
         let body_code = self.generate_match_arm_body(variant, variant_args)?;
 
-        match self.args.discriminator_size {
+        match self.args.discriminator_size() {
             1 => {
                 let disc = match &self.explicit_discriminators[index] {
                     Some(ExplicitDiscriminator::U32(d)) => {
@@ -174,11 +174,15 @@ impl<'a> InstructionDecoderBuilder<'a> {
                 Ok(quote! {
                     #disc => {
                         #body_code
-                        Some(light_instruction_decoder::DecodedInstruction::with_fields_and_accounts(
+                        let mut decoded = light_instruction_decoder::DecodedInstruction::with_fields_and_accounts(
                             #instruction_name,
                             fields,
                             account_names,
-                        ))
+                        );
+                        if let Some(count) = expected_accounts {
+                            decoded = decoded.with_expected_accounts(count);
+                        }
+                        Some(decoded)
                     }
                 })
             }
@@ -207,17 +211,21 @@ impl<'a> InstructionDecoderBuilder<'a> {
                 Ok(quote! {
                     #disc => {
                         #body_code
-                        Some(light_instruction_decoder::DecodedInstruction::with_fields_and_accounts(
+                        let mut decoded = light_instruction_decoder::DecodedInstruction::with_fields_and_accounts(
                             #instruction_name,
                             fields,
                             account_names,
-                        ))
+                        );
+                        if let Some(count) = expected_accounts {
+                            decoded = decoded.with_expected_accounts(count);
+                        }
+                        Some(decoded)
                     }
                 })
             }
             8 => {
                 // For 8-byte mode: check for explicit array discriminator first,
-                // then fall back to explicit u32, then to computed Anchor discriminator
+                // then fall back to explicit u32, then to the discriminator scheme
                 let discriminator: [u8; 8] = match &self.explicit_discriminators[index] {
                     Some(ExplicitDiscriminator::Array(arr)) => *arr,
                     Some(ExplicitDiscriminator::U32(_)) => {
@@ -227,20 +235,31 @@ impl<'a> InstructionDecoderBuilder<'a> {
                         ));
                     }
                     None => {
-                        // Fall back to computed Anchor discriminator
                         let snake_name = to_snake_case(&instruction_name);
-                        compute_anchor_discriminator(&snake_name)
+                        match self.args.discriminator {
+                            DiscriminatorScheme::SighashNoNamespace => {
+                                compute_sighash_discriminator(&snake_name)
+                            }
+                            // `shank_u8` is rejected for 8-byte discriminators by `validate`
+                            DiscriminatorScheme::Anchor | DiscriminatorScheme::ShankU8 => {
+                                compute_anchor_discriminator(&snake_name)
+                            }
+                        }
                     }
                 };
                 let disc_array = discriminator.iter();
                 Ok(quote! {
                     [#(#disc_array),*] => {
                         #body_code
-                        Some(light_instruction_decoder::DecodedInstruction::with_fields_and_accounts(
+                        let mut decoded = light_instruction_decoder::DecodedInstruction::with_fields_and_accounts(
                             #instruction_name,
                             fields,
                             account_names,
-                        ))
+                        );
+                        if let Some(count) = expected_accounts {
+                            decoded = decoded.with_expected_accounts(count);
+                        }
+                        Some(decoded)
                     }
                 })
             }
@@ -258,7 +277,8 @@ impl<'a> InstructionDecoderBuilder<'a> {
     /// 2. Calls the resolver function with params and accounts to get dynamic account names
     /// 3. Calls the formatter if specified
     ///
-    /// Otherwise, it uses static account names from `accounts` or `account_names`.
+    /// Otherwise, it uses static account names from `accounts` or `account_names`,
+    /// whose length also becomes the instruction's expected account count.
     fn generate_match_arm_body(
         &self,
         variant: &syn::Variant,
@@ -270,7 +290,13 @@ impl<'a> InstructionDecoderBuilder<'a> {
             variant_args.params_type(),
         ) {
             // Dynamic resolver mode: parse params first, then call resolver
-            let fields_code = if let Some(formatter_path) = &variant_args.pretty_formatter {
+            let failure_code = params_failure_code(params_ty);
+            let fields_code = if let Some(formatter_path) = &variant_args.fields_formatter {
+                // Use custom structured fields
+                quote! {
+                    #formatter_path(&params, accounts)
+                }
+            } else if let Some(formatter_path) = &variant_args.pretty_formatter {
                 // Use custom formatter
                 quote! {
                     let mut fields = Vec::new();
@@ -301,14 +327,11 @@ impl<'a> InstructionDecoderBuilder<'a> {
                 } else {
                     let account_names: Vec<String> = Vec::new();
                     let mut fields = Vec::new();
-                    if !remaining.is_empty() {
-                        fields.push(light_instruction_decoder::DecodedField::new(
-                            "data_len",
-                            remaining.len().to_string(),
-                        ));
-                    }
+                    #failure_code
                     (account_names, fields)
                 };
+                // Resolved account names follow the provided accounts, so there is no fixed count
+                let expected_accounts: Option<usize> = None;
             })
         } else {
             // Static account names mode
@@ -317,6 +340,11 @@ impl<'a> InstructionDecoderBuilder<'a> {
 
             Ok(quote! {
                 let account_names: Vec<String> = #account_names_code;
+                let expected_accounts = if account_names.is_empty() {
+                    None
+                } else {
+                    Some(account_names.len())
+                };
                 let fields = { #fields_code };
             })
         }
@@ -330,6 +358,21 @@ impl<'a> InstructionDecoderBuilder<'a> {
     ) -> syn::Result<TokenStream2> {
         // If params type is specified, use borsh deserialization
         if let Some(params_ty) = variant_args.params_type() {
+            let failure_code = params_failure_code(params_ty);
+            // Check if fields_formatter is specified
+            if let Some(formatter_path) = &variant_args.fields_formatter {
+                return Ok(quote! {
+                    if let Ok(params) = <#params_ty as borsh::BorshDeserialize>::try_from_slice(remaining) {
+                        // Call custom formatter with params and accounts
+                        #formatter_path(&params, accounts)
+                    } else {
+                        let mut fields = Vec::new();
+                        #failure_code
+                        fields
+                    }
+                });
+            }
+
             // Check if pretty_formatter is specified
             if let Some(formatter_path) = &variant_args.pretty_formatter {
                 return Ok(quote! {
@@ -341,11 +384,8 @@ impl<'a> InstructionDecoderBuilder<'a> {
                             "",
                             formatted,
                         ));
-                    } else if !remaining.is_empty() {
-                        fields.push(light_instruction_decoder::DecodedField::new(
-                            "data_len",
-                            remaining.len().to_string(),
-                        ));
+                    } else {
+                        #failure_code
                     }
                     fields
                 });
@@ -359,11 +399,8 @@ impl<'a> InstructionDecoderBuilder<'a> {
                         "",
                         format!("{:#?}", params),
                     ));
-                } else if !remaining.is_empty() {
-                    fields.push(light_instruction_decoder::DecodedField::new(
-                        "data_len",
-                        remaining.len().to_string(),
-                    ));
+                } else {
+                    #failure_code
                 }
                 fields
             });
@@ -381,9 +418,9 @@ impl<'a> InstructionDecoderBuilder<'a> {
         match_arms: &[TokenStream2],
     ) -> TokenStream2 {
         let program_id_bytes = &self.program_id_bytes;
-        let disc_size = self.args.discriminator_size as usize;
+        let disc_size = self.args.discriminator_size() as usize;
 
-        match self.args.discriminator_size {
+        match self.args.discriminator_size() {
             1 => quote! {
                 /// Generated InstructionDecoder implementation
                 pub struct #decoder_name;
@@ -397,6 +434,10 @@ impl<'a> InstructionDecoderBuilder<'a> {
                         #program_name
                     }
 
+                    fn discriminator_size(&self) -> Option<usize> {
+                        Some(#disc_size)
+                    }
+
                     fn decode(
                         &self,
                         data: &[u8],
@@ -429,6 +470,10 @@ impl<'a> InstructionDecoderBuilder<'a> {
                         #program_name
                     }
 
+                    fn discriminator_size(&self) -> Option<usize> {
+                        Some(#disc_size)
+                    }
+
                     fn decode(
                         &self,
                         data: &[u8],
@@ -461,6 +506,10 @@ impl<'a> InstructionDecoderBuilder<'a> {
                         #program_name
                     }
 
+                    fn discriminator_size(&self) -> Option<usize> {
+                        Some(#disc_size)
+                    }
+
                     fn decode(
                         &self,
                         data: &[u8],
@@ -485,153 +534,194 @@ impl<'a> InstructionDecoderBuilder<'a> {
 }
 
 /// Generate field parsing code for native program instructions.
-/// Parses fields based on their types (u8, u16, u32, u64, i64) using little-endian byte reading.
+/// Parses fields based on their types (u8, u16, u32, u64, i64, [u8; 32]) using little-endian byte reading.
+///
+/// Fields are read one by one; when the data runs out, the fields read so far are kept
+/// and the first missing field is marked with a decode error.
 pub fn generate_native_fields_code(variant: &syn::Variant) -> syn::Result<TokenStream2> {
-    match &variant.fields {
-        Fields::Named(fields_named) => {
-            let mut field_parsers = Vec::new();
-            let mut offset: usize = 0;
-
-            for field in &fields_named.named {
-                let field_name = field.ident.as_ref().unwrap().to_string();
-                let field_type = &field.ty;
-                let type_str = quote!(#field_type).to_string();
-
-                let (parser, size) = generate_field_parser(&field_name, &type_str, offset);
-                field_parsers.push(parser);
-                offset += size;
-            }
-
-            Ok(quote! {
-                let mut fields = Vec::new();
-                #(#field_parsers)*
+    let fields: Vec<(String, &syn::Field)> = match &variant.fields {
+        Fields::Named(fields_named) => fields_named
+            .named
+            .iter()
+            .map(|field| (field.ident.as_ref().unwrap().to_string(), field))
+            .collect(),
+        Fields::Unnamed(fields_unnamed) => fields_unnamed
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| (format!("arg{}", i), field))
+            .collect(),
+        Fields::Unit => {
+            return Ok(quote! {
+                let fields: Vec<light_instruction_decoder::DecodedField> = Vec::new();
                 fields
             })
         }
-        Fields::Unnamed(fields_unnamed) => {
-            let mut field_parsers = Vec::new();
-            let mut offset: usize = 0;
-
-            for (i, field) in fields_unnamed.unnamed.iter().enumerate() {
-                let field_name = format!("arg{}", i);
-                let field_type = &field.ty;
-                let type_str = quote!(#field_type).to_string();
-
-                let (parser, size) = generate_field_parser(&field_name, &type_str, offset);
-                field_parsers.push(parser);
-                offset += size;
+    };
+
+    let mut field_parsers = Vec::new();
+    let mut offset: usize = 0;
+    for (field_name, field) in fields {
+        let field_type = &field.ty;
+        let type_str = quote!(#field_type).to_string();
+        let display = parse_field_display(&field.attrs)?;
+
+        let (parser, size) =
+            generate_field_parser(&field_name, &type_str, offset, display.as_ref());
+        field_parsers.push(parser);
+        offset += size;
+    }
+
+    // Only fixed-size fields can run out of data and break out of the labeled block
+    let parsers = if offset > 0 {
+        quote! { 'fields: { #(#field_parsers)* } }
+    } else {
+        quote! { #(#field_parsers)* }
+    };
+    Ok(quote! {
+        let mut fields = Vec::new();
+        #parsers
+        fields
+    })
+}
+
+/// Generate the fields pushed when a `params` type fails to deserialize.
+///
+/// Params types are opaque to the macro, so nothing can be kept; instead the data length
+/// (if any) and a decode error locating where deserialization stopped are pushed.
+/// Borsh reports running out of input as `InvalidData`; other errors, such as a
+/// rejected collection length, keep their message.
+fn params_failure_code(params_ty: &syn::Type) -> TokenStream2 {
+    quote! {
+        if !remaining.is_empty() {
+            fields.push(light_instruction_decoder::DecodedField::new(
+                "data_len",
+                remaining.len().to_string(),
+            ));
+        }
+        let mut cursor: &[u8] = remaining;
+        let reason = match <#params_ty as borsh::BorshDeserialize>::deserialize(&mut cursor) {
+            Ok(_) => format!("{} trailing bytes", cursor.len()),
+            Err(err) if cursor.is_empty() && err.kind() == borsh::io::ErrorKind::InvalidData => {
+                "unexpected end of input".to_string()
             }
+            Err(err) => err.to_string(),
+        };
+        fields.push(light_instruction_decoder::DecodedField::decode_error(
+            "",
+            data.len() - cursor.len(),
+            reason,
+        ));
+    }
+}
 
-            Ok(quote! {
-                let mut fields = Vec::new();
-                #(#field_parsers)*
-                fields
-            })
+/// Generate code rendering `value` according to a `#[decoder(...)]` display override.
+pub fn field_display_code(display: &FieldDisplay, value: TokenStream2) -> TokenStream2 {
+    match display {
+        FieldDisplay::Amount { decimals: 0 } => quote! { #value.to_string() },
+        FieldDisplay::Amount { decimals } => {
+            let width = *decimals as usize;
+            let scale = 10u128.pow(*decimals);
+            quote! {{
+                let amount = (#value as i128).unsigned_abs();
+                let sign = if (#value as i128) < 0 { "-" } else { "" };
+                format!("{}{}.{:0width$}", sign, amount / #scale, amount % #scale, width = #width)
+            }}
         }
-        Fields::Unit => Ok(quote! {
-            let fields: Vec<light_instruction_decoder::DecodedField> = Vec::new();
-            fields
-        }),
+        FieldDisplay::Pubkey => quote! {
+            light_instruction_decoder::solana_pubkey::Pubkey::new_from_array(#value).to_string()
+        },
+        FieldDisplay::Hex => quote! {
+            #value.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+        },
+        FieldDisplay::Enum(path) => quote! {
+            <#path as borsh::BorshDeserialize>::try_from_slice(&[#value])
+                .map(|variant| format!("{:?}", variant))
+                .unwrap_or_else(|_| format!("{} (unknown)", #value))
+        },
     }
 }
 
 /// Generate parser code for a single field based on its type.
-fn generate_field_parser(field_name: &str, type_str: &str, offset: usize) -> (TokenStream2, usize) {
-    match type_str {
-        "u8" => (
-            quote! {
-                if remaining.len() > #offset {
-                    let value = remaining[#offset];
-                    fields.push(light_instruction_decoder::DecodedField::new(
-                        #field_name,
-                        value.to_string(),
-                    ));
-                }
-            },
-            1,
-        ),
+///
+/// `display` overrides how the parsed value is rendered; by default it uses `to_string()`
+/// (`Debug` for byte arrays). Fixed-size fields that do not fit in the remaining data
+/// push a decode error and break out of the enclosing `'fields` block.
+fn generate_field_parser(
+    field_name: &str,
+    type_str: &str,
+    offset: usize,
+    display: Option<&FieldDisplay>,
+) -> (TokenStream2, usize) {
+    let value_code = display
+        .map(|display| field_display_code(display, quote!(value)))
+        .unwrap_or_else(|| quote!(value.to_string()));
+    // Token spacing differs between the compiler and proc_macro2, so compare without whitespace
+    let (size, read_code, value_code) = match type_str.replace(' ', "").as_str() {
+        "u8" => (1, quote!(remaining[#offset]), value_code),
         "u16" => (
-            quote! {
-                if remaining.len() > #offset + 1 {
-                    let value = u16::from_le_bytes([
-                        remaining[#offset],
-                        remaining[#offset + 1],
-                    ]);
-                    fields.push(light_instruction_decoder::DecodedField::new(
-                        #field_name,
-                        value.to_string(),
-                    ));
-                }
-            },
             2,
+            quote!(u16::from_le_bytes(remaining[#offset..#offset + 2].try_into().unwrap())),
+            value_code,
         ),
         "u32" => (
-            quote! {
-                if remaining.len() > #offset + 3 {
-                    let value = u32::from_le_bytes([
-                        remaining[#offset],
-                        remaining[#offset + 1],
-                        remaining[#offset + 2],
-                        remaining[#offset + 3],
-                    ]);
-                    fields.push(light_instruction_decoder::DecodedField::new(
-                        #field_name,
-                        value.to_string(),
-                    ));
-                }
-            },
             4,
+            quote!(u32::from_le_bytes(remaining[#offset..#offset + 4].try_into().unwrap())),
+            value_code,
         ),
         "u64" => (
-            quote! {
-                if remaining.len() > #offset + 7 {
-                    let value = u64::from_le_bytes([
-                        remaining[#offset],
-                        remaining[#offset + 1],
-                        remaining[#offset + 2],
-                        remaining[#offset + 3],
-                        remaining[#offset + 4],
-                        remaining[#offset + 5],
-                        remaining[#offset + 6],
-                        remaining[#offset + 7],
-                    ]);
-                    fields.push(light_instruction_decoder::DecodedField::new(
-                        #field_name,
-                        value.to_string(),
-                    ));
-                }
-            },
             8,
+            quote!(u64::from_le_bytes(remaining[#offset..#offset + 8].try_into().unwrap())),
+            value_code,
         ),
         "i64" => (
-            quote! {
-                if remaining.len() > #offset + 7 {
-                    let value = i64::from_le_bytes([
-                        remaining[#offset],
-                        remaining[#offset + 1],
-                        remaining[#offset + 2],
-                        remaining[#offset + 3],
-                        remaining[#offset + 4],
-                        remaining[#offset + 5],
-                        remaining[#offset + 6],
-                        remaining[#offset + 7],
-                    ]);
+            8,
+            quote!(i64::from_le_bytes(remaining[#offset..#offset + 8].try_into().unwrap())),
+            value_code,
+        ),
+        "[u8;32]" => {
+            let value_code = if display.is_some() {
+                value_code
+            } else {
+                quote!(format!("{:?}", value))
+            };
+            (
+                32,
+                quote!(<[u8; 32]>::try_from(&remaining[#offset..#offset + 32]).unwrap()),
+                value_code,
+            )
+        }
+        _ => {
+            return (
+                quote! {
                     fields.push(light_instruction_decoder::DecodedField::new(
                         #field_name,
-                        value.to_string(),
+                        format!("({}bytes)", remaining.len().saturating_sub(#offset)),
                     ));
-                }
-            },
-            8,
-        ),
-        _ => (
-            quote! {
+                },
+                0,
+            )
+        }
+    };
+
+    let end = offset + size;
+    (
+        quote! {
+            if remaining.len() >= #end {
+                let value = #read_code;
                 fields.push(light_instruction_decoder::DecodedField::new(
                     #field_name,
-                    format!("({}bytes)", remaining.len().saturating_sub(#offset)),
+                    #value_code,
                 ));
-            },
-            0,
-        ),
-    }
+            } else {
+                fields.push(light_instruction_decoder::DecodedField::decode_error(
+                    #field_name,
+                    data.len() - remaining.len() + #offset,
+                    "unexpected end of input",
+                ));
+                break 'fields;
+            }
+        },
+        size,
+    )
 }
@@ -11,7 +11,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use syn::{Item, ItemStruct};
+use syn::{Item, ItemEnum, ItemStruct};
 
 // =============================================================================
 // CRATE CONTEXT
@@ -62,6 +62,128 @@ impl CrateContext {
         self.modules.values().flat_map(|module| module.structs())
     }
 
+    /// Path of the module named `mod_name` (e.g., "crate::counter").
+    ///
+    /// Returns None if no module or more than one module has that name.
+    pub fn module_path(&self, mod_name: &str) -> Option<&str> {
+        let mut paths = self
+            .modules
+            .keys()
+            .filter(|path| path.rsplit("::").next() == Some(mod_name));
+        match (paths.next(), paths.next()) {
+            (Some(path), None) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Resolve a type path written in module `module_path` to an enum of this crate.
+    ///
+    /// Paths starting with `crate`, `self` or `super` are followed as written. Other
+    /// paths start at an item of `module_path`: a child module or enum declared
+    /// there, or one brought in by its `use` items (imports, renames and globs;
+    /// re-exports are not followed).
+    ///
+    /// Returns None if the path does not lead to a non-generic enum declared in the
+    /// crate, e.g. for a type of another crate named like a local enum.
+    pub fn resolve_enum(&self, module_path: &str, path: &syn::Path) -> Option<&ItemEnum> {
+        if path.leading_colon.is_some() {
+            return None;
+        }
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let (name, parents) = segments.split_last()?;
+        let (enum_module, enum_name) = if parents.is_empty() {
+            self.resolve_local(module_path, name, |module, name| {
+                self.get_enum_in(module, name).is_some()
+            })?
+        } else {
+            (self.resolve_module(module_path, parents)?, name.clone())
+        };
+        self.get_enum_in(&enum_module, &enum_name)
+            .filter(|item_enum| item_enum.generics.params.is_empty())
+    }
+
+    /// Enum `name` declared directly in module `module_path`.
+    fn get_enum_in(&self, module_path: &str, name: &str) -> Option<&ItemEnum> {
+        self.modules
+            .get(module_path)?
+            .enums()
+            .find(|item_enum| item_enum.ident == name)
+    }
+
+    /// Resolve module path `segments` written in module `module_path`.
+    ///
+    /// The first segment may also be a module imported into `module_path`.
+    fn resolve_module(&self, module_path: &str, segments: &[String]) -> Option<String> {
+        let (first, rest) = segments.split_first()?;
+        if matches!(first.as_str(), "crate" | "self" | "super") {
+            return self.resolve_module_path(module_path, segments);
+        }
+        let (parent, name) = self.resolve_local(module_path, first, |module, name| {
+            self.modules.contains_key(&format!("{}::{}", module, name))
+        })?;
+        self.resolve_module_path(&format!("{}::{}", parent, name), rest)
+    }
+
+    /// Follow module path `segments` from `module_path` without looking at imports:
+    /// `crate`, `self`, `super` and child modules.
+    fn resolve_module_path(&self, module_path: &str, segments: &[String]) -> Option<String> {
+        let mut current = module_path.to_string();
+        for segment in segments {
+            match segment.as_str() {
+                "crate" => current = "crate".to_string(),
+                "self" => {}
+                "super" => current = current.rsplit_once("::")?.0.to_string(),
+                name => current = format!("{}::{}", current, name),
+            }
+        }
+        self.modules.contains_key(&current).then_some(current)
+    }
+
+    /// Find what the single-segment path `name` in module `module_path` refers to,
+    /// as the module and name of an item for which `exists` holds.
+    ///
+    /// Items declared in the module win over explicit imports, which win over
+    /// globs. A name found through several globs is ambiguous.
+    fn resolve_local(
+        &self,
+        module_path: &str,
+        name: &str,
+        exists: impl Fn(&str, &str) -> bool,
+    ) -> Option<(String, String)> {
+        if exists(module_path, name) {
+            return Some((module_path.to_string(), name.to_string()));
+        }
+        let mut glob_matches = Vec::new();
+        for import in self.modules.get(module_path)?.imports() {
+            match import {
+                Import::Name {
+                    parents,
+                    ident,
+                    alias,
+                } if alias == name => {
+                    let module = self.resolve_module_path(module_path, &parents)?;
+                    return exists(&module, &ident).then_some((module, ident));
+                }
+                Import::Glob { parents } => {
+                    if let Some(module) = self.resolve_module_path(module_path, &parents) {
+                        if exists(&module, name) && !glob_matches.contains(&module) {
+                            glob_matches.push(module);
+                        }
+                    }
+                }
+                Import::Name { .. } => {}
+            }
+        }
+        match glob_matches.as_slice() {
+            [module] => Some((module.clone(), name.to_string())),
+            _ => None,
+        }
+    }
+
     /// Get field names of a struct by its simple name (e.g., "CreateTwoMints").
     ///
     /// Returns None if the struct is not found.
@@ -92,6 +214,60 @@ impl CrateContext {
     }
 }
 
+/// One name or glob brought into scope by a `use` item.
+enum Import {
+    /// `use parents::ident;` or `use parents::ident as alias;`
+    Name {
+        parents: Vec<String>,
+        ident: String,
+        alias: String,
+    },
+    /// `use parents::*;`
+    Glob { parents: Vec<String> },
+}
+
+/// Flatten a `use` tree into its imports, with `parents` holding the path so far.
+fn flatten_use_tree(tree: &syn::UseTree, parents: &mut Vec<String>, imports: &mut Vec<Import>) {
+    let mut name = |ident: &syn::Ident, alias: &syn::Ident, parents: &[String]| {
+        // `use parent::{self}` imports the parent module itself
+        let (parents, ident) = if ident == "self" {
+            match parents.split_last() {
+                Some((last, parents)) => (parents.to_vec(), last.clone()),
+                None => return,
+            }
+        } else {
+            (parents.to_vec(), ident.to_string())
+        };
+        let alias = if alias == "self" {
+            ident.clone()
+        } else {
+            alias.to_string()
+        };
+        imports.push(Import::Name {
+            parents,
+            ident,
+            alias,
+        });
+    };
+    match tree {
+        syn::UseTree::Path(use_path) => {
+            parents.push(use_path.ident.to_string());
+            flatten_use_tree(&use_path.tree, parents, imports);
+            parents.pop();
+        }
+        syn::UseTree::Name(use_name) => name(&use_name.ident, &use_name.ident, parents),
+        syn::UseTree::Rename(rename) => name(&rename.ident, &rename.rename, parents),
+        syn::UseTree::Glob(_) => imports.push(Import::Glob {
+            parents: parents.clone(),
+        }),
+        syn::UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten_use_tree(tree, parents, imports);
+            }
+        }
+    }
+}
+
 /// A parsed module containing its items.
 pub struct ParsedModule {
     /// All items in the module
@@ -179,6 +355,32 @@ impl ParsedModule {
         })
     }
 
+    /// Get all enum items in this module.
+    fn enums(&self) -> impl Iterator<Item = &ItemEnum> {
+        self.items.iter().filter_map(|item| {
+            if let Item::Enum(e) = item {
+                Some(e)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Names and globs imported by the `use` items of this module.
+    ///
+    /// Imports with a leading `::` name other crates and are skipped.
+    fn imports(&self) -> Vec<Import> {
+        let mut imports = Vec::new();
+        for item in &self.items {
+            if let Item::Use(item_use) = item {
+                if item_use.leading_colon.is_none() {
+                    flatten_use_tree(&item_use.tree, &mut Vec::new(), &mut imports);
+                }
+            }
+        }
+        imports
+    }
+
     /// Recursively process inline modules to find nested module declarations.
     ///
     /// For inline modules like `mod foo { mod bar { ... } }`, this traverses
@@ -307,6 +509,61 @@ fn find_module_file(parent_dir: &Path, parent_name: &str, mod_name: &str) -> Opt
 mod tests {
     use super::*;
 
+    /// Crate context for a single `lib.rs` with `source`
+    fn parse_source(name: &str, source: &str) -> CrateContext {
+        let dir = std::env::temp_dir().join(format!(
+            "instruction-decoder-derive-{}-{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let root = dir.join("lib.rs");
+        std::fs::write(&root, source).unwrap();
+        let ctx = CrateContext::parse(&root).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        ctx
+    }
+
+    #[test]
+    fn test_resolve_enum_by_path() {
+        let ctx = parse_source(
+            "resolve-enum",
+            r#"
+                pub enum Adjustment { Reset, Add(u64), Scale { numerator: u64 } }
+                pub enum Generic<T> { Value(T) }
+                pub mod v1 {
+                    pub enum Adjustment { Reset, Add(u64) }
+                }
+                pub mod program {
+                    use super::*;
+                    use crate::v1 as legacy;
+                    use other_crate::Mode;
+                }
+            "#,
+        );
+        let variants = |path: &str| {
+            ctx.resolve_enum("crate::program", &syn::parse_str(path).unwrap())
+                .map(|item_enum| item_enum.variants.len())
+        };
+
+        // Through the `super::*` glob
+        assert_eq!(variants("Adjustment"), Some(3));
+        // The colliding name in `v1`, reached through the glob, a rename or as written
+        assert_eq!(variants("v1::Adjustment"), Some(2));
+        assert_eq!(variants("legacy::Adjustment"), Some(2));
+        assert_eq!(variants("crate::v1::Adjustment"), Some(2));
+        assert_eq!(variants("super::v1::Adjustment"), Some(2));
+        assert_eq!(variants("self::super::Adjustment"), Some(3));
+        // Not local items, or generic
+        assert_eq!(variants("Mode"), None);
+        assert_eq!(variants("other_crate::Adjustment"), None);
+        assert_eq!(variants("::v1::Adjustment"), None);
+        assert_eq!(variants("Generic"), None);
+
+        assert_eq!(ctx.module_path("program"), Some("crate::program"));
+        assert_eq!(ctx.module_path("missing"), None);
+    }
+
     #[test]
     fn test_find_module_file_sibling() {
         // This test verifies the path construction logic
@@ -122,4 +122,66 @@ mod tests {
         let result = derive_instruction_decoder_impl(input);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_derive_discriminator_schemes() {
+        let derive = |tokens: proc_macro2::TokenStream| {
+            derive_instruction_decoder_impl(syn::parse2(tokens).unwrap())
+        };
+
+        // shank_u8 implies 1-byte discriminators (the variant index)
+        let output = derive(quote! {
+            #[instruction_decoder(
+                program_id = "11111111111111111111111111111111",
+                discriminator = "shank_u8"
+            )]
+            pub enum TestInstruction {
+                Init,
+                Process,
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(output.contains("1u8 =>"));
+
+        let sighash = crate::utils::compute_sighash_discriminator("init");
+        let output = derive(quote! {
+            #[instruction_decoder(
+                program_id = "11111111111111111111111111111111",
+                discriminator = "sighash_no_namespace"
+            )]
+            pub enum TestInstruction {
+                Init,
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(output.contains(&quote!([#(#sighash),*]).to_string()));
+
+        let err = derive(quote! {
+            #[instruction_decoder(
+                program_id = "11111111111111111111111111111111",
+                discriminator = "shank_u8",
+                discriminator_size = 8
+            )]
+            pub enum TestInstruction {
+                Init,
+            }
+        })
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("discriminator = \"shank_u8\" requires discriminator_size = 1"));
+
+        assert!(derive(quote! {
+            #[instruction_decoder(
+                program_id = "11111111111111111111111111111111",
+                discriminator = "murmur"
+            )]
+            pub enum TestInstruction {
+                Init,
+            }
+        })
+        .is_err());
+    }
 }
@@ -70,7 +70,30 @@ use crate::utils::into_token_stream;
 /// ```
 ///
 /// This generates a `MyInstructionDecoder` struct that implements `InstructionDecoder`.
-#[proc_macro_derive(InstructionDecoder, attributes(instruction_decoder, discriminator))]
+///
+/// ## Field display
+///
+/// Fields are rendered by type. Use `#[decoder(...)]` on a field to override this:
+///
+/// - `#[decoder(display = "amount(decimals = 6)")]` - integer with 6 decimals (`1500000` -> `1.500000`)
+/// - `#[decoder(display = "pubkey")]` - `[u8; 32]` as a base58 pubkey
+/// - `#[decoder(display = "hex")]` - byte array as lowercase hex
+/// - `#[decoder(enum = "MyEnum")]` - `u8` as the variant name of `MyEnum` (requires `BorshDeserialize + Debug`)
+///
+/// ```rust,ignore
+/// pub enum MyInstruction {
+///     Deposit {
+///         #[decoder(display = "amount(decimals = 6)")]
+///         amount: u64,
+///         #[decoder(enum = "DepositKind")]
+///         kind: u8,
+///     },
+/// }
+/// ```
+#[proc_macro_derive(
+    InstructionDecoder,
+    attributes(instruction_decoder, discriminator, decoder)
+)]
 pub fn derive_instruction_decoder(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     into_token_stream(derive_impl::derive_instruction_decoder_impl(input))
@@ -96,6 +119,13 @@ pub fn derive_instruction_decoder(input: TokenStream) -> TokenStream {
 ///
 /// This generates a `MyProgramInstructionDecoder` struct that implements `InstructionDecoder`.
 /// The program_id can also be omitted if `declare_id!` is used inside the module.
+///
+/// Instruction arguments accept the same `#[decoder(...)]` display overrides as
+/// derive fields; the attributes are removed from the emitted module.
+///
+/// Arguments typed as an enum declared in the crate are rendered by variant name,
+/// with the data of tuple and struct variants as child fields. Other arguments
+/// use their `Debug` output.
 #[proc_macro_attribute]
 pub fn instruction_decoder(attr: TokenStream, item: TokenStream) -> TokenStream {
     into_token_stream(attribute_impl::instruction_decoder_attr(
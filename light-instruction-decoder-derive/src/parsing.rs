@@ -12,7 +12,8 @@
 //! #[instruction_decoder(
 //!     program_id = "Base58ProgramId...",
 //!     program_name = "My Program",      // optional, defaults to enum name
-//!     discriminator_size = 8            // optional: 1, 4, or 8 (default: 8)
+//!     discriminator_size = 8,           // optional: 1, 4, or 8 (default: 8)
+//!     discriminator = "anchor"          // optional: "anchor", "sighash_no_namespace" or "shank_u8"
 //! )]
 //! ```
 //!
@@ -25,6 +26,17 @@
 //! )]
 //! #[discriminator = 5]                  // Explicit discriminator value (for 1/4 byte modes)
 //! ```
+//!
+//! ## Field display (`#[decoder(...)]`)
+//!
+//! On variant fields of the derive macro and on instruction arguments of the
+//! attribute macro:
+//! ```ignore
+//! #[decoder(display = "amount(decimals = 6)")] // integer rendered with 6 decimals: 1.500000
+//! #[decoder(display = "pubkey")]               // [u8; 32] rendered as base58
+//! #[decoder(display = "hex")]                  // byte array rendered as lowercase hex
+//! #[decoder(enum = "MyEnum")]                  // u8 rendered as the MyEnum variant name
+//! ```
 
 use darling::{FromDeriveInput, FromMeta, FromVariant};
 use proc_macro2::TokenStream as TokenStream2;
@@ -36,9 +48,19 @@ use crate::{
     utils::{parse_program_id_bytes, pascal_to_display, validate_discriminator_size},
 };
 
-/// Default discriminator size (Anchor-style 8 bytes).
-fn default_discriminator_size() -> u8 {
-    8
+/// Scheme used to compute discriminators of variants without `#[discriminator]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, FromMeta)]
+pub enum DiscriminatorScheme {
+    /// `sha256("global:<snake_case_name>")[..8]`, as generated by Anchor
+    #[default]
+    #[darling(rename = "anchor")]
+    Anchor,
+    /// `sha256("<snake_case_name>")[..8]`, without Anchor's `global:` namespace
+    #[darling(rename = "sighash_no_namespace")]
+    SighashNoNamespace,
+    /// Variant index as a single byte, as used by shank and native programs
+    #[darling(rename = "shank_u8")]
+    ShankU8,
 }
 
 /// Top-level attributes for `#[derive(InstructionDecoder)]`.
@@ -55,9 +77,14 @@ pub struct InstructionDecoderArgs {
     #[darling(default)]
     pub program_name: Option<String>,
 
-    /// Discriminator size in bytes: 1 (native), 4 (system), or 8 (Anchor)
-    #[darling(default = "default_discriminator_size")]
-    pub discriminator_size: u8,
+    /// Discriminator size in bytes: 1 (native), 4 (system), or 8 (Anchor).
+    /// Defaults to 1 for the `shank_u8` scheme and 8 otherwise
+    #[darling(default)]
+    pub discriminator_size: Option<u8>,
+
+    /// Discriminator scheme for variants without an explicit `#[discriminator]`
+    #[darling(default)]
+    pub discriminator: DiscriminatorScheme,
 
     /// Enum data for accessing variants
     pub data: darling::ast::Data<VariantDecoderArgs, ()>,
@@ -76,9 +103,32 @@ impl InstructionDecoderArgs {
         parse_program_id_bytes(&self.program_id, span)
     }
 
+    /// Discriminator size in bytes, explicit or implied by the discriminator scheme.
+    pub fn discriminator_size(&self) -> u8 {
+        self.discriminator_size.unwrap_or(match self.discriminator {
+            DiscriminatorScheme::ShankU8 => 1,
+            DiscriminatorScheme::Anchor | DiscriminatorScheme::SighashNoNamespace => 8,
+        })
+    }
+
     /// Validate all arguments.
     pub fn validate(&self) -> syn::Result<()> {
-        validate_discriminator_size(self.discriminator_size, self.ident.span())?;
+        let size = self.discriminator_size();
+        validate_discriminator_size(size, self.ident.span())?;
+        let required = match self.discriminator {
+            DiscriminatorScheme::Anchor => None,
+            DiscriminatorScheme::SighashNoNamespace => Some(("sighash_no_namespace", 8)),
+            DiscriminatorScheme::ShankU8 => Some(("shank_u8", 1)),
+        };
+        if let Some((scheme, required_size)) = required.filter(|&(_, required)| required != size) {
+            return Err(syn::Error::new(
+                self.ident.span(),
+                format!(
+                    "discriminator = \"{}\" requires discriminator_size = {}",
+                    scheme, required_size
+                ),
+            ));
+        }
         // Validate program_id can be parsed (will error at code gen time if invalid)
         let _ = self.program_id_bytes(self.ident.span())?;
         Ok(())
@@ -158,6 +208,12 @@ pub struct VariantDecoderArgs {
     #[darling(default)]
     pub pretty_formatter: Option<syn::Path>,
 
+    /// Optional function producing structured (nested) fields from parsed params.
+    /// The function must have signature `fn(&ParamsType, &[AccountMeta]) -> Vec<DecodedField>`.
+    /// When specified, this takes precedence over `pretty_formatter`.
+    #[darling(default)]
+    pub fields_formatter: Option<syn::Path>,
+
     /// Optional function to resolve account names dynamically from parsed params.
     /// The function must have signature `fn(&ParamsType, &[AccountMeta]) -> Vec<String>`.
     /// When specified, this takes precedence over `accounts` and `account_names`.
@@ -359,6 +415,78 @@ pub fn parse_explicit_discriminator(
     Ok(None)
 }
 
+/// Display override for a decoded field, set with `#[decoder(...)]`.
+#[derive(Debug, Clone)]
+pub enum FieldDisplay {
+    /// Integer amount with a fixed number of decimals
+    Amount { decimals: u32 },
+    /// 32-byte array rendered as a base58 pubkey
+    Pubkey,
+    /// Byte array rendered as lowercase hex
+    Hex,
+    /// u8 discriminant rendered as the variant name of a borsh-deserializable enum
+    Enum(syn::Path),
+}
+
+impl FieldDisplay {
+    /// Parse a `display = "..."` spec.
+    fn from_spec(spec: &syn::LitStr) -> syn::Result<Self> {
+        let value = spec.value();
+        let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+        match compact.as_str() {
+            "pubkey" => return Ok(FieldDisplay::Pubkey),
+            "hex" => return Ok(FieldDisplay::Hex),
+            _ => {}
+        }
+        if let Some(decimals) = compact
+            .strip_prefix("amount(decimals=")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let decimals = decimals
+                .parse::<u32>()
+                .ok()
+                .filter(|decimals| *decimals <= 38)
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(spec, "amount decimals must be an integer from 0 to 38")
+                })?;
+            return Ok(FieldDisplay::Amount { decimals });
+        }
+        Err(syn::Error::new_spanned(
+            spec,
+            format!(
+                "unsupported display spec \"{}\" (expected \"amount(decimals = N)\", \"pubkey\" or \"hex\")",
+                value
+            ),
+        ))
+    }
+}
+
+/// Parse a field display override from `#[decoder(display = "...")]` or `#[decoder(enum = "Type")]`.
+pub fn parse_field_display(attrs: &[syn::Attribute]) -> syn::Result<Option<FieldDisplay>> {
+    let mut display = None;
+    for attr in attrs {
+        if !attr.path().is_ident("decoder") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let parsed = if meta.path.is_ident("display") {
+                FieldDisplay::from_spec(&meta.value()?.parse::<syn::LitStr>()?)?
+            } else if meta.path.is_ident("enum") {
+                let lit = meta.value()?.parse::<syn::LitStr>()?;
+                FieldDisplay::Enum(lit.parse::<syn::Path>()?)
+            } else {
+                return Err(meta.error("expected `display` or `enum`"));
+            };
+            if display.is_some() {
+                return Err(meta.error("only one of `display` or `enum` may be set per field"));
+            }
+            display = Some(parsed);
+            Ok(())
+        })?;
+    }
+    Ok(display)
+}
+
 /// Represents either a literal pubkey or a path reference for program ID.
 #[derive(Debug, Clone)]
 pub enum ProgramIdSource {
@@ -566,6 +694,45 @@ mod tests {
         assert!(disc.is_none());
     }
 
+    fn field_display(variant: syn::Variant) -> syn::Result<Option<FieldDisplay>> {
+        parse_field_display(&variant.fields.iter().next().unwrap().attrs)
+    }
+
+    #[test]
+    fn test_parse_field_display() {
+        let display = field_display(parse_quote! {
+            Deposit { #[decoder(display = "amount(decimals = 6)")] amount: u64 }
+        });
+        assert!(matches!(
+            display.unwrap(),
+            Some(FieldDisplay::Amount { decimals: 6 })
+        ));
+
+        let display = field_display(parse_quote! {
+            Deposit { #[decoder(enum = "MyEnum")] kind: u8 }
+        });
+        match display.unwrap() {
+            Some(FieldDisplay::Enum(path)) => assert!(path.is_ident("MyEnum")),
+            other => panic!("expected enum display, got {:?}", other),
+        }
+
+        let display = field_display(parse_quote! { Deposit { key: [u8; 32] } });
+        assert!(display.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_field_display_invalid() {
+        let display = field_display(parse_quote! {
+            Deposit { #[decoder(display = "percent")] rate: u16 }
+        });
+        assert!(display.is_err());
+
+        let display = field_display(parse_quote! {
+            Deposit { #[decoder(display = "hex", enum = "MyEnum")] kind: u8 }
+        });
+        assert!(display.is_err());
+    }
+
     #[test]
     fn test_extract_struct_name_simple() {
         let ty: syn::Type = parse_quote!(CreateTwoMints);
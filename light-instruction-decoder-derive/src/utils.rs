@@ -2,7 +2,7 @@
 //!
 //! This module provides common utilities for:
 //! - Case conversion (snake_case, PascalCase)
-//! - Anchor discriminator computation
+//! - Anchor and sighash discriminator computation
 //! - Error handling helpers
 //! - Program ID validation
 
@@ -68,7 +68,10 @@ pub(crate) fn to_pascal_case(name: &str) -> String {
 /// Compute Anchor-style instruction discriminator.
 ///
 /// Anchor discriminators are the first 8 bytes of SHA256("global:<instruction_name>")
-/// where instruction_name is in snake_case.
+/// where instruction_name is in snake_case. Must stay in sync with
+/// `light_instruction_decoder::anchor_instruction_discriminator`, which this
+/// proc-macro crate cannot depend on; `test_derived_discriminators_match_runtime`
+/// in the tests crate checks that they agree.
 ///
 /// # Examples
 ///
@@ -77,7 +80,19 @@ pub(crate) fn to_pascal_case(name: &str) -> String {
 /// assert_eq!(disc.len(), 8);
 /// ```
 pub(crate) fn compute_anchor_discriminator(instruction_name: &str) -> [u8; 8] {
-    let preimage = format!("global:{}", instruction_name);
+    compute_sighash_discriminator(&format!("global:{}", instruction_name))
+}
+
+/// Compute a discriminator as the first 8 bytes of SHA256(preimage), without
+/// Anchor's `global:` namespace.
+///
+/// # Examples
+///
+/// ```ignore
+/// let disc = compute_sighash_discriminator("create_record");
+/// assert_ne!(disc, compute_anchor_discriminator("create_record"));
+/// ```
+pub(crate) fn compute_sighash_discriminator(preimage: &str) -> [u8; 8] {
     let hash = Sha256::digest(preimage.as_bytes());
     let mut discriminator = [0u8; 8];
     discriminator.copy_from_slice(&hash[..8]);
@@ -175,6 +190,18 @@ mod tests {
         assert_eq!(disc, disc2);
     }
 
+    #[test]
+    fn test_compute_sighash_discriminator() {
+        assert_eq!(
+            compute_sighash_discriminator("global:create_record"),
+            compute_anchor_discriminator("create_record")
+        );
+        assert_ne!(
+            compute_sighash_discriminator("create_record"),
+            compute_anchor_discriminator("create_record")
+        );
+    }
+
     #[test]
     fn test_pascal_to_display() {
         assert_eq!(pascal_to_display("CreateRecord"), "Create Record");
@@ -0,0 +1,301 @@
+//! Builders for constructing transaction logs by hand
+//!
+//! Useful to test formatters and snapshot conversion without running a
+//! transaction:
+//!
+//! ```
+//! use light_instruction_decoder::{InstructionLogBuilder, TransactionLogBuilder};
+//! use solana_pubkey::Pubkey;
+//!
+//! let payer = Pubkey::new_unique();
+//! let log = TransactionLogBuilder::new()
+//!     .instruction(
+//!         InstructionLogBuilder::new(Pubkey::new_unique())
+//!             .name("Initialize")
+//!             .field("amount", "100")
+//!             .account(payer, true, true)
+//!             .inner(InstructionLogBuilder::new(Pubkey::default()).name("CreateAccount")),
+//!     )
+//!     .build();
+//! assert_eq!(log.instructions[0].inner_instructions[0].depth, 1);
+//! ```
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+
+use crate::{
+    types::get_program_name, AccountStateSnapshot, DecodedField, DecodedInstruction,
+    EnhancedInstructionLog, EnhancedTransactionLog, TransactionStatus,
+};
+
+/// Fluent builder for [`EnhancedTransactionLog`]
+///
+/// Starts from an empty log with the default signature, slot 0 and
+/// [`TransactionStatus::Unknown`].
+#[derive(Debug, Clone)]
+pub struct TransactionLogBuilder {
+    log: EnhancedTransactionLog,
+}
+
+impl TransactionLogBuilder {
+    /// Create a builder for an empty transaction log
+    pub fn new() -> Self {
+        Self {
+            log: EnhancedTransactionLog::new(Signature::default(), 0),
+        }
+    }
+
+    /// Set the transaction signature
+    pub fn signature(mut self, signature: Signature) -> Self {
+        self.log.signature = signature;
+        self
+    }
+
+    /// Set the slot
+    pub fn slot(mut self, slot: u64) -> Self {
+        self.log.slot = slot;
+        self
+    }
+
+    /// Set the block time (Unix timestamp)
+    pub fn block_time(mut self, block_time: i64) -> Self {
+        self.log.block_time = Some(block_time);
+        self
+    }
+
+    /// Set the transaction status
+    pub fn status(mut self, status: TransactionStatus) -> Self {
+        self.log.status = status;
+        self
+    }
+
+    /// Set the transaction label
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.log.label = Some(label.into());
+        self
+    }
+
+    /// Set the fee in lamports
+    pub fn fee(mut self, fee: u64) -> Self {
+        self.log.fee = fee;
+        self
+    }
+
+    /// Set the compute units used and the compute budget
+    pub fn compute(mut self, used: u64, total: u64) -> Self {
+        self.log.compute_used = used;
+        self.log.compute_total = total;
+        self
+    }
+
+    /// Append a top-level instruction
+    ///
+    /// Its index is its position among the top-level instructions.
+    pub fn instruction(mut self, instruction: InstructionLogBuilder) -> Self {
+        let index = self.log.instructions.len();
+        self.log.instructions.push(instruction.finish(index, 0));
+        self
+    }
+
+    /// Record the pre and post state of an account
+    pub fn account_state(mut self, pubkey: Pubkey, state: AccountStateSnapshot) -> Self {
+        self.log
+            .account_states
+            .get_or_insert_with(Default::default)
+            .insert(pubkey, state);
+        self
+    }
+
+    /// Set the formatted program logs
+    pub fn program_logs(mut self, logs: impl Into<String>) -> Self {
+        self.log.program_logs_pretty = logs.into();
+        self
+    }
+
+    /// Finish the transaction log
+    pub fn build(self) -> EnhancedTransactionLog {
+        self.log
+    }
+}
+
+impl Default for TransactionLogBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fluent builder for [`EnhancedInstructionLog`]
+///
+/// The program name defaults to the built-in name for `program_id`, else
+/// "Unknown Program (<id>)". Index and depth are assigned when the instruction
+/// is added to a [`TransactionLogBuilder`] or nested with
+/// [`InstructionLogBuilder::inner`].
+#[derive(Debug, Clone)]
+pub struct InstructionLogBuilder {
+    instruction: EnhancedInstructionLog,
+}
+
+impl InstructionLogBuilder {
+    /// Create a builder for an instruction of `program_id`
+    pub fn new(program_id: Pubkey) -> Self {
+        let program_name = get_program_name(&program_id, None);
+        Self {
+            instruction: EnhancedInstructionLog::new(0, program_id, program_name),
+        }
+    }
+
+    /// Set the program name
+    pub fn program_name(mut self, name: impl Into<String>) -> Self {
+        self.instruction.program_name = name.into();
+        self
+    }
+
+    /// Set the instruction name, as a decoder would
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.decoded().name = name.clone();
+        self.instruction.instruction_name = Some(name);
+        self
+    }
+
+    /// Append a decoded field
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.decoded().fields.push(DecodedField::new(name, value));
+        self
+    }
+
+    /// Append an account
+    pub fn account(mut self, pubkey: Pubkey, signer: bool, writable: bool) -> Self {
+        self.instruction.accounts.push(AccountMeta {
+            pubkey,
+            is_signer: signer,
+            is_writable: writable,
+        });
+        self
+    }
+
+    /// Set the raw instruction data
+    pub fn data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.instruction.data = data.into();
+        self
+    }
+
+    /// Set the compute units consumed
+    pub fn compute_consumed(mut self, units: u64) -> Self {
+        self.instruction.compute_consumed = Some(units);
+        self
+    }
+
+    /// Mark the instruction as the one the transaction failed at
+    pub fn failed(mut self) -> Self {
+        self.instruction.success = false;
+        self
+    }
+
+    /// Append an inner instruction (a CPI made by this instruction)
+    pub fn inner(mut self, instruction: InstructionLogBuilder) -> Self {
+        let index = self.instruction.inner_instructions.len();
+        self.instruction
+            .inner_instructions
+            .push(instruction.finish(index, 1));
+        self
+    }
+
+    /// Finish a standalone instruction log at index 0 and depth 0
+    pub fn build(self) -> EnhancedInstructionLog {
+        self.finish(0, 0)
+    }
+
+    /// Decoded instruction, created on first use with the current name
+    fn decoded(&mut self) -> &mut DecodedInstruction {
+        let name = self
+            .instruction
+            .instruction_name
+            .clone()
+            .unwrap_or_default();
+        self.instruction.decoded_instruction.get_or_insert_with(|| {
+            DecodedInstruction::with_fields_and_accounts(name, Vec::new(), Vec::new())
+        })
+    }
+
+    /// Place the instruction at `index` and `depth`, shifting inner instructions below it
+    ///
+    /// Paths are relative to this instruction until it is placed under a parent.
+    fn finish(mut self, index: usize, depth: usize) -> EnhancedInstructionLog {
+        self.instruction.index = index;
+        place(&mut self.instruction, depth, vec![index]);
+        self.instruction
+    }
+}
+
+fn place(instruction: &mut EnhancedInstructionLog, depth: usize, path: Vec<usize>) {
+    instruction.depth = depth;
+    for (i, inner) in instruction.inner_instructions.iter_mut().enumerate() {
+        place(inner, depth + 1, [path.as_slice(), &[i]].concat());
+    }
+    instruction.path = path;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EnhancedLoggingConfig, TransactionFormatter};
+
+    #[test]
+    fn test_builders_nest_instructions() {
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let payer = Pubkey::new_from_array([1; 32]);
+        let log = TransactionLogBuilder::new()
+            .slot(42)
+            .status(TransactionStatus::Success)
+            .instruction(
+                InstructionLogBuilder::new(program_id)
+                    .program_name("Counter")
+                    .field("amount", "100")
+                    .name("Increment")
+                    .account(payer, true, true)
+                    .inner(
+                        InstructionLogBuilder::new(Pubkey::default())
+                            .name("Transfer")
+                            .inner(InstructionLogBuilder::new(Pubkey::default())),
+                    ),
+            )
+            .instruction(InstructionLogBuilder::new(Pubkey::default()).failed())
+            .account_state(
+                payer,
+                AccountStateSnapshot {
+                    lamports_before: 10,
+                    lamports_after: 5,
+                    ..Default::default()
+                },
+            )
+            .build();
+
+        assert_eq!(log.slot, 42);
+        let outer = &log.instructions[0];
+        assert_eq!(outer.instruction_name.as_deref(), Some("Increment"));
+        let decoded = outer.decoded_instruction.as_ref().unwrap();
+        assert_eq!(decoded.name, "Increment");
+        assert_eq!(decoded.fields, vec![DecodedField::new("amount", "100")]);
+        assert_eq!(outer.accounts, vec![AccountMeta::new(payer, true)]);
+        let inner = &outer.inner_instructions[0];
+        assert_eq!(inner.program_name, "System Program");
+        assert_eq!(inner.depth, 1);
+        assert_eq!(inner.inner_instructions[0].depth, 2);
+        assert_eq!(inner.inner_instructions[0].path, vec![0, 0, 0]);
+        assert_eq!(log.instructions[1].index, 1);
+        assert!(!log.instructions[1].success);
+        assert_eq!(
+            log.account_states.as_ref().unwrap()[&payer].lamports_after,
+            5
+        );
+
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("Counter"));
+        assert!(output.contains("Increment"));
+        assert!(output.contains("amount: 100"));
+    }
+}
@@ -1,13 +1,20 @@
 //! Configuration types for enhanced logging
 
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
 
-use crate::{registry::DecoderRegistry, InstructionDecoder};
+use crate::{registry::DecoderRegistry, types::EnhancedInstructionLog, InstructionDecoder};
 
 /// Configuration for enhanced transaction logging
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EnhancedLoggingConfig {
     /// Whether enhanced logging is enabled
     pub enabled: bool,
@@ -29,12 +36,95 @@ pub struct EnhancedLoggingConfig {
     pub show_compression_instruction_data: bool,
     /// Truncate byte arrays: Some((first, last)) shows first N and last N elements; None disables
     pub truncate_byte_arrays: Option<(usize, usize)>,
+    /// Maximum output width: narrows the transaction box and drops low-priority account table columns and shortens pubkeys to fit; None keeps full width
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub table_width: Option<TableWidth>,
+    /// Unit used to render lamport amounts (fees, account balances, decoded lamport fields)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lamport_display: LamportDisplay,
+    /// Render top-level instructions grouped by program instead of in execution order
+    /// (formatting only; each entry keeps its execution number)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub group_by_program: bool,
+    /// Known PDA derivations used to label accounts (see [`EnhancedLoggingConfig::with_pda_seeds`])
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pda_seeds: Vec<PdaSeeds>,
+    /// How the transaction signature is rendered in headers and snapshots
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub signature_display: SignatureDisplay,
+    /// Maximum size of one formatted transaction in bytes; longer output is cut
+    /// with a truncation marker. None disables the limit
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_output_bytes: Option<usize>,
+    /// Never print to stderr, not even failed transactions; the log file is still written
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub suppress_console_output: bool,
+    /// Emphasize account table rows whose absolute lamport change exceeds this many
+    /// lamports and dim the rest; None disables highlighting
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub highlight_lamport_delta_threshold: Option<u64>,
+    /// Add the parsed program log invoke tree to snapshots (`program_log_tree`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub include_log_tree: bool,
+    /// Show whether each account is rent-exempt after the transaction, in the account
+    /// table (`Rent` column) and in snapshots (`account_states`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_rent_status: bool,
+    /// Prefix inner instruction headers with their position path (e.g. `[#1.3.2]`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_instruction_paths: bool,
+    /// Show the account roles section listing each account's signer/writable use per instruction
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_account_roles: bool,
+    /// Panic when any instruction can't be decoded, see [`EnhancedLoggingConfig::with_strict_unknown`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub strict_unknown: bool,
+    /// Known mint decimals used to render token amounts, see [`EnhancedLoggingConfig::with_mint_decimals`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mint_decimals: HashMap<Pubkey, u8>,
+    /// Decoded fields kept in snapshots, in this order, see [`EnhancedLoggingConfig::with_field_filter`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub field_filter: Option<Vec<String>>,
+    /// Write the log file gzip-compressed to `target/instruction_decoder.log.gz`
+    /// (feature `flate2`, ignored without it)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compress_log: bool,
+    /// Only show accounts owned by these programs in account state tables,
+    /// see [`EnhancedLoggingConfig::with_account_owner_filter`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub account_owner_filter: Option<HashSet<Pubkey>>,
+    /// How nested instructions are indented
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub indent: IndentStyle,
+    /// Show decoded `proof` fields (raw zk proof bytes of Light instructions); by
+    /// default only `has_proof` is shown
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_proof_bytes: bool,
+    /// Show the serialized transaction size in the header, each top-level instruction's
+    /// share of it, and the message size in snapshots (`total_message_size`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_transaction_size: bool,
+    /// Line written to the log file before each transaction block, with `{tx_number}`
+    /// replaced by the transaction number; None writes the blocks back to back
+    #[cfg_attr(feature = "serde", serde(default = "transaction_separator_default"))]
+    pub transaction_separator: Option<String>,
+    /// Called for every decoded instruction, see [`EnhancedLoggingConfig::with_instruction_callback`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_instruction: Option<InstructionCallback>,
     /// Decoder registry containing built-in and custom decoders
     /// Wrapped in Arc so it can be shared across clones instead of being lost
-    #[serde(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     decoder_registry: Option<Arc<DecoderRegistry>>,
 }
 
+/// Default [`EnhancedLoggingConfig::transaction_separator`]
+pub const DEFAULT_TRANSACTION_SEPARATOR: &str =
+    "==================== Transaction #{tx_number} ====================";
+
+fn transaction_separator_default() -> Option<String> {
+    Some(DEFAULT_TRANSACTION_SEPARATOR.to_string())
+}
+
 impl Clone for EnhancedLoggingConfig {
     fn clone(&self) -> Self {
         // Arc clone shares the underlying DecoderRegistry across clones
@@ -50,6 +140,28 @@ impl Clone for EnhancedLoggingConfig {
             max_cpi_depth: self.max_cpi_depth,
             show_compression_instruction_data: self.show_compression_instruction_data,
             truncate_byte_arrays: self.truncate_byte_arrays,
+            table_width: self.table_width,
+            lamport_display: self.lamport_display,
+            group_by_program: self.group_by_program,
+            pda_seeds: self.pda_seeds.clone(),
+            signature_display: self.signature_display,
+            max_output_bytes: self.max_output_bytes,
+            suppress_console_output: self.suppress_console_output,
+            highlight_lamport_delta_threshold: self.highlight_lamport_delta_threshold,
+            include_log_tree: self.include_log_tree,
+            show_rent_status: self.show_rent_status,
+            show_instruction_paths: self.show_instruction_paths,
+            show_account_roles: self.show_account_roles,
+            strict_unknown: self.strict_unknown,
+            mint_decimals: self.mint_decimals.clone(),
+            field_filter: self.field_filter.clone(),
+            compress_log: self.compress_log,
+            account_owner_filter: self.account_owner_filter.clone(),
+            indent: self.indent,
+            show_proof_bytes: self.show_proof_bytes,
+            show_transaction_size: self.show_transaction_size,
+            transaction_separator: self.transaction_separator.clone(),
+            on_instruction: self.on_instruction.clone(),
             decoder_registry: self.decoder_registry.clone(),
         }
     }
@@ -68,13 +180,46 @@ impl Default for EnhancedLoggingConfig {
             max_cpi_depth: 60,
             show_compression_instruction_data: false,
             truncate_byte_arrays: Some((2, 2)),
+            table_width: None,
+            lamport_display: LamportDisplay::Lamports,
+            group_by_program: false,
+            pda_seeds: Vec::new(),
+            signature_display: SignatureDisplay::Full,
+            max_output_bytes: None,
+            suppress_console_output: false,
+            highlight_lamport_delta_threshold: None,
+            include_log_tree: false,
+            show_rent_status: false,
+            show_instruction_paths: false,
+            show_account_roles: false,
+            strict_unknown: false,
+            mint_decimals: HashMap::new(),
+            field_filter: None,
+            compress_log: false,
+            account_owner_filter: None,
+            indent: IndentStyle::Bars,
+            show_proof_bytes: false,
+            show_transaction_size: false,
+            transaction_separator: transaction_separator_default(),
+            on_instruction: None,
             decoder_registry: Some(Arc::new(DecoderRegistry::new())),
         }
     }
 }
 
+/// Callback invoked with each decoded instruction, shared across config clones.
+#[derive(Clone)]
+pub struct InstructionCallback(pub Arc<dyn Fn(&EnhancedInstructionLog) + Send + Sync>);
+
+impl std::fmt::Debug for InstructionCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("InstructionCallback")
+    }
+}
+
 /// Verbosity levels for transaction logging
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LogVerbosity {
     /// Only instruction hierarchy and status
     Brief,
@@ -86,8 +231,164 @@ pub enum LogVerbosity {
     Full,
 }
 
+/// Width limit for account tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TableWidth {
+    /// Detect the width of the attached terminal (full width if there is none)
+    Auto,
+    /// Fixed width in columns
+    Fixed(usize),
+}
+
+impl TableWidth {
+    /// Resolve to a column count, or None if no limit applies
+    pub fn resolve(&self) -> Option<usize> {
+        match self {
+            TableWidth::Auto => terminal_size::terminal_size().map(|(width, _)| width.0 as usize),
+            TableWidth::Fixed(width) => Some(*width),
+        }
+    }
+}
+
+/// How transaction signatures are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SignatureDisplay {
+    /// Full base58 signature
+    #[default]
+    Full,
+    /// First and last 4 characters (e.g. `5KtP…9xQr`)
+    Short,
+    /// Left out of headers; `<hidden>` in snapshots
+    Hidden,
+}
+
+impl SignatureDisplay {
+    /// Render a signature in this style, or None if it is hidden
+    pub fn format(&self, signature: &Signature) -> Option<String> {
+        match self {
+            SignatureDisplay::Full => Some(signature.to_string()),
+            SignatureDisplay::Short => {
+                let full = signature.to_string();
+                if full.len() <= 8 {
+                    return Some(full);
+                }
+                Some(format!("{}…{}", &full[..4], &full[full.len() - 4..]))
+            }
+            SignatureDisplay::Hidden => None,
+        }
+    }
+}
+
+/// How the formatter indents nested (CPI) instructions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IndentStyle {
+    /// A `│` guide per nesting level, every inner instruction connected with `└─`
+    #[default]
+    Bars,
+    /// This many spaces per nesting level, e.g. where box-drawing characters
+    /// don't render
+    Spaces(usize),
+    /// Tree connectors: `├─` for instructions followed by a sibling, `└─` for the
+    /// last one, with guides only where a branch continues
+    Tree,
+}
+
+/// Known PDA derivation: seeds and program id, plus the canonical address and bump
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PdaSeeds {
+    pub program_id: Pubkey,
+    /// Label shown for matching accounts (e.g. "counter")
+    pub label: String,
+    pub seeds: Vec<Vec<u8>>,
+    /// Result of `Pubkey::try_find_program_address`, derived once on construction
+    pub address: Pubkey,
+    pub bump: u8,
+}
+
+impl PdaSeeds {
+    /// Derive the canonical address and bump for `seeds`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the seeds are invalid (more than 15 seeds or a seed longer
+    /// than 32 bytes) or no bump yields an off-curve address; see
+    /// [`PdaSeeds::try_new`].
+    pub fn new(program_id: Pubkey, label: impl Into<String>, seeds: &[&[u8]]) -> Self {
+        Self::try_new(program_id, label, seeds).expect("invalid PDA seeds")
+    }
+
+    /// Derive the canonical address and bump for `seeds`, or `None` if the
+    /// seeds are invalid or no bump yields an off-curve address
+    pub fn try_new(program_id: Pubkey, label: impl Into<String>, seeds: &[&[u8]]) -> Option<Self> {
+        let (address, bump) = Pubkey::try_find_program_address(seeds, &program_id)?;
+        Some(Self {
+            program_id,
+            label: label.into(),
+            seeds: seeds.iter().map(|seed| seed.to_vec()).collect(),
+            address,
+            bump,
+        })
+    }
+
+    /// Bump seed if `pubkey` is the canonical PDA for these seeds
+    pub fn matches(&self, pubkey: &Pubkey) -> Option<u8> {
+        (*pubkey == self.address).then_some(self.bump)
+    }
+}
+
+/// Unit used to render lamport amounts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LamportDisplay {
+    /// Raw lamports (e.g. `1,500,000,000`); the fee header keeps its short SOL summary
+    #[default]
+    Lamports,
+    /// SOL with full precision (e.g. `1.500000000 SOL`)
+    Sol,
+    /// Raw lamports followed by SOL (e.g. `1500000000 (1.5 SOL)`)
+    Both,
+}
+
+impl LamportDisplay {
+    /// Render a lamport amount in this unit
+    pub fn format(&self, lamports: u64) -> String {
+        match self {
+            LamportDisplay::Lamports => lamports.to_string(),
+            LamportDisplay::Sol => format!("{} SOL", format_sol(lamports, false)),
+            LamportDisplay::Both => format!("{} ({} SOL)", lamports, format_sol(lamports, true)),
+        }
+    }
+}
+
+/// Format lamports as SOL without going through floating point
+///
+/// With `trim` set, trailing zeros of the fractional part are dropped (`1.5`),
+/// otherwise all nine decimals are kept (`1.500000000`).
+fn format_sol(lamports: u64, trim: bool) -> String {
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    let whole = lamports / LAMPORTS_PER_SOL;
+    let fraction = format!("{:09}", lamports % LAMPORTS_PER_SOL);
+    let fraction = if trim {
+        fraction.trim_end_matches('0')
+    } else {
+        fraction.as_str()
+    };
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
 impl EnhancedLoggingConfig {
     /// Create config optimized for debugging
+    ///
+    /// Full verbosity, and every transaction is printed to stderr. [`Default`] uses
+    /// standard verbosity and only prints failed transactions.
     pub fn debug() -> Self {
         Self {
             enabled: true,
@@ -100,10 +401,44 @@ impl EnhancedLoggingConfig {
             max_cpi_depth: 60,
             show_compression_instruction_data: false,
             truncate_byte_arrays: Some((2, 2)),
+            table_width: None,
+            lamport_display: LamportDisplay::Lamports,
+            group_by_program: false,
+            pda_seeds: Vec::new(),
+            signature_display: SignatureDisplay::Full,
+            max_output_bytes: None,
+            suppress_console_output: false,
+            highlight_lamport_delta_threshold: None,
+            include_log_tree: false,
+            show_rent_status: false,
+            show_instruction_paths: false,
+            show_account_roles: false,
+            strict_unknown: false,
+            mint_decimals: HashMap::new(),
+            field_filter: None,
+            compress_log: false,
+            account_owner_filter: None,
+            indent: IndentStyle::Bars,
+            show_proof_bytes: false,
+            show_transaction_size: false,
+            transaction_separator: transaction_separator_default(),
+            on_instruction: None,
             decoder_registry: Some(Arc::new(DecoderRegistry::new())),
         }
     }
 
+    /// Create config that decodes like [`debug`](Self::debug) but only writes the log file
+    ///
+    /// Nothing is printed to stderr, not even failed transactions, so CI keeps the
+    /// full log file as an artifact without console noise.
+    pub fn quiet() -> Self {
+        Self {
+            log_events: false,
+            suppress_console_output: true,
+            ..Self::debug()
+        }
+    }
+
     /// Create config optimized for CI/production
     pub fn minimal() -> Self {
         Self {
@@ -117,6 +452,28 @@ impl EnhancedLoggingConfig {
             max_cpi_depth: 60,
             show_compression_instruction_data: false,
             truncate_byte_arrays: Some((2, 2)),
+            table_width: None,
+            lamport_display: LamportDisplay::Lamports,
+            group_by_program: false,
+            pda_seeds: Vec::new(),
+            signature_display: SignatureDisplay::Full,
+            max_output_bytes: None,
+            suppress_console_output: false,
+            highlight_lamport_delta_threshold: None,
+            include_log_tree: false,
+            show_rent_status: false,
+            show_instruction_paths: false,
+            show_account_roles: false,
+            strict_unknown: false,
+            mint_decimals: HashMap::new(),
+            field_filter: None,
+            compress_log: false,
+            account_owner_filter: None,
+            indent: IndentStyle::Bars,
+            show_proof_bytes: false,
+            show_transaction_size: false,
+            transaction_separator: transaction_separator_default(),
+            on_instruction: None,
             decoder_registry: Some(Arc::new(DecoderRegistry::new())),
         }
     }
@@ -134,12 +491,69 @@ impl EnhancedLoggingConfig {
             }
         }
         // Create new registry if none exists or Arc has multiple references
-        let mut registry = DecoderRegistry::new();
+        let mut registry = match &self.decoder_registry {
+            Some(existing) if !existing.has_builtins() => DecoderRegistry::empty(),
+            _ => DecoderRegistry::new(),
+        };
         registry.register_all(decoders);
         self.decoder_registry = Some(Arc::new(registry));
         self
     }
 
+    /// Set a decoder for all programs without a registered decoder
+    ///
+    /// See [`DecoderRegistry::set_fallback`]. As with `with_decoders`, if the registry
+    /// Arc has been cloned, a new registry without custom decoders is created.
+    pub fn with_fallback_decoder(mut self, decoder: Box<dyn InstructionDecoder>) -> Self {
+        if let Some(registry) = self.decoder_registry.as_mut().and_then(Arc::get_mut) {
+            registry.set_fallback(decoder);
+            return self;
+        }
+        let mut registry = match &self.decoder_registry {
+            Some(existing) if !existing.has_builtins() => DecoderRegistry::empty(),
+            _ => DecoderRegistry::new(),
+        };
+        registry.set_fallback(decoder);
+        self.decoder_registry = Some(Arc::new(registry));
+        self
+    }
+
+    /// Include or leave out the built-in decoders (listed on [`DecoderRegistry::new`])
+    ///
+    /// Custom decoders are kept either way, so `with_builtin_decoders(false)` can be
+    /// combined with [`EnhancedLoggingConfig::with_decoders`] in any order to decode
+    /// only your own programs. As with `with_decoders`, if the registry Arc has been
+    /// cloned, a new registry without custom decoders is created.
+    pub fn with_builtin_decoders(mut self, enabled: bool) -> Self {
+        if self
+            .decoder_registry
+            .as_mut()
+            .and_then(Arc::get_mut)
+            .is_none()
+        {
+            self.decoder_registry = Some(Arc::new(DecoderRegistry::new()));
+        }
+        if let Some(registry) = self.decoder_registry.as_mut().and_then(Arc::get_mut) {
+            if enabled {
+                registry.register_builtins();
+            } else {
+                registry.remove_builtins();
+            }
+        }
+        self
+    }
+
+    /// Use a prebuilt registry shared with other configs
+    ///
+    /// Build the registry once (e.g. in a `LazyLock`) and hand out the `Arc` to every
+    /// config: clones of the config and each decode only borrow it, nothing is
+    /// rebuilt. Since the registry is shared, a later `with_decoders` or
+    /// `with_builtin_decoders` on this config builds a new registry instead.
+    pub fn with_shared_registry(mut self, registry: Arc<DecoderRegistry>) -> Self {
+        self.decoder_registry = Some(registry);
+        self
+    }
+
     /// Get or create the decoder registry
     pub fn get_decoder_registry(&mut self) -> &DecoderRegistry {
         if self.decoder_registry.is_none() {
@@ -174,4 +588,339 @@ impl EnhancedLoggingConfig {
         self.log_events = false;
         self
     }
+
+    /// Limit the transaction box and its account tables to the given width
+    pub fn with_table_width(mut self, width: TableWidth) -> Self {
+        self.table_width = Some(width);
+        self
+    }
+
+    /// Render lamport amounts in the given unit
+    pub fn with_lamport_display(mut self, display: LamportDisplay) -> Self {
+        self.lamport_display = display;
+        self
+    }
+
+    /// Group top-level instructions by program in the formatted output
+    pub fn with_group_by_program(mut self) -> Self {
+        self.group_by_program = true;
+        self
+    }
+
+    /// Render transaction signatures in the given style
+    pub fn with_signature_display(mut self, display: SignatureDisplay) -> Self {
+        self.signature_display = display;
+        self
+    }
+
+    /// Emphasize account table rows whose lamport change exceeds `threshold` lamports
+    /// (in either direction), so transfers stand out from rent and fees
+    pub fn with_highlight_lamport_delta_threshold(mut self, threshold: u64) -> Self {
+        self.highlight_lamport_delta_threshold = Some(threshold);
+        self
+    }
+
+    /// Include the program log invoke tree in snapshots
+    pub fn with_log_tree(mut self) -> Self {
+        self.include_log_tree = true;
+        self
+    }
+
+    /// Show the rent-exemption status of accounts with captured state
+    ///
+    /// Accounts left below the rent-exempt minimum for their data length would be
+    /// reaped, which usually means the account was sized or funded wrong.
+    pub fn with_rent_status(mut self) -> Self {
+        self.show_rent_status = true;
+        self
+    }
+
+    /// Indent nested instructions in the given style
+    pub fn with_indent(mut self, indent: IndentStyle) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Show how many bytes the transaction and each top-level instruction take up
+    ///
+    /// For tuning transactions towards the 1232-byte packet limit (e.g. before moving
+    /// accounts into an address lookup table). Transactions close to the limit get
+    /// a warning regardless of this setting.
+    pub fn with_transaction_size(mut self) -> Self {
+        self.show_transaction_size = true;
+        self
+    }
+
+    /// Write `separator` to the log file before each transaction block
+    ///
+    /// `{tx_number}` in the separator is replaced by the transaction number. ANSI
+    /// codes are stripped like from the rest of the log file.
+    pub fn with_transaction_separator(mut self, separator: impl Into<String>) -> Self {
+        self.transaction_separator = Some(separator.into());
+        self
+    }
+
+    /// Keep the 128 proof bytes of Light System, Light Token and batched Account
+    /// Compression instructions
+    ///
+    /// Decoded Light instructions always show `has_proof: true/false`; the proof
+    /// itself (a top-level `proof` field) differs between runs and is left out of
+    /// formatted output and snapshots unless this is set. The decoded instruction
+    /// always keeps it.
+    pub fn with_proof_bytes(mut self) -> Self {
+        self.show_proof_bytes = true;
+        self
+    }
+
+    /// Prefix inner instruction headers with their position path
+    ///
+    /// The path is 1-based like warning locations, e.g. `[#1.3.2]` is inner
+    /// instruction 2 of inner instruction 3 of top-level instruction 1 (see
+    /// [`EnhancedInstructionLog::location`]).
+    pub fn with_instruction_paths(mut self) -> Self {
+        self.show_instruction_paths = true;
+        self
+    }
+
+    /// Show a section listing, for every account, the instructions it was passed to
+    /// with its role and signer/writable flags
+    ///
+    /// Accounts whose signer or writable flag differs between instructions are
+    /// flagged (see [`crate::AccountRoles::has_inconsistent_access`]).
+    pub fn with_account_roles(mut self) -> Self {
+        self.show_account_roles = true;
+        self
+    }
+
+    /// Panic when decoding a transaction leaves any instruction undecoded
+    ///
+    /// For test suites that must decode every instruction: an unknown program or
+    /// an instruction its decoder rejects fails the test with the instruction's
+    /// path, program id and discriminator instead of rendering "Unknown Program".
+    /// Applies to every decode function in [`crate::litesvm`] (`decode_transaction`,
+    /// `decode_message`, `decode_transaction_borrowed`, `decode_base64_transaction`)
+    /// and everything built on them, including [`crate::TransactionLogger`]. The
+    /// check runs once account states are attached.
+    pub fn with_strict_unknown(mut self) -> Self {
+        self.strict_unknown = true;
+        self
+    }
+
+    /// Call `callback` for every decoded top-level and inner instruction
+    ///
+    /// Instructions are visited in execution order (each parent before its inner
+    /// instructions) once a transaction is fully decoded, e.g. to count transfers
+    /// or collect touched mints. Formatting is unaffected.
+    pub fn with_instruction_callback(
+        mut self,
+        callback: impl Fn(&EnhancedInstructionLog) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_instruction = Some(InstructionCallback(Arc::new(callback)));
+        self
+    }
+
+    /// Whether a top-level decoded field named `name` is shown in formatted output
+    /// and snapshots: everything but `proof`, unless proof bytes are requested
+    pub(crate) fn shows_field(&self, name: &str) -> bool {
+        self.show_proof_bytes || name != "proof"
+    }
+
+    /// Invoke the instruction callback, if any, for `instructions` and all their inner instructions
+    pub(crate) fn notify_instructions(&self, instructions: &[EnhancedInstructionLog]) {
+        let Some(InstructionCallback(callback)) = &self.on_instruction else {
+            return;
+        };
+        fn visit(
+            instructions: &[EnhancedInstructionLog],
+            callback: &(dyn Fn(&EnhancedInstructionLog) + Send + Sync),
+        ) {
+            for instruction in instructions {
+                callback(instruction);
+                visit(&instruction.inner_instructions, callback);
+            }
+        }
+        visit(instructions, callback.as_ref());
+    }
+
+    /// Cut formatted transactions longer than `max_bytes`
+    ///
+    /// Formatting stops once the limit is reached, so the remaining instructions
+    /// and sections are never rendered.
+    pub fn with_max_output_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_output_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Register a known PDA derivation
+    ///
+    /// Accounts equal to `Pubkey::try_find_program_address(seeds, program_id)` are
+    /// labeled as PDA `label` with their bump in account tables and snapshots.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the seeds are invalid, like [`PdaSeeds::new`]. Build the
+    /// derivation with [`PdaSeeds::try_new`] and push it onto `pda_seeds` to
+    /// handle that case instead.
+    pub fn with_pda_seeds(
+        mut self,
+        program_id: Pubkey,
+        label: impl Into<String>,
+        seeds: &[&[u8]],
+    ) -> Self {
+        self.pda_seeds.push(PdaSeeds::new(program_id, label, seeds));
+        self
+    }
+
+    /// Register the decimals of a token mint
+    ///
+    /// Token instructions (SPL Token, Token-2022, Light Token) whose account
+    /// labeled as the mint is `mint` get an `amount_ui` field next to `amount`, e.g.
+    /// `1.000000` for 1000000 base units with 6 decimals. Checked instructions carry
+    /// their own decimals and don't need this.
+    ///
+    /// Only instructions that carry a mint (`MintTo`, `Burn` and the `*Checked`
+    /// variants) get a UI amount. An unchecked `Transfer` has no mint account, so
+    /// its amount stays raw even when the mint is registered.
+    pub fn with_mint_decimals(mut self, mint: Pubkey, decimals: u8) -> Self {
+        self.mint_decimals.insert(mint, decimals);
+        self
+    }
+
+    /// Keep only the named decoded fields in snapshots, in the given order
+    ///
+    /// Applies to the `decoded_fields` of every instruction in
+    /// [`crate::litesvm::decode_transaction_snapshot`] and its `_with_logs` variant,
+    /// e.g. to leave volatile fields out of insta snapshots. Names an instruction
+    /// doesn't have are skipped; formatted output is unaffected.
+    pub fn with_field_filter(
+        mut self,
+        fields: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.field_filter = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only show accounts owned by one of `owners` in the account state table
+    ///
+    /// Rows of other accounts are left out of the table of each top-level
+    /// instruction and counted in a line below it. An account is shown when its
+    /// owner before or after the transaction matches, so accounts the transaction
+    /// creates for (or assigns to) a filtered program are kept. Accounts without a
+    /// captured state are hidden too.
+    pub fn with_account_owner_filter(mut self, owners: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.account_owner_filter = Some(owners.into_iter().collect());
+        self
+    }
+
+    /// Write the log file gzip-compressed, for suites that log many transactions
+    ///
+    /// Transactions go to `target/instruction_decoder.log.gz` instead of
+    /// `target/instruction_decoder.log`; read it with `zcat` or `gunzip`.
+    #[cfg(feature = "flate2")]
+    pub fn with_compressed_log(mut self) -> Self {
+        self.compress_log = true;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::AccountMeta;
+
+    use super::*;
+
+    fn instruction(program_id: Pubkey) -> EnhancedInstructionLog {
+        EnhancedInstructionLog::new(0, program_id, String::new())
+    }
+
+    #[test]
+    fn test_without_builtin_decoders() {
+        let transfer = || {
+            let mut ix = instruction(Pubkey::default()); // System Program
+            ix.accounts = vec![
+                AccountMeta::new(Pubkey::new_unique(), true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ];
+            ix.data = [2u32.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat();
+            ix
+        };
+
+        let config = EnhancedLoggingConfig::default().with_builtin_decoders(false);
+        let mut ix = transfer();
+        ix.decode(&config);
+        assert_eq!(ix.instruction_name, None);
+        assert_eq!(
+            ix.decode_warnings(&config),
+            [(
+                crate::DecodeWarningKind::UnknownProgram,
+                format!("no decoder registered for program {}", ix.program_id)
+            )]
+        );
+
+        // Custom decoders survive toggling built-ins in either order
+        let config = EnhancedLoggingConfig::default()
+            .with_decoders(vec![Box::new(crate::programs::SystemInstructionDecoder)])
+            .with_builtin_decoders(false);
+        let registry = config.decoder_registry().unwrap();
+        assert_eq!(registry.program_ids().count(), 1);
+        let mut ix = transfer();
+        ix.decode(&config);
+        assert_eq!(ix.instruction_name.as_deref(), Some("Transfer"));
+
+        let config = config.with_builtin_decoders(true);
+        assert_eq!(
+            config.decoder_registry().unwrap().program_ids().count(),
+            DecoderRegistry::new().program_ids().count()
+        );
+    }
+
+    #[test]
+    fn test_pda_seeds_try_new_rejects_invalid_seeds() {
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let pda = PdaSeeds::try_new(program_id, "counter", &[b"counter"]).unwrap();
+        assert_eq!(
+            Pubkey::find_program_address(&[b"counter"], &program_id),
+            (pda.address, pda.bump)
+        );
+        assert_eq!(pda.matches(&pda.address), Some(pda.bump));
+
+        // Seeds are limited to 32 bytes each
+        assert!(PdaSeeds::try_new(program_id, "counter", &[&[0; 33]]).is_none());
+    }
+
+    #[test]
+    fn test_quiet_config() {
+        let quiet = EnhancedLoggingConfig::quiet();
+        let debug = EnhancedLoggingConfig::debug();
+        assert!(!quiet.log_events);
+        assert!(quiet.suppress_console_output);
+        assert!(!debug.suppress_console_output);
+        assert_eq!(quiet.verbosity, debug.verbosity);
+        assert_eq!(quiet.show_account_changes, debug.show_account_changes);
+        assert_eq!(quiet.truncate_byte_arrays, debug.truncate_byte_arrays);
+    }
+
+    #[test]
+    fn test_shared_registry_is_not_rebuilt() {
+        let registry = Arc::new(DecoderRegistry::new());
+        let config = EnhancedLoggingConfig::default().with_shared_registry(registry.clone());
+
+        for _ in 0..100 {
+            let mut ix = instruction(Pubkey::default()); // System Program
+            ix.accounts = vec![
+                AccountMeta::new(Pubkey::new_unique(), true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ];
+            ix.data = [2u32.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat();
+            let config = config.clone();
+            ix.decode(&config);
+            assert_eq!(ix.instruction_name.as_deref(), Some("Transfer"));
+            assert!(std::ptr::eq(
+                config.decoder_registry().unwrap(),
+                Arc::as_ptr(&registry)
+            ));
+        }
+        // Only `registry` and `config` hold the Arc; every clone was dropped
+        assert_eq!(Arc::strong_count(&registry), 2);
+    }
 }
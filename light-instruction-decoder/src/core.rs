@@ -1,10 +1,13 @@
 //! Core types for instruction decoding.
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use solana_instruction::AccountMeta;
 use solana_pubkey::Pubkey;
 
 /// A decoded instruction field for display.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DecodedField {
     /// Field name
     pub name: String,
@@ -32,10 +35,70 @@ impl DecodedField {
             children,
         }
     }
+
+    /// Create a marker for a field that failed to decode.
+    ///
+    /// `offset` is the byte position in the instruction data (including the
+    /// discriminator) where decoding stopped. Fields decoded before it are kept.
+    pub fn decode_error(
+        name: impl Into<String>,
+        offset: usize,
+        reason: impl std::fmt::Display,
+    ) -> Self {
+        Self::new(
+            name,
+            format!("{}{}: {}>", DECODE_ERROR_PREFIX, offset, reason),
+        )
+    }
+
+    /// Whether this field was created by [`DecodedField::decode_error`].
+    pub fn is_decode_error(&self) -> bool {
+        self.value.starts_with(DECODE_ERROR_PREFIX)
+    }
+}
+
+const DECODE_ERROR_PREFIX: &str = "<decode error at offset ";
+
+/// Resolve packed account indices to the pubkeys they reference, e.g. `[ABCD…WXYZ, EFGH…STUV]`.
+///
+/// Light instructions address trees and queues by `u8` index into the packed
+/// accounts, which start at `packed_start` in the instruction's accounts. Indices
+/// without a matching account render as `OUT_OF_BOUNDS(index)`.
+pub fn format_packed_pubkeys(
+    indices: &[u8],
+    accounts: &[AccountMeta],
+    packed_start: usize,
+) -> String {
+    let pubkeys: Vec<String> = indices
+        .iter()
+        .map(|&index| {
+            accounts
+                .get(packed_start + index as usize)
+                .map(|account| {
+                    let pubkey = account.pubkey.to_string();
+                    format!("{}…{}", &pubkey[..4], &pubkey[pubkey.len() - 4..])
+                })
+                .unwrap_or_else(|| format!("OUT_OF_BOUNDS({})", index))
+        })
+        .collect();
+    format!("[{}]", pubkeys.join(", "))
+}
+
+/// Lowercase hex of `bytes`, without a `0x` prefix
+#[cfg(not(target_os = "solana"))]
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(DIGITS[(byte >> 4) as usize] as char);
+        hex.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    hex
 }
 
 /// Result of decoding an instruction.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DecodedInstruction {
     /// Human-readable instruction name (e.g., "Transfer", "MintTo")
     pub name: String,
@@ -43,6 +106,9 @@ pub struct DecodedInstruction {
     pub fields: Vec<DecodedField>,
     /// Account names in order (index corresponds to account position)
     pub account_names: Vec<String>,
+    /// Number of accounts the instruction expects (None if unknown or resolved dynamically)
+    #[cfg_attr(feature = "serde", serde(default))]
+    expected_accounts: Option<usize>,
 }
 
 impl DecodedInstruction {
@@ -56,10 +122,46 @@ impl DecodedInstruction {
             name: name.into(),
             fields,
             account_names,
+            expected_accounts: None,
+        }
+    }
+
+    /// Set the number of accounts the instruction expects.
+    pub fn with_expected_accounts(mut self, count: usize) -> Self {
+        self.expected_accounts = Some(count);
+        self
+    }
+
+    /// Number of accounts the instruction expects, if known.
+    pub fn expected_accounts(&self) -> Option<usize> {
+        self.expected_accounts
+    }
+
+    /// Compare the expected account count against the provided one.
+    ///
+    /// Only missing accounts are reported; extra trailing accounts are treated
+    /// as remaining accounts, which many programs accept.
+    pub fn check_account_count(&self, provided: usize) -> Option<AccountCountMismatch> {
+        match self.expected_accounts {
+            Some(expected) if provided < expected => Some(AccountCountMismatch {
+                expected,
+                actual: provided,
+            }),
+            _ => None,
         }
     }
 }
 
+/// Mismatch between the accounts an instruction expects and those provided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountCountMismatch {
+    /// Number of accounts the decoded instruction expects
+    pub expected: usize,
+    /// Number of accounts the transaction provided
+    pub actual: usize,
+}
+
 /// Trait for instruction decoders - each program implements this.
 pub trait InstructionDecoder: Send + Sync {
     /// Program ID this decoder handles.
@@ -68,7 +170,23 @@ pub trait InstructionDecoder: Send + Sync {
     /// Human-readable program name (e.g., "Compressed Token Program").
     fn program_name(&self) -> &'static str;
 
+    /// Number of leading instruction data bytes that select the instruction
+    /// (1, 4 or 8), if the decoder matches on a fixed-size discriminator.
+    fn discriminator_size(&self) -> Option<usize> {
+        None
+    }
+
     /// Decode instruction data into a structured representation.
     /// Returns None if decoding fails or instruction is unknown.
     fn decode(&self, data: &[u8], accounts: &[AccountMeta]) -> Option<DecodedInstruction>;
+
+    /// Validate the provided accounts against a decoded instruction.
+    /// The default reports missing accounts based on `expected_accounts`.
+    fn validate(
+        &self,
+        decoded: &DecodedInstruction,
+        accounts: &[AccountMeta],
+    ) -> Option<AccountCountMismatch> {
+        decoded.check_account_count(accounts.len())
+    }
 }
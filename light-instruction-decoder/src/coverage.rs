@@ -0,0 +1,252 @@
+//! Decoder coverage across a batch of transaction logs
+//!
+//! Meant for scenario suites: decode every transaction, then check which
+//! programs and instructions the registered decoders still miss.
+//!
+//! ```
+//! use light_instruction_decoder::{coverage_report, InstructionLogBuilder, TransactionLogBuilder};
+//! use solana_pubkey::Pubkey;
+//!
+//! let log = TransactionLogBuilder::new()
+//!     .instruction(InstructionLogBuilder::new(Pubkey::default()).name("Transfer"))
+//!     .instruction(InstructionLogBuilder::new(Pubkey::new_unique()).data([7, 0, 1]))
+//!     .build();
+//! let report = coverage_report(&[log]);
+//! assert_eq!((report.decoded_instructions, report.total_instructions), (1, 2));
+//! assert_eq!(report.unknown[0].discriminator, Some(vec![7, 0, 1]));
+//! ```
+
+use std::{cmp::Reverse, collections::BTreeMap};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use solana_pubkey::Pubkey;
+
+use crate::{EnhancedInstructionLog, EnhancedTransactionLog};
+
+/// How many instructions of a batch of transactions were decoded, see [`coverage_report`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CoverageReport {
+    /// Number of transaction logs in the batch
+    pub transactions: usize,
+    /// Instructions in the batch, top-level and inner
+    pub total_instructions: usize,
+    /// Instructions a decoder handled
+    pub decoded_instructions: usize,
+    /// Per-program counts, programs with the most unknown instructions first
+    pub programs: Vec<ProgramCoverage>,
+    /// Undecoded instruction kinds, most frequent first
+    pub unknown: Vec<UnknownInstruction>,
+}
+
+/// Decoded and unknown instruction counts of one program
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ProgramCoverage {
+    pub program_id: Pubkey,
+    pub program_name: String,
+    pub decoded: usize,
+    pub unknown: usize,
+}
+
+/// Undecoded instructions sharing a program and discriminator
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct UnknownInstruction {
+    pub program_id: Pubkey,
+    pub program_name: String,
+    /// Leading data bytes (up to 8), see [`EnhancedInstructionLog::discriminator`];
+    /// None for instructions without data
+    pub discriminator: Option<Vec<u8>>,
+    /// Number of occurrences in the batch
+    pub count: usize,
+}
+
+impl CoverageReport {
+    /// Share of instructions that were decoded, in percent (100 for an empty batch)
+    pub fn decoded_percent(&self) -> f64 {
+        if self.total_instructions == 0 {
+            return 100.0;
+        }
+        self.decoded_instructions as f64 / self.total_instructions as f64 * 100.0
+    }
+}
+
+/// Count decoded and undecoded instructions (inner ones included) across `logs`
+///
+/// An instruction counts as decoded when a decoder produced a
+/// [`crate::DecodedInstruction`] for it. Undecoded instructions are grouped by
+/// program id and discriminator.
+pub fn coverage_report(logs: &[EnhancedTransactionLog]) -> CoverageReport {
+    fn walk(
+        instructions: &[EnhancedInstructionLog],
+        programs: &mut BTreeMap<Pubkey, ProgramCoverage>,
+        unknown: &mut BTreeMap<(Pubkey, Option<Vec<u8>>), UnknownInstruction>,
+    ) {
+        for instruction in instructions {
+            let program =
+                programs
+                    .entry(instruction.program_id)
+                    .or_insert_with(|| ProgramCoverage {
+                        program_id: instruction.program_id,
+                        program_name: instruction.program_name.clone(),
+                        decoded: 0,
+                        unknown: 0,
+                    });
+            if instruction.decoded_instruction.is_some() {
+                program.decoded += 1;
+            } else {
+                program.unknown += 1;
+                let discriminator = instruction.discriminator.clone().or_else(|| {
+                    Some(instruction.data[..instruction.data.len().min(8)].to_vec())
+                        .filter(|prefix| !prefix.is_empty())
+                });
+                unknown
+                    .entry((instruction.program_id, discriminator.clone()))
+                    .or_insert_with(|| UnknownInstruction {
+                        program_id: instruction.program_id,
+                        program_name: instruction.program_name.clone(),
+                        discriminator,
+                        count: 0,
+                    })
+                    .count += 1;
+            }
+            walk(&instruction.inner_instructions, programs, unknown);
+        }
+    }
+
+    let mut programs = BTreeMap::new();
+    let mut unknown = BTreeMap::new();
+    for log in logs {
+        walk(&log.instructions, &mut programs, &mut unknown);
+    }
+
+    let mut programs: Vec<ProgramCoverage> = programs.into_values().collect();
+    programs.sort_by_key(|program| Reverse(program.unknown));
+    let mut unknown: Vec<UnknownInstruction> = unknown.into_values().collect();
+    unknown.sort_by_key(|instruction| Reverse(instruction.count));
+    let decoded_instructions = programs.iter().map(|program| program.decoded).sum();
+    let unknown_instructions: usize = programs.iter().map(|program| program.unknown).sum();
+
+    CoverageReport {
+        transactions: logs.len(),
+        total_instructions: decoded_instructions + unknown_instructions,
+        decoded_instructions,
+        programs,
+        unknown,
+    }
+}
+
+impl std::fmt::Display for CoverageReport {
+    /// Summary line, per-program counts, then the unknown instructions, e.g.
+    ///
+    /// ```text
+    /// Decoder coverage: 3/4 instructions decoded (75.0%) across 2 transaction(s)
+    /// Programs:
+    ///   Unknown Program (Abc…): 0 decoded, 1 unknown
+    ///   System Program (111…): 3 decoded, 0 unknown
+    /// Unknown instructions:
+    ///   1x Unknown Program (Abc…) discriminator 0x0700
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Decoder coverage: {}/{} instructions decoded ({:.1}%) across {} transaction(s)",
+            self.decoded_instructions,
+            self.total_instructions,
+            self.decoded_percent(),
+            self.transactions
+        )?;
+        if !self.programs.is_empty() {
+            writeln!(f, "Programs:")?;
+            for program in &self.programs {
+                writeln!(
+                    f,
+                    "  {} ({}): {} decoded, {} unknown",
+                    program.program_name, program.program_id, program.decoded, program.unknown
+                )?;
+            }
+        }
+        if !self.unknown.is_empty() {
+            writeln!(f, "Unknown instructions:")?;
+            for instruction in &self.unknown {
+                let discriminator = match &instruction.discriminator {
+                    Some(bytes) => format!("0x{}", crate::core::hex(bytes)),
+                    None => "none".to_string(),
+                };
+                writeln!(
+                    f,
+                    "  {}x {} ({}) discriminator {}",
+                    instruction.count,
+                    instruction.program_name,
+                    instruction.program_id,
+                    discriminator
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InstructionLogBuilder, TransactionLogBuilder};
+
+    #[test]
+    fn test_coverage_report() {
+        let system = Pubkey::default();
+        let custom = Pubkey::new_from_array([9; 32]);
+        let transfer = || InstructionLogBuilder::new(system).name("Transfer");
+        let unknown = |data: &[u8]| {
+            InstructionLogBuilder::new(custom)
+                .program_name("Custom")
+                .data(data)
+        };
+        let logs = [
+            TransactionLogBuilder::new()
+                .instruction(unknown(&[1, 2]).inner(transfer()))
+                .instruction(transfer())
+                .build(),
+            TransactionLogBuilder::new()
+                .instruction(unknown(&[3]))
+                .instruction(unknown(&[1, 2]))
+                .instruction(unknown(&[]))
+                .build(),
+        ];
+
+        let report = coverage_report(&logs);
+        assert_eq!(report.transactions, 2);
+        assert_eq!(report.total_instructions, 6);
+        assert_eq!(report.decoded_instructions, 2);
+        let counts: Vec<(Pubkey, usize, usize)> = report
+            .programs
+            .iter()
+            .map(|program| (program.program_id, program.decoded, program.unknown))
+            .collect();
+        assert_eq!(counts, [(custom, 0, 4), (system, 2, 0)]);
+        let unknown: Vec<(Option<Vec<u8>>, usize)> = report
+            .unknown
+            .iter()
+            .map(|instruction| (instruction.discriminator.clone(), instruction.count))
+            .collect();
+        assert_eq!(
+            unknown,
+            [(Some(vec![1, 2]), 2), (None, 1), (Some(vec![3]), 1)]
+        );
+
+        let text = report.to_string();
+        assert!(text.starts_with(
+            "Decoder coverage: 2/6 instructions decoded (33.3%) across 2 transaction(s)\n"
+        ));
+        assert!(text.contains(&format!("  Custom ({}): 0 decoded, 4 unknown\n", custom)));
+        assert!(text.contains(&format!(
+            "Unknown instructions:\n  2x Custom ({}) discriminator 0x0102\n",
+            custom
+        )));
+        assert!(text.contains("discriminator none\n"));
+
+        assert_eq!(coverage_report(&[]).decoded_percent(), 100.0);
+    }
+}
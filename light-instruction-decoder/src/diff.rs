@@ -0,0 +1,407 @@
+//! Line diff between two transaction logs
+//!
+//! Meant for regression snapshots: run the same logical transaction before and
+//! after a change and snapshot only what differs.
+//!
+//! ```
+//! use light_instruction_decoder::{
+//!     format_log_diff, EnhancedLoggingConfig, InstructionLogBuilder, TransactionLogBuilder,
+//! };
+//! use solana_pubkey::Pubkey;
+//!
+//! let transfer = |lamports: &str| {
+//!     TransactionLogBuilder::new()
+//!         .instruction(
+//!             InstructionLogBuilder::new(Pubkey::default())
+//!                 .name("Transfer")
+//!                 .field("lamports", lamports),
+//!         )
+//!         .build()
+//! };
+//! let mut config = EnhancedLoggingConfig::default();
+//! config.use_colors = false;
+//! let diff = format_log_diff(&transfer("100"), &transfer("200"), &config);
+//! assert!(diff.contains("-    lamports: 100\n+    lamports: 200\n"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::{
+    types::instruction_location, Colors, DecodedField, EnhancedInstructionLog,
+    EnhancedLoggingConfig, EnhancedTransactionLog,
+};
+
+/// Unchanged lines kept around each change
+const CONTEXT_LINES: usize = 3;
+
+/// One line of a log diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Present in both logs
+    Same(String),
+    /// Only in the first (before) log
+    Removed(String),
+    /// Only in the second (after) log
+    Added(String),
+}
+
+impl DiffLine {
+    /// Line text without the diff marker
+    pub fn text(&self) -> &str {
+        match self {
+            DiffLine::Same(text) | DiffLine::Removed(text) | DiffLine::Added(text) => text,
+        }
+    }
+
+    fn is_change(&self) -> bool {
+        !matches!(self, DiffLine::Same(_))
+    }
+}
+
+impl EnhancedTransactionLog {
+    /// Line diff of the decoded instructions and account changes against `after`
+    ///
+    /// Both logs are rendered to plain lines (status, instructions with their
+    /// decoded fields and accounts, then pre/post account states) and compared.
+    /// Signature, slot, fees and compute units are left out so that re-running
+    /// the same transaction yields no changes.
+    pub fn diff(&self, after: &EnhancedTransactionLog) -> Vec<DiffLine> {
+        diff_lines(&log_lines(self), &log_lines(after))
+    }
+}
+
+/// Render the diff of two logs unified-diff style, for `insta::assert_snapshot!`
+///
+/// Changed lines are prefixed with `-` / `+` and shown with up to three unchanged
+/// lines of context; longer unchanged runs are elided behind `@@ -l,n +l,n @@`
+/// hunk headers. Returns "No changes" when the logs match. Colors follow
+/// `config.use_colors`.
+pub fn format_log_diff(
+    before: &EnhancedTransactionLog,
+    after: &EnhancedTransactionLog,
+    config: &EnhancedLoggingConfig,
+) -> String {
+    let lines = before.diff(after);
+    if !lines.iter().any(DiffLine::is_change) {
+        return "No changes\n".to_string();
+    }
+    let colors = Colors::new(config.use_colors);
+
+    // Line number (1-based) of each diff line in the before and after logs
+    let mut positions = Vec::with_capacity(lines.len());
+    let (mut old_line, mut new_line) = (1, 1);
+    for line in &lines {
+        positions.push((old_line, new_line));
+        match line {
+            DiffLine::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+
+    let mut output = String::new();
+    for (start, end) in hunks(&lines) {
+        let hunk = &lines[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+        let (old_start, new_start) = positions[start];
+        writeln!(
+            output,
+            "{}@@ -{},{} +{},{} @@{}",
+            colors.cyan, old_start, old_count, new_start, new_count, colors.reset
+        )
+        .expect("Failed to write hunk header");
+        for line in hunk {
+            let (marker, color) = match line {
+                DiffLine::Same(_) => (' ', ""),
+                DiffLine::Removed(_) => ('-', colors.red),
+                DiffLine::Added(_) => ('+', colors.green),
+            };
+            let reset = if color.is_empty() { "" } else { colors.reset };
+            writeln!(output, "{color}{marker}{}{reset}", line.text())
+                .expect("Failed to write diff line");
+        }
+    }
+    output
+}
+
+/// Ranges of `lines` to show: every change plus its context, overlapping ranges merged
+fn hunks(lines: &[DiffLine]) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, _) in lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.is_change())
+    {
+        let start = i.saturating_sub(CONTEXT_LINES);
+        let end = (i + CONTEXT_LINES + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    hunks
+}
+
+/// Longest-common-subsequence line diff
+fn diff_lines(before: &[String], after: &[String]) -> Vec<DiffLine> {
+    // common[i][j]: length of the LCS of before[i..] and after[j..]
+    let mut common = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            common[i][j] = if before[i] == after[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() && j < after.len() {
+        if before[i] == after[j] {
+            lines.push(DiffLine::Same(before[i].clone()));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(DiffLine::Removed(before[i].clone()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(after[j].clone()));
+            j += 1;
+        }
+    }
+    lines.extend(before[i..].iter().cloned().map(DiffLine::Removed));
+    lines.extend(after[j..].iter().cloned().map(DiffLine::Added));
+    lines
+}
+
+/// Plain line rendering of the parts of a log that are compared
+fn log_lines(log: &EnhancedTransactionLog) -> Vec<String> {
+    let mut lines = vec![format!("status: {}", log.status.text())];
+    for instruction in &log.instructions {
+        instruction_lines(instruction, &[instruction.index], &mut lines);
+    }
+    if let Some(states) = &log.account_states {
+        let mut states: Vec<_> = states.iter().collect();
+        states.sort_by_key(|(pubkey, _)| **pubkey);
+        lines.push("accounts:".to_string());
+        for (pubkey, state) in states {
+            let owner = if state.owner_after == state.owner {
+                state.owner.to_string()
+            } else {
+                format!("{} -> {}", state.owner, state.owner_after)
+            };
+            lines.push(format!(
+                "  {pubkey}: lamports {} -> {}, data_len {} -> {}, owner {owner}",
+                state.lamports_before,
+                state.lamports_after,
+                state.data_len_before,
+                state.data_len_after,
+            ));
+        }
+    }
+    lines
+}
+
+fn instruction_lines(
+    instruction: &EnhancedInstructionLog,
+    path: &[usize],
+    lines: &mut Vec<String>,
+) {
+    let indent = "  ".repeat(instruction.depth);
+    let name = instruction.instruction_name.as_deref().unwrap_or("Unknown");
+    let failed = if instruction.success { "" } else { " (failed)" };
+    lines.push(format!(
+        "{indent}{} {}: {name}{failed}",
+        instruction_location(path),
+        instruction.program_name
+    ));
+    if let Some(decoded) = &instruction.decoded_instruction {
+        for field in &decoded.fields {
+            field_lines(field, &format!("{indent}    "), lines);
+        }
+    }
+    for (i, account) in instruction.accounts.iter().enumerate() {
+        let role = instruction
+            .account_role(i)
+            .map(|role| format!(" {role}"))
+            .unwrap_or_default();
+        let access = match (account.is_signer, account.is_writable) {
+            (true, true) => " (signer, writable)",
+            (true, false) => " (signer)",
+            (false, true) => " (writable)",
+            (false, false) => "",
+        };
+        lines.push(format!(
+            "{indent}    account #{i}{role}: {}{access}",
+            account.pubkey
+        ));
+    }
+    for inner in &instruction.inner_instructions {
+        instruction_lines(inner, &[path, &[inner.index]].concat(), lines);
+    }
+}
+
+/// Lines of `field` and its children; multi-line values (e.g. a pretty formatter's
+/// summary) get one indented line each, so a change in one of them diffs as that line
+fn field_lines(field: &DecodedField, indent: &str, lines: &mut Vec<String>) {
+    if field.value.contains('\n') {
+        let value_indent = if field.name.is_empty() {
+            indent.to_string()
+        } else {
+            lines.push(format!("{indent}{}:", field.name));
+            format!("{indent}  ")
+        };
+        lines.extend(
+            field
+                .value
+                .lines()
+                .map(|line| format!("{value_indent}{line}")),
+        );
+    } else if field.value.is_empty() {
+        lines.push(format!("{indent}{}:", field.name));
+    } else {
+        lines.push(format!("{indent}{}: {}", field.name, field.value));
+    }
+    for child in &field.children {
+        field_lines(child, &format!("{indent}  "), lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_pubkey::Pubkey;
+
+    use super::*;
+    use crate::{AccountStateSnapshot, InstructionLogBuilder, TransactionLogBuilder};
+
+    fn transfer_log(lamports: u64, memo: Option<&str>) -> EnhancedTransactionLog {
+        let from = Pubkey::new_from_array([1; 32]);
+        let to = Pubkey::new_from_array([2; 32]);
+        let mut builder = TransactionLogBuilder::new()
+            .instruction(
+                InstructionLogBuilder::new(Pubkey::default())
+                    .name("Transfer")
+                    .field("lamports", lamports.to_string())
+                    .account(from, true, true)
+                    .account(to, false, true),
+            )
+            .account_state(
+                to,
+                AccountStateSnapshot {
+                    lamports_before: 0,
+                    lamports_after: lamports,
+                    ..Default::default()
+                },
+            );
+        if let Some(memo) = memo {
+            builder = builder.instruction(
+                InstructionLogBuilder::new(Pubkey::new_from_array([9; 32]))
+                    .program_name("Memo")
+                    .name("Memo")
+                    .field("text", memo),
+            );
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn test_format_log_diff() {
+        let mut config = EnhancedLoggingConfig::default();
+        config.use_colors = false;
+        let before = transfer_log(100, None);
+        let after = transfer_log(200, Some("hi"));
+
+        let to = Pubkey::new_from_array([2; 32]);
+        let expected = format!(
+            "@@ -1,7 +1,9 @@
+ status: Unknown
+ #1 System Program: Transfer
+-    lamports: 100
++    lamports: 200
+     account #0: {from} (signer, writable)
+     account #1: {to} (writable)
++#2 Memo: Memo
++    text: hi
+ accounts:
+-  {to}: lamports 0 -> 100, data_len 0 -> 0, owner {owner}
++  {to}: lamports 0 -> 200, data_len 0 -> 0, owner {owner}
+",
+            from = Pubkey::new_from_array([1; 32]),
+            owner = Pubkey::default(),
+        );
+        assert_eq!(format_log_diff(&before, &after, &config), expected);
+        assert_eq!(format_log_diff(&before, &before, &config), "No changes\n");
+    }
+
+    #[test]
+    fn test_format_log_diff_splits_multi_line_fields() {
+        let mut config = EnhancedLoggingConfig::default();
+        config.use_colors = false;
+        let log = |summary: &str| {
+            TransactionLogBuilder::new()
+                .instruction(
+                    InstructionLogBuilder::new(Pubkey::default())
+                        .name("Invoke")
+                        .field("", summary)
+                        .field("outputs", "out[0]: 10\nout[1]: 20\n"),
+                )
+                .build()
+        };
+        let output = format_log_diff(
+            &log("Accounts: in: 1, out: 2\nhas_proof: false\n"),
+            &log("Accounts: in: 1, out: 2\nhas_proof: true\n"),
+            &config,
+        );
+        assert_eq!(
+            output,
+            "@@ -1,7 +1,7 @@
+ status: Unknown
+ #1 System Program: Invoke
+     Accounts: in: 1, out: 2
+-    has_proof: false
++    has_proof: true
+     outputs:
+       out[0]: 10
+       out[1]: 20
+"
+        );
+    }
+
+    #[test]
+    fn test_format_log_diff_elides_unchanged_lines() {
+        let mut config = EnhancedLoggingConfig::default();
+        config.use_colors = false;
+        let log = |last: &str| {
+            let mut instruction = InstructionLogBuilder::new(Pubkey::default()).name("Batch");
+            for i in 0..10 {
+                instruction = instruction.field(format!("f{i}"), "0");
+            }
+            TransactionLogBuilder::new()
+                .instruction(instruction.field("last", last))
+                .build()
+        };
+        let output = format_log_diff(&log("a"), &log("b"), &config);
+        assert_eq!(
+            output,
+            "@@ -10,4 +10,4 @@
+     f7: 0
+     f8: 0
+     f9: 0
+-    last: a
++    last: b
+"
+        );
+    }
+}
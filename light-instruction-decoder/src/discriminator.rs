@@ -0,0 +1,49 @@
+//! Anchor discriminator helpers.
+//!
+//! The derive macro computes the same discriminators at compile time; these
+//! functions let tests and hand-written decoders build instruction data and
+//! account data without re-deriving them.
+
+use sha2::{Digest, Sha256};
+
+/// Anchor instruction discriminator: the first 8 bytes of SHA256("global:<name>").
+///
+/// `name` is the instruction's snake_case name as written in the program, e.g. `"initialize"`.
+pub fn anchor_instruction_discriminator(name: &str) -> [u8; 8] {
+    sighash(&format!("global:{}", name))
+}
+
+/// Anchor account discriminator: the first 8 bytes of SHA256("account:<Name>").
+///
+/// `name` is the account struct's name as written in the program, e.g. `"Counter"`.
+pub fn anchor_account_discriminator(name: &str) -> [u8; 8] {
+    sighash(&format!("account:{}", name))
+}
+
+fn sighash(preimage: &str) -> [u8; 8] {
+    let hash = Sha256::digest(preimage.as_bytes());
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+    discriminator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_instruction_discriminator() {
+        assert_eq!(
+            anchor_instruction_discriminator("initialize"),
+            [175, 175, 109, 31, 13, 152, 155, 237]
+        );
+    }
+
+    #[test]
+    fn test_anchor_account_discriminator() {
+        assert_eq!(
+            anchor_account_discriminator("Counter"),
+            [255, 176, 4, 245, 188, 253, 124, 25]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Hex fixtures for decoder unit tests
+//!
+//! Instruction data captured from real transactions (explorer, RPC logs) can be
+//! pasted into tests as hex instead of being rebuilt byte by byte:
+//!
+//! ```
+//! use light_instruction_decoder::decode_fixture;
+//! use solana_pubkey::Pubkey;
+//!
+//! // System Program transfer of 1000 lamports
+//! let decoded = decode_fixture(&Pubkey::default(), "02000000 e803000000000000", 2).unwrap();
+//! assert_eq!(decoded.name, "Transfer");
+//! ```
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{DecodedInstruction, DecoderRegistry};
+
+/// Decode hex instruction data with the built-in decoders
+///
+/// See [`decode_fixture_with`].
+pub fn decode_fixture(
+    program_id: &Pubkey,
+    hex_data: &str,
+    account_count: usize,
+) -> Option<DecodedInstruction> {
+    decode_fixture_with(&DecoderRegistry::new(), program_id, hex_data, account_count)
+}
+
+/// Decode hex instruction data with the decoder `registry` holds for `program_id`
+///
+/// `hex_data` may start with `0x` and contain whitespace. The instruction gets
+/// `account_count` dummy writable accounts with pubkeys `[1; 32]`, `[2; 32]`, ...
+/// Returns `None` when no decoder is registered or the decoder rejects the data.
+///
+/// # Panics
+///
+/// Panics if `hex_data` is not valid hex, since that is a broken fixture.
+pub fn decode_fixture_with(
+    registry: &DecoderRegistry,
+    program_id: &Pubkey,
+    hex_data: &str,
+    account_count: usize,
+) -> Option<DecodedInstruction> {
+    let data = parse_hex(hex_data);
+    let accounts: Vec<AccountMeta> = (1..=account_count)
+        .map(|i| AccountMeta::new(Pubkey::new_from_array([i as u8; 32]), false))
+        .collect();
+    registry
+        .decode(program_id, &data, &accounts)
+        .map(|(decoded, _)| decoded)
+}
+
+fn parse_hex(hex_data: &str) -> Vec<u8> {
+    let digits: Vec<u8> = hex_data
+        .trim()
+        .trim_start_matches("0x")
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    assert!(
+        digits.len().is_multiple_of(2),
+        "hex fixture has an odd number of digits: {hex_data:?}"
+    );
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .unwrap_or_else(|| panic!("invalid hex in fixture: {hex_data:?}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComputeBudgetInstructionDecoder, InstructionDecoder};
+
+    #[test]
+    fn test_decode_fixture() {
+        // SetComputeUnitLimit(200_000)
+        let compute_budget = ComputeBudgetInstructionDecoder.program_id();
+        let decoded = decode_fixture(&compute_budget, "0x02 400d0300", 0).unwrap();
+        assert_eq!(decoded.name, "SetComputeUnitLimit");
+        assert_eq!(decoded.fields[0].value, "200000");
+
+        assert!(decode_fixture(&Pubkey::new_unique(), "00", 0).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid hex in fixture")]
+    fn test_decode_fixture_rejects_invalid_hex() {
+        decode_fixture(&Pubkey::default(), "zz", 0);
+    }
+}
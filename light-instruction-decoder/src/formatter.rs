@@ -6,13 +6,17 @@ use std::{
 };
 
 use solana_pubkey::Pubkey;
-use tabled::{Table, Tabled};
+use tabled::{
+    grid::util::string::{get_char_width, get_string_width},
+    settings::{location::ByColumnName, Remove, Width},
+    Table, Tabled,
+};
 
 use crate::{
-    config::{EnhancedLoggingConfig, LogVerbosity},
+    config::{EnhancedLoggingConfig, IndentStyle, LamportDisplay, LogVerbosity},
     types::{
-        AccountAccess, AccountChange, AccountStateSnapshot, EnhancedInstructionLog,
-        EnhancedTransactionLog, TransactionStatus,
+        sysvar_name, AccountAccess, AccountChange, AccountStateSnapshot, EnhancedInstructionLog,
+        EnhancedTransactionLog, PdaMatch, TransactionStatus, PACKET_DATA_SIZE,
     },
 };
 
@@ -194,8 +198,13 @@ static KNOWN_ACCOUNTS: &[(&str, &str)] = &[
         "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV",
         "noop program",
     ),
+    // The all-zeros pubkey is the System Program id, but also what an unresolved
+    // account index decodes to, so an unlabeled one is flagged. Decoders label real
+    // System Program accounts by role (e.g. `system_program`).
+    ("11111111111111111111111111111111", "‹zero pubkey›"),
+    // Burn address: lamports sent here are destroyed
+    ("1nc1nerator11111111111111111111111111111111", "incinerator"),
     // Solana native programs
-    ("11111111111111111111111111111111", "system program"),
     (
         "ComputeBudget111111111111111111111111111111",
         "compute budget program",
@@ -223,7 +232,8 @@ struct AccountRow {
     name: String,
 }
 
-/// Row for outer instruction account table display (8 columns - includes account state + owner)
+/// Row for outer instruction account table display (8 columns - includes account state + owner,
+/// plus Rent with `show_rent_status`)
 #[derive(Tabled)]
 struct OuterAccountRow {
     #[tabled(rename = "#")]
@@ -242,6 +252,88 @@ struct OuterAccountRow {
     lamports: String,
     #[tabled(rename = "Change")]
     lamports_change: String,
+    /// Only shown with `show_rent_status`
+    #[tabled(rename = "Rent")]
+    rent: String,
+}
+
+/// Outer account table without the low-priority Owner and Data Len columns (used for narrow widths)
+#[derive(Tabled)]
+struct CompactOuterAccountRow {
+    #[tabled(rename = "#")]
+    symbol: String,
+    #[tabled(rename = "Account")]
+    pubkey: String,
+    #[tabled(rename = "Type")]
+    access: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Lamports")]
+    lamports: String,
+    #[tabled(rename = "Change")]
+    lamports_change: String,
+    /// Only shown with `show_rent_status`
+    #[tabled(rename = "Rent")]
+    rent: String,
+}
+
+impl From<OuterAccountRow> for CompactOuterAccountRow {
+    fn from(row: OuterAccountRow) -> Self {
+        Self {
+            symbol: row.symbol,
+            pubkey: row.pubkey,
+            access: row.access,
+            name: row.name,
+            lamports: row.lamports,
+            lamports_change: row.lamports_change,
+            rent: row.rent,
+        }
+    }
+}
+
+/// Shorten a base58 pubkey to its first and last 4 characters (e.g. "AKnL..RSZ9")
+///
+/// Works on characters, so labels with multi-byte characters are safe to pass.
+fn shorten_pubkey(pubkey: &str) -> String {
+    let chars: Vec<char> = pubkey.chars().collect();
+    if chars.len() <= 10 {
+        pubkey.to_string()
+    } else {
+        let start: String = chars[..4].iter().collect();
+        let end: String = chars[chars.len() - 4..].iter().collect();
+        format!("{}..{}", start, end)
+    }
+}
+
+/// Split `value` into chunks of at most `max_width` display columns
+///
+/// Splits between characters, so multi-byte and double-width characters (emoji,
+/// CJK) are never cut. A character wider than `max_width` gets a chunk of its own.
+fn split_by_width(value: &str, max_width: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    let mut width = 0;
+    for c in value.chars() {
+        let char_width = get_char_width(c);
+        if width + char_width > max_width && !chunk.is_empty() {
+            chunks.push(std::mem::take(&mut chunk));
+            width = 0;
+        }
+        chunk.push(c);
+        width += char_width;
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Truncate table cells as a last resort if it is still wider than `max_width`
+fn truncate_table(mut table: Table, max_width: usize) -> String {
+    if table.total_width() > max_width {
+        table.with(Width::truncate(max_width));
+    }
+    table.to_string()
 }
 
 /// Colors for terminal output
@@ -361,8 +453,9 @@ impl TransactionFormatter {
                 // Check if this cell contains a long value
                 let mut cell_modified = false;
                 for word in part.split_whitespace() {
-                    if word.len() > 44 && word.chars().all(|c| c.is_alphanumeric()) {
-                        let indent = " ".repeat(leading_chars.len() + 2); // Extra space for table formatting
+                    if get_string_width(word) > 44 && word.chars().all(|c| c.is_alphanumeric()) {
+                        // Extra space for table formatting
+                        let indent = " ".repeat(get_string_width(&leading_chars) + 2);
                         let formatted_word = self.format_long_value_with_indent(word, 44, &indent);
                         new_line.push_str(&part.replace(word, &formatted_word));
                         cell_modified = true;
@@ -385,8 +478,10 @@ impl TransactionFormatter {
     }
 
     /// Format long value with proper indentation for continuation lines
+    ///
+    /// Widths are display columns, so wide characters count double.
     fn format_long_value_with_indent(&self, value: &str, max_width: usize, indent: &str) -> String {
-        if value.len() <= max_width {
+        if get_string_width(value) <= max_width {
             return value.to_string();
         }
 
@@ -410,7 +505,9 @@ impl TransactionFormatter {
                 };
 
                 // Check if adding this part would exceed the line width
-                if current_line.len() + addition.len() > max_width && !current_line.is_empty() {
+                if get_string_width(&current_line) + get_string_width(&addition) > max_width
+                    && !current_line.is_empty()
+                {
                     // Add current line to result and start new line
                     if first_line {
                         result.push_str(&current_line);
@@ -437,20 +534,12 @@ impl TransactionFormatter {
             result.push(']');
         } else {
             // Fall back to character-based breaking for non-array values
-            let chars = value.chars().collect::<Vec<char>>();
-            let mut pos = 0;
-
-            while pos < chars.len() {
-                let end = (pos + max_width).min(chars.len());
-                let chunk: String = chars[pos..end].iter().collect();
-
-                if pos == 0 {
-                    result.push_str(&chunk);
+            for (i, chunk) in split_by_width(value, max_width).iter().enumerate() {
+                if i == 0 {
+                    result.push_str(chunk);
                 } else {
                     result.push_str(&format!("\n{}{}", indent, chunk));
                 }
-
-                pos = end;
             }
         }
 
@@ -462,41 +551,195 @@ impl TransactionFormatter {
         let mut output = String::new();
 
         // Transaction box header with number (wide enough for signature + slot + status)
-        writeln!(output, "{}┌──────────────────────────────────────────────────────────── Transaction #{} ─────────────────────────────────────────────────────────────┐{}", self.colors.gray, tx_number, self.colors.reset).expect("Failed to write box header");
+        let box_width = self.box_width();
+        let title = format!(" Transaction #{} ", tx_number);
+        let rule = box_width.saturating_sub(2 + title.chars().count());
+        writeln!(
+            output,
+            "{}┌{}{}{}┐{}",
+            self.colors.gray,
+            "─".repeat(rule / 2),
+            title,
+            "─".repeat(rule - rule / 2),
+            self.colors.reset
+        )
+        .expect("Failed to write box header");
 
         // Transaction header
         self.write_transaction_header(&mut output, log)
             .expect("Failed to write header");
 
-        // Instructions section
+        // Instructions section (each instruction stops once `max_output_bytes` is reached)
         if !log.instructions.is_empty() {
             self.write_instructions_section(&mut output, log)
                 .expect("Failed to write instructions");
         }
 
+        // Decode warnings section
+        if !self.output_full(&output) && !log.warnings.is_empty() {
+            self.write_warnings_section(&mut output, log)
+                .expect("Failed to write warnings");
+        }
+
+        // Account roles section
+        if !self.output_full(&output)
+            && self.config.show_account_roles
+            && !log.instructions.is_empty()
+        {
+            self.write_account_roles_section(&mut output, log)
+                .expect("Failed to write account roles");
+        }
+
         // Account changes section
-        if self.config.show_account_changes && !log.account_changes.is_empty() {
+        if !self.output_full(&output)
+            && self.config.show_account_changes
+            && !log.account_changes.is_empty()
+        {
             self.write_account_changes_section(&mut output, log)
                 .expect("Failed to write account changes");
         }
 
         // Light Protocol events section
-        if !log.light_events.is_empty() {
+        if !self.output_full(&output) && !log.light_events.is_empty() {
             self.write_light_events_section(&mut output, log)
                 .expect("Failed to write Light Protocol events");
         }
 
         // Program logs section (LiteSVM pretty logs)
-        if !log.program_logs_pretty.trim().is_empty() {
+        if !self.output_full(&output) && !log.program_logs_pretty.trim().is_empty() {
             self.write_program_logs_section(&mut output, log)
                 .expect("Failed to write program logs");
         }
 
+        // Aggregate invoke stats
+        if !self.output_full(&output) {
+            self.write_stats_footer(&mut output, log)
+                .expect("Failed to write stats footer");
+        }
+
         // Transaction box footer (matches header width)
-        writeln!(output, "{}└──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────┘{}", self.colors.gray, self.colors.reset).expect("Failed to write box footer");
+        writeln!(
+            output,
+            "{}└{}┘{}",
+            self.colors.gray,
+            "─".repeat(box_width.saturating_sub(2)),
+            self.colors.reset
+        )
+        .expect("Failed to write box footer");
 
         // Apply line breaks for long values in the complete output
-        self.apply_line_breaks(&output)
+        let output = self.apply_line_breaks(&output);
+        self.limit_output_size(output)
+    }
+
+    /// Whether `output` has reached `max_output_bytes`; sections and instructions
+    /// are not rendered past this point
+    fn output_full(&self, output: &str) -> bool {
+        self.config
+            .max_output_bytes
+            .is_some_and(|max| output.len() >= max)
+    }
+
+    /// Cut output longer than `max_output_bytes` at the last line that fits and
+    /// append a truncation marker
+    ///
+    /// Rendering already stopped near the limit (see [`Self::output_full`]), so
+    /// this only trims the last partly written section.
+    fn limit_output_size(&self, mut output: String) -> String {
+        let max = match self.config.max_output_bytes {
+            Some(max) if output.len() > max => max,
+            _ => return output,
+        };
+        let mut cut = max;
+        while !output.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        if let Some(newline) = output[..cut].rfind('\n') {
+            cut = newline + 1;
+        }
+        output.truncate(cut);
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+        writeln!(
+            output,
+            "{}{}… output truncated at {} bytes{}",
+            self.colors.reset, self.colors.yellow, max, self.colors.reset
+        )
+        .expect("Failed to write truncation marker");
+        output
+    }
+
+    /// Format without ANSI escape codes, regardless of `use_colors`
+    ///
+    /// Unlike stripping the colored output afterwards, the text is generated
+    /// plain, so no escape bytes can leak into it.
+    pub fn format_plain(&self, log: &EnhancedTransactionLog, tx_number: usize) -> String {
+        if !self.config.use_colors {
+            return self.format(log, tx_number);
+        }
+        let plain = Self {
+            config: self.config.clone(),
+            colors: Colors::default(),
+        };
+        plain.format(log, tx_number)
+    }
+
+    /// Format a sequence of transactions as one numbered report with an overall summary
+    pub fn format_batch(&self, logs: &[EnhancedTransactionLog]) -> String {
+        let mut output = String::new();
+        for (i, log) in logs.iter().enumerate() {
+            output.push_str(&self.format(log, i + 1));
+        }
+        self.write_batch_summary(&mut output, logs)
+            .expect("Failed to write batch summary");
+        output
+    }
+
+    /// Write totals across all transactions of a batch
+    fn write_batch_summary(
+        &self,
+        output: &mut String,
+        logs: &[EnhancedTransactionLog],
+    ) -> fmt::Result {
+        let total_compute: u64 = logs.iter().map(|log| log.compute_used).sum();
+        let total_fees: u64 = logs.iter().map(|log| log.fee).sum();
+        let succeeded = logs
+            .iter()
+            .filter(|log| matches!(log.status, TransactionStatus::Success))
+            .count();
+        let failed = logs
+            .iter()
+            .filter(|log| matches!(log.status, TransactionStatus::Failed(_)))
+            .count();
+
+        writeln!(
+            output,
+            "{}Batch Summary ({} transactions):{}",
+            self.colors.bold,
+            logs.len(),
+            self.colors.reset
+        )?;
+        writeln!(
+            output,
+            "  Status: {}{} succeeded{} | {}{} failed{}",
+            self.colors.green,
+            succeeded,
+            self.colors.reset,
+            if failed > 0 { self.colors.red } else { "" },
+            failed,
+            self.colors.reset
+        )?;
+        writeln!(
+            output,
+            "  Total Compute Used: {}{} CU{} | Total Fees: {}{}{}",
+            self.colors.blue,
+            format_with_thousands_separator(total_compute),
+            self.colors.reset,
+            self.colors.yellow,
+            self.format_fee(total_fees),
+            self.colors.reset
+        )
     }
 
     /// Write transaction header with status, fee, and compute units
@@ -505,31 +748,118 @@ impl TransactionFormatter {
         output: &mut String,
         log: &EnhancedTransactionLog,
     ) -> fmt::Result {
+        if let Some(label) = &log.label {
+            writeln!(
+                output,
+                "{}│{} {}▶ {}{}",
+                self.colors.gray, self.colors.reset, self.colors.bold, label, self.colors.reset
+            )?;
+        }
+
+        let signature = match self.config.signature_display.format(&log.signature) {
+            Some(signature) => format!(
+                "{}Transaction: {}{} | ",
+                self.colors.bold, self.colors.cyan, signature
+            ),
+            None => String::new(),
+        };
+        let block_time = log
+            .block_time
+            .map(|block_time| format!(" | Block Time: {}", block_time))
+            .unwrap_or_default();
         writeln!(
             output,
-            "{}│{} {}Transaction: {}{} | Slot: {} | Status: {}{}",
+            "{}│{} {}Slot: {}{} | Status: {}{}",
             self.colors.gray,
             self.colors.reset,
-            self.colors.bold,
-            self.colors.cyan,
-            log.signature,
+            signature,
             log.slot,
+            block_time,
             self.status_color(&log.status),
             log.status.text(),
         )?;
 
-        writeln!(
-            output,
-            "{}│{} Fee: {}{:.6} SOL | Compute Used: {}{}/{} CU{}",
-            self.colors.gray,
-            self.colors.reset,
-            self.colors.yellow,
-            log.fee as f64 / 1_000_000_000.0,
-            self.colors.blue,
-            log.compute_used,
-            log.compute_total,
-            self.colors.reset
-        )?;
+        // The recent blockhash field holds the nonce value here, not a blockhash
+        if let Some(nonce) = &log.durable_nonce {
+            writeln!(
+                output,
+                "{}│{} Durable Nonce: {} (nonce account: {}, authority: {})",
+                self.colors.gray,
+                self.colors.reset,
+                nonce.nonce,
+                nonce.nonce_account,
+                nonce.nonce_authority
+            )?;
+        }
+
+        // No fee or compute without an execution result
+        if log.status != TransactionStatus::NotExecuted {
+            write!(
+                output,
+                "{}│{} Fee: {}{}",
+                self.colors.gray,
+                self.colors.reset,
+                self.colors.yellow,
+                self.format_fee(log.fee),
+            )?;
+            if log.prioritization_fee > 0 {
+                write!(
+                    output,
+                    "{} (base {} + priority {})",
+                    self.colors.reset,
+                    self.format_fee(log.base_fee),
+                    self.format_fee(log.prioritization_fee)
+                )?;
+            }
+            write!(
+                output,
+                " | Compute Used: {}{}/{} CU{}",
+                self.colors.blue, log.compute_used, log.compute_total, self.colors.reset
+            )?;
+            if let Some(utilization) = log.compute_utilization() {
+                if log.compute_limit_reached() {
+                    write!(
+                        output,
+                        " ({:.1}% of limit, {}limit reached{})",
+                        utilization, self.colors.red, self.colors.reset
+                    )?;
+                } else {
+                    write!(output, " ({:.1}% of limit)", utilization)?;
+                }
+            }
+            // The priority fee itself is already in the fee breakdown
+            if let Some(price) = log.compute_unit_price {
+                write!(
+                    output,
+                    " | Priority: {}{} µlam/CU{}",
+                    self.colors.yellow, price, self.colors.reset
+                )?;
+            }
+            writeln!(output)?;
+        }
+
+        if self.config.show_transaction_size {
+            if let (Some(size), Some(message_size)) =
+                (log.transaction_size(), log.total_message_size)
+            {
+                let color = if log.near_packet_limit() {
+                    self.colors.red
+                } else {
+                    self.colors.blue
+                };
+                writeln!(
+                    output,
+                    "{}│{} Size: {}{}/{} bytes{} (message: {} bytes)",
+                    self.colors.gray,
+                    self.colors.reset,
+                    color,
+                    size,
+                    PACKET_DATA_SIZE,
+                    self.colors.reset,
+                    message_size
+                )?;
+            }
+        }
 
         writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
         Ok(())
@@ -552,8 +882,71 @@ impl TransactionFormatter {
         )?;
         writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
 
+        if self.config.group_by_program {
+            return self.write_instructions_by_program(output, log);
+        }
+
         for (i, instruction) in log.instructions.iter().enumerate() {
-            self.write_instruction(output, instruction, 0, i + 1, log.account_states.as_ref())?;
+            self.write_instruction(
+                output,
+                instruction,
+                &[i + 1 < log.instructions.len()],
+                i + 1,
+                log.account_states.as_ref(),
+                &log.pdas,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Write top-level instructions grouped under program headers
+    ///
+    /// Groups appear in order of each program's first instruction; within a group
+    /// instructions keep execution order and their execution number.
+    fn write_instructions_by_program(
+        &self,
+        output: &mut String,
+        log: &EnhancedTransactionLog,
+    ) -> fmt::Result {
+        let mut groups: Vec<(Pubkey, Vec<usize>)> = Vec::new();
+        for (i, instruction) in log.instructions.iter().enumerate() {
+            match groups
+                .iter_mut()
+                .find(|(program_id, _)| *program_id == instruction.program_id)
+            {
+                Some((_, indices)) => indices.push(i),
+                None => groups.push((instruction.program_id, vec![i])),
+            }
+        }
+
+        for (group, (program_id, indices)) in groups.into_iter().enumerate() {
+            if group > 0 {
+                writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
+            }
+            let program_name = &log.instructions[indices[0]].program_name;
+            writeln!(
+                output,
+                "{}│{} {}▸ {}{} ({}) - {} instruction{}",
+                self.colors.gray,
+                self.colors.reset,
+                self.colors.bold,
+                program_name,
+                self.colors.reset,
+                program_id,
+                indices.len(),
+                if indices.len() == 1 { "" } else { "s" }
+            )?;
+            for (position, &i) in indices.iter().enumerate() {
+                self.write_instruction(
+                    output,
+                    &log.instructions[i],
+                    &[position + 1 < indices.len()],
+                    i + 1,
+                    log.account_states.as_ref(),
+                    &log.pdas,
+                )?;
+            }
         }
 
         Ok(())
@@ -564,16 +957,25 @@ impl TransactionFormatter {
     /// For outer instructions (depth=0), if account_states is provided, displays
     /// a 7-column table with Data Len, Lamports, and Change columns.
     /// For inner instructions, displays a 4-column table.
+    ///
+    /// `branches` holds, for the instruction and each of its parents (outermost
+    /// first), whether a sibling follows it; its length is the depth plus one.
     fn write_instruction(
         &self,
         output: &mut String,
         instruction: &EnhancedInstructionLog,
-        depth: usize,
+        branches: &[bool],
         number: usize,
         account_states: Option<&HashMap<Pubkey, AccountStateSnapshot>>,
+        pdas: &HashMap<Pubkey, PdaMatch>,
     ) -> fmt::Result {
-        let indent = self.get_tree_indent(depth);
-        let prefix = if depth == 0 { "├─" } else { "└─" };
+        // Past the output limit the rest is cut anyway, so don't render it
+        if self.output_full(output) {
+            return Ok(());
+        }
+        let depth = branches.len() - 1;
+        let indent = self.get_tree_indent(&branches[..depth]);
+        let prefix = self.tree_connector(depth, branches[depth]);
 
         // Instruction header
         let inner_count = if instruction.inner_instructions.is_empty() {
@@ -581,12 +983,24 @@ impl TransactionFormatter {
         } else {
             format!(".{}", instruction.inner_instructions.len())
         };
+        let path =
+            if self.config.show_instruction_paths && depth > 0 && !instruction.path.is_empty() {
+                format!(
+                    "{}[{}]{} ",
+                    self.colors.gray,
+                    instruction.location(),
+                    self.colors.reset
+                )
+            } else {
+                String::new()
+            };
 
         write!(
             output,
-            "{}{} {}#{}{} {}{} ({}{}{})",
+            "{}{} {}{}#{}{} {}{} ({}{}{})",
             indent,
             prefix,
+            path,
             self.colors.bold,
             number,
             inner_count,
@@ -611,23 +1025,51 @@ impl TransactionFormatter {
             if let Some(compute) = instruction.compute_consumed {
                 write!(
                     output,
-                    " {}({}{}CU{})",
+                    " {}(cu: {}{}{})",
                     self.colors.gray, self.colors.blue, compute, self.colors.gray
                 )?;
             }
         }
+        if self.config.show_transaction_size {
+            if let Some(size) = instruction.instruction_size_bytes {
+                write!(output, " {}({} bytes)", self.colors.gray, size)?;
+            }
+        }
+
+        if !instruction.success {
+            write!(
+                output,
+                " {}{}✗ FAILED HERE",
+                self.colors.reset, self.colors.red
+            )?;
+        }
 
         writeln!(output, "{}", self.colors.reset)?;
 
+        // Flag instructions that were given fewer accounts than they expect
+        if let Some(mismatch) = instruction.account_count_mismatch {
+            writeln!(
+                output,
+                "{}{}⚠ Account count mismatch: expected {} accounts, got {}{}",
+                self.get_tree_indent(branches),
+                self.colors.red,
+                mismatch.expected,
+                mismatch.actual,
+                self.colors.reset
+            )?;
+        }
+
         // Show instruction details based on verbosity
         match self.config.verbosity {
             LogVerbosity::Detailed | LogVerbosity::Full => {
                 // Display decoded instruction fields from custom decoder
                 if let Some(ref decoded) = instruction.decoded_instruction {
                     if !decoded.fields.is_empty() {
-                        let indent = self.get_tree_indent(depth + 1);
+                        let indent = self.get_tree_indent(branches);
                         for field in &decoded.fields {
-                            self.write_decoded_field(field, output, &indent, 0)?;
+                            if self.config.shows_field(&field.name) {
+                                self.write_decoded_field(field, output, &indent, 0)?;
+                            }
                         }
                     }
                 } else if !instruction.data.is_empty() {
@@ -640,7 +1082,7 @@ impl TransactionFormatter {
                     };
 
                     if should_show_data {
-                        let indent = self.get_tree_indent(depth + 1);
+                        let indent = self.get_tree_indent(branches);
                         writeln!(
                             output,
                             "{}{}Raw instruction data ({} bytes): {}[",
@@ -673,7 +1115,7 @@ impl TransactionFormatter {
 
         // Show accounts if verbose
         if self.config.verbosity == LogVerbosity::Full && !instruction.accounts.is_empty() {
-            let accounts_indent = self.get_tree_indent(depth + 1);
+            let accounts_indent = self.get_tree_indent(branches);
             writeln!(
                 output,
                 "{}{}Accounts ({}):{}",
@@ -687,8 +1129,14 @@ impl TransactionFormatter {
             // For inner instructions, use 4-column table
             if let (0, Some(states)) = (depth, account_states) {
                 let mut outer_rows: Vec<OuterAccountRow> = Vec::new();
+                let mut highlighted: Vec<bool> = Vec::new();
+                let mut hidden = 0;
 
                 for (idx, account) in instruction.accounts.iter().enumerate() {
+                    if !self.shows_account_owner(states.get(&account.pubkey)) {
+                        hidden += 1;
+                        continue;
+                    }
                     let access = if account.is_signer && account.is_writable {
                         AccountAccess::SignerWritable
                     } else if account.is_signer {
@@ -699,47 +1147,45 @@ impl TransactionFormatter {
                         AccountAccess::Readonly
                     };
 
-                    // Try to get account name from decoded instruction first, then fall back to lookup
-                    // Empty names from resolver indicate "use KNOWN_ACCOUNTS lookup"
-                    let account_name = instruction
-                        .decoded_instruction
-                        .as_ref()
-                        .and_then(|decoded| decoded.account_names.get(idx).cloned())
-                        .filter(|name| !name.is_empty())
-                        .unwrap_or_else(|| self.get_account_name(&account.pubkey));
+                    let account_name = self.account_label(instruction, idx, pdas);
 
                     // Get account state if available
-                    let (owner, data_len, lamports, lamports_change) = if let Some(state) =
+                    let (owner, data_len, lamports, lamports_change, rent) = if let Some(state) =
                         states.get(&account.pubkey)
                     {
                         let change = (state.lamports_after as i128 - state.lamports_before as i128)
                             .clamp(i64::MIN as i128, i64::MAX as i128)
                             as i64;
-                        let change_str = if change > 0 {
-                            format!("+{}", format_signed_with_thousands_separator(change))
-                        } else if change < 0 {
-                            format_signed_with_thousands_separator(change)
-                        } else {
-                            "0".to_string()
-                        };
-                        let owner_pubkey_str = state.owner.to_string();
-                        let owner_str = if owner_pubkey_str.len() >= 5 {
-                            owner_pubkey_str[..5].to_string()
+                        let mut change_str = self.format_lamport_change(change);
+                        let is_highlighted = self.exceeds_highlight_threshold(change);
+                        if is_highlighted {
+                            change_str.push_str(" ◀");
+                        }
+                        highlighted.push(is_highlighted);
+                        let owner_str: String = state.owner.to_string().chars().take(5).collect();
+                        let rent = if state.is_rent_exempt() {
+                            "exempt".to_string()
                         } else {
-                            owner_pubkey_str
+                            format!(
+                                "below {}",
+                                self.format_lamports(state.rent_exempt_minimum())
+                            )
                         };
                         (
                             owner_str,
                             format_with_thousands_separator(state.data_len_before as u64),
-                            format_with_thousands_separator(state.lamports_before),
+                            self.format_lamports(state.lamports_before),
                             change_str,
+                            rent,
                         )
                     } else {
+                        highlighted.push(false);
                         (
                             "-".to_string(),
                             "-".to_string(),
                             "-".to_string(),
                             "-".to_string(),
+                            "-".to_string(),
                         )
                     };
 
@@ -752,18 +1198,32 @@ impl TransactionFormatter {
                         data_len,
                         lamports,
                         lamports_change,
+                        rent,
                     });
                 }
 
                 if !outer_rows.is_empty() {
-                    let table = Table::new(outer_rows)
-                        .to_string()
+                    let table = self
+                        .render_outer_account_table(outer_rows, self.table_width_for_depth(depth));
+                    let table = self
+                        .emphasize_highlighted_rows(&table, &highlighted)
                         .lines()
                         .map(|line| format!("{}{}", accounts_indent, line))
                         .collect::<Vec<_>>()
                         .join("\n");
                     writeln!(output, "{}", table)?;
                 }
+                if hidden > 0 {
+                    writeln!(
+                        output,
+                        "{}{}{} account{} hidden by owner filter{}",
+                        accounts_indent,
+                        self.colors.gray,
+                        hidden,
+                        if hidden == 1 { "" } else { "s" },
+                        self.colors.reset
+                    )?;
+                }
             } else {
                 // Inner instructions or no account states - use 4-column table
                 let mut account_rows: Vec<AccountRow> = Vec::new();
@@ -779,14 +1239,7 @@ impl TransactionFormatter {
                         AccountAccess::Readonly
                     };
 
-                    // Try to get account name from decoded instruction first, then fall back to lookup
-                    // Empty names from resolver indicate "use KNOWN_ACCOUNTS lookup"
-                    let account_name = instruction
-                        .decoded_instruction
-                        .as_ref()
-                        .and_then(|decoded| decoded.account_names.get(idx).cloned())
-                        .filter(|name| !name.is_empty())
-                        .unwrap_or_else(|| self.get_account_name(&account.pubkey));
+                    let account_name = self.account_label(instruction, idx, pdas);
                     account_rows.push(AccountRow {
                         symbol: access.symbol(idx + 1),
                         pubkey: account.pubkey.to_string(),
@@ -796,8 +1249,8 @@ impl TransactionFormatter {
                 }
 
                 if !account_rows.is_empty() {
-                    let table = Table::new(account_rows)
-                        .to_string()
+                    let table = self
+                        .render_account_table(account_rows, self.table_width_for_depth(depth))
                         .lines()
                         .map(|line| format!("{}{}", accounts_indent, line))
                         .collect::<Vec<_>>()
@@ -810,13 +1263,131 @@ impl TransactionFormatter {
         // Write inner instructions recursively (inner instructions don't get account states)
         for (i, inner) in instruction.inner_instructions.iter().enumerate() {
             if depth < self.config.max_cpi_depth {
-                self.write_instruction(output, inner, depth + 1, i + 1, None)?;
+                let mut inner_branches = branches.to_vec();
+                inner_branches.push(i + 1 < instruction.inner_instructions.len());
+                self.write_instruction(output, inner, &inner_branches, i + 1, None, pdas)?;
             }
         }
 
         Ok(())
     }
 
+    /// Whether an account passes the configured owner filter
+    fn shows_account_owner(&self, state: Option<&AccountStateSnapshot>) -> bool {
+        match &self.config.account_owner_filter {
+            Some(owners) => state.is_some_and(|state| {
+                owners.contains(&state.owner) || owners.contains(&state.owner_after)
+            }),
+            None => true,
+        }
+    }
+
+    /// Whether a lamport change exceeds the configured highlight threshold
+    fn exceeds_highlight_threshold(&self, change: i64) -> bool {
+        self.config
+            .highlight_lamport_delta_threshold
+            .is_some_and(|threshold| change.unsigned_abs() > threshold)
+    }
+
+    /// Bold the highlighted rows of a rendered account table and dim the others
+    ///
+    /// Leaves the table unchanged when no highlight threshold is configured.
+    fn emphasize_highlighted_rows(&self, table: &str, highlighted: &[bool]) -> String {
+        if self.config.highlight_lamport_delta_threshold.is_none() {
+            return table.to_string();
+        }
+        // Row lines start with '|'; the first one is the header
+        let mut rows = std::iter::once(None).chain(highlighted.iter().map(Some));
+        table
+            .lines()
+            .map(|line| {
+                if !line.starts_with('|') {
+                    return line.to_string();
+                }
+                match rows.next().flatten() {
+                    Some(true) => format!("{}{}{}", self.colors.bold, line, self.colors.reset),
+                    Some(false) => format!("{}{}{}", self.colors.gray, line, self.colors.reset),
+                    None => line.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Width of the transaction box, capped at the configured table width
+    fn box_width(&self) -> usize {
+        const DEFAULT_BOX_WIDTH: usize = 140;
+        self.config
+            .table_width
+            .and_then(|width| width.resolve())
+            .map_or(DEFAULT_BOX_WIDTH, |width| width.min(DEFAULT_BOX_WIDTH))
+    }
+
+    /// Width available to an account table nested under an instruction at `depth`
+    ///
+    /// Returns None when no table width limit is configured (or no terminal is attached).
+    fn table_width_for_depth(&self, depth: usize) -> Option<usize> {
+        let width = self.config.table_width.and_then(|width| width.resolve())?;
+        // Account tables are indented by the tree border ("│ ") plus one "│  " per level
+        Some(width.saturating_sub(2 + 3 * (depth + 1)))
+    }
+
+    /// Render the outer account table, shedding columns to fit `max_width` if set
+    ///
+    /// Owner and Data Len are dropped first, then pubkeys are shortened, and
+    /// finally cells are truncated.
+    fn render_outer_account_table(
+        &self,
+        rows: Vec<OuterAccountRow>,
+        max_width: Option<usize>,
+    ) -> String {
+        let table = self.outer_account_table(&rows);
+        let Some(max_width) = max_width else {
+            return table.to_string();
+        };
+        if table.total_width() <= max_width {
+            return table.to_string();
+        }
+
+        let mut compact_rows: Vec<CompactOuterAccountRow> =
+            rows.into_iter().map(CompactOuterAccountRow::from).collect();
+        let table = self.outer_account_table(&compact_rows);
+        if table.total_width() <= max_width {
+            return table.to_string();
+        }
+
+        for row in &mut compact_rows {
+            row.pubkey = shorten_pubkey(&row.pubkey);
+        }
+        truncate_table(self.outer_account_table(compact_rows), max_width)
+    }
+
+    /// Build an outer account table, leaving out the Rent column unless
+    /// `show_rent_status` is set
+    fn outer_account_table<T: Tabled>(&self, rows: impl IntoIterator<Item = T>) -> Table {
+        let mut table = Table::new(rows);
+        if !self.config.show_rent_status {
+            table.with(Remove::column(ByColumnName::new("Rent")));
+        }
+        table
+    }
+
+    /// Render the inner account table, shortening pubkeys to fit `max_width` if set
+    fn render_account_table(&self, mut rows: Vec<AccountRow>, max_width: Option<usize>) -> String {
+        let table = Table::new(&rows);
+        let Some(max_width) = max_width else {
+            return table.to_string();
+        };
+        if table.total_width() <= max_width {
+            return table.to_string();
+        }
+
+        for row in &mut rows {
+            row.pubkey = shorten_pubkey(&row.pubkey);
+        }
+        truncate_table(Table::new(rows), max_width)
+    }
+
     /// Collapse simple multiline enum variants onto one line
     /// Converts `Some(\n    2,\n)` to `Some(2)`
     fn collapse_simple_enums(&self, input: &str) -> String {
@@ -974,6 +1545,7 @@ impl TransactionFormatter {
             } else {
                 field.value.clone()
             };
+            let display_value = self.apply_lamport_display(&field.name, display_value);
 
             // Handle multiline values by indenting each subsequent line
             if display_value.contains('\n') {
@@ -1095,7 +1667,10 @@ impl TransactionFormatter {
             writeln!(
                 output,
                 "│   {}Lamports: {} → {}{}",
-                self.colors.gray, change.lamports_before, change.lamports_after, self.colors.reset
+                self.colors.gray,
+                self.format_lamports(change.lamports_before),
+                self.format_lamports(change.lamports_after),
+                self.colors.reset
             )?;
         }
 
@@ -1148,6 +1723,111 @@ impl TransactionFormatter {
         Ok(())
     }
 
+    /// Write warnings collected while decoding
+    fn write_warnings_section(
+        &self,
+        output: &mut String,
+        log: &EnhancedTransactionLog,
+    ) -> fmt::Result {
+        writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
+        writeln!(
+            output,
+            "{}│{} {}{}⚠ Warnings ({}):{}",
+            self.colors.gray,
+            self.colors.reset,
+            self.colors.bold,
+            self.colors.yellow,
+            log.warnings.len(),
+            self.colors.reset
+        )?;
+        writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
+
+        for warning in &log.warnings {
+            writeln!(
+                output,
+                "{}│{}   {}{}:{} {}",
+                self.colors.gray,
+                self.colors.reset,
+                self.colors.yellow,
+                warning.location(),
+                self.colors.reset,
+                warning.message
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Write each account's role and signer/writable flags per instruction
+    fn write_account_roles_section(
+        &self,
+        output: &mut String,
+        log: &EnhancedTransactionLog,
+    ) -> fmt::Result {
+        let roles = log.account_roles();
+        let inconsistent = roles
+            .iter()
+            .filter(|account| account.has_inconsistent_access())
+            .count();
+        writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
+        writeln!(
+            output,
+            "{}│{} {}Account Roles ({} accounts, {} inconsistent):{}",
+            self.colors.gray,
+            self.colors.reset,
+            self.colors.bold,
+            roles.len(),
+            inconsistent,
+            self.colors.reset
+        )?;
+        writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
+
+        for account in &roles {
+            let flag = if account.has_inconsistent_access() {
+                format!(
+                    " {}⚠ signer/writable differs across instructions{}",
+                    self.colors.yellow, self.colors.reset
+                )
+            } else {
+                String::new()
+            };
+            writeln!(
+                output,
+                "{}│{}   {}{}{}{}",
+                self.colors.gray,
+                self.colors.reset,
+                self.colors.cyan,
+                account.pubkey,
+                self.colors.reset,
+                flag
+            )?;
+            for usage in &account.usages {
+                let role = usage
+                    .role
+                    .as_deref()
+                    .map(|role| format!(" {role}"))
+                    .unwrap_or_default();
+                let access = match (usage.is_signer, usage.is_writable) {
+                    (true, true) => "signer, writable",
+                    (true, false) => "signer",
+                    (false, true) => "writable",
+                    (false, false) => "readonly",
+                };
+                writeln!(
+                    output,
+                    "{}│{}     {}{}: {}",
+                    self.colors.gray,
+                    self.colors.reset,
+                    usage.location(),
+                    role,
+                    access
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Write program logs section using LiteSVM's pretty logs
     fn write_program_logs_section(
         &self,
@@ -1176,26 +1856,182 @@ impl TransactionFormatter {
         Ok(())
     }
 
-    /// Get tree-style indentation for given depth
-    fn get_tree_indent(&self, depth: usize) -> String {
+    /// Write aggregate instruction/CPI statistics above the box footer
+    fn write_stats_footer(&self, output: &mut String, log: &EnhancedTransactionLog) -> fmt::Result {
+        let stats = log.stats();
+        writeln!(output, "{}│{}", self.colors.gray, self.colors.reset)?;
+        write!(
+            output,
+            "{}│{} Total Instructions: {} | Max CPI Depth: {} | Distinct Programs: {}",
+            self.colors.gray,
+            self.colors.reset,
+            stats.total_instructions,
+            stats.max_depth,
+            stats.distinct_programs,
+        )?;
+        let data_change = log.net_data_len_change();
+        if data_change > 0 {
+            write!(
+                output,
+                " | Account Data: {}+{} bytes allocated{}",
+                self.colors.green,
+                format_with_thousands_separator(data_change.unsigned_abs()),
+                self.colors.reset
+            )?;
+        } else if data_change < 0 {
+            write!(
+                output,
+                " | Account Data: {}-{} bytes freed{}",
+                self.colors.red,
+                format_with_thousands_separator(data_change.unsigned_abs()),
+                self.colors.reset
+            )?;
+        }
+        writeln!(output)
+    }
+
+    /// Get tree-style indentation with one column per entry of `branches`
+    ///
+    /// Each entry tells whether the instruction at that level has a following
+    /// sibling, which [`IndentStyle::Tree`] uses to draw only continuing guides.
+    fn get_tree_indent(&self, branches: &[bool]) -> String {
         let border = format!("{}│{} ", self.colors.gray, self.colors.reset);
-        if depth == 0 {
-            border
-        } else {
-            format!("{}{}", border, "│  ".repeat(depth))
+        let columns: String = branches
+            .iter()
+            .map(|&continues| match self.config.indent {
+                IndentStyle::Bars => "│  ".to_string(),
+                IndentStyle::Spaces(width) => " ".repeat(width),
+                IndentStyle::Tree if continues => "│  ".to_string(),
+                IndentStyle::Tree => "   ".to_string(),
+            })
+            .collect();
+        format!("{}{}", border, columns)
+    }
+
+    /// Connector in front of an instruction header
+    fn tree_connector(&self, depth: usize, continues: bool) -> &'static str {
+        match self.config.indent {
+            IndentStyle::Tree if continues => "├─",
+            IndentStyle::Tree => "└─",
+            _ if depth == 0 => "├─",
+            _ => "└─",
+        }
+    }
+
+    /// Format a lamport balance according to `lamport_display`
+    fn format_lamports(&self, lamports: u64) -> String {
+        match self.config.lamport_display {
+            LamportDisplay::Lamports => format_with_thousands_separator(lamports),
+            display => display.format(lamports),
+        }
+    }
+
+    /// Format a signed lamport change, prefixing increases with '+'
+    fn format_lamport_change(&self, change: i64) -> String {
+        if change == 0 {
+            return "0".to_string();
+        }
+        match self.config.lamport_display {
+            LamportDisplay::Lamports if change > 0 => {
+                format!("+{}", format_signed_with_thousands_separator(change))
+            }
+            LamportDisplay::Lamports => format_signed_with_thousands_separator(change),
+            display => {
+                let sign = if change > 0 { "+" } else { "-" };
+                format!("{}{}", sign, display.format(change.unsigned_abs()))
+            }
+        }
+    }
+
+    /// Format a fee; the default `Lamports` display keeps the short SOL summary
+    fn format_fee(&self, fee: u64) -> String {
+        match self.config.lamport_display {
+            LamportDisplay::Lamports => format!("{:.6} SOL", fee as f64 / 1_000_000_000.0),
+            display => display.format(fee),
         }
     }
 
+    /// Re-render lamport amounts inside a decoded field value
+    ///
+    /// Covers fields named `*lamports`, `lamports: N` lines of multi-line values
+    /// and `N lamports` phrases emitted by the Light Protocol formatters.
+    fn apply_lamport_display(&self, name: &str, value: String) -> String {
+        let display = self.config.lamport_display;
+        if display == LamportDisplay::Lamports {
+            return value;
+        }
+        if name.ends_with("lamports") {
+            if let Ok(lamports) = value.parse::<u64>() {
+                return display.format(lamports);
+            }
+        }
+        if !value.contains("lamports") {
+            return value;
+        }
+        value
+            .split('\n')
+            .map(|line| {
+                let trimmed = line.trim_start();
+                if let Some(amount) = trimmed.strip_prefix("lamports: ") {
+                    if let Ok(lamports) = amount.parse::<u64>() {
+                        let indent = &line[..line.len() - trimmed.len()];
+                        return format!("{}lamports: {}", indent, display.format(lamports));
+                    }
+                }
+                if let Some((before, after)) = line.split_once(" lamports") {
+                    // Digits are single bytes, so this stays on a char boundary
+                    let start = before.len()
+                        - before
+                            .chars()
+                            .rev()
+                            .take_while(char::is_ascii_digit)
+                            .count();
+                    if let Ok(lamports) = before[start..].parse::<u64>() {
+                        return format!(
+                            "{}{}{}",
+                            &before[..start],
+                            display.format(lamports),
+                            after
+                        );
+                    }
+                }
+                line.to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Get color for transaction status
     fn status_color(&self, status: &TransactionStatus) -> &str {
         match status {
             TransactionStatus::Success => self.colors.green,
             TransactionStatus::Failed(_) => self.colors.red,
             TransactionStatus::Unknown => self.colors.yellow,
+            TransactionStatus::NotExecuted => self.colors.gray,
+        }
+    }
+
+    /// Name column for the account at `idx`, with a PDA label if it matches a registered derivation
+    fn account_label(
+        &self,
+        instruction: &EnhancedInstructionLog,
+        idx: usize,
+        pdas: &HashMap<Pubkey, PdaMatch>,
+    ) -> String {
+        let pubkey = &instruction.accounts[idx].pubkey;
+        // Use the decoder's role for the account first, then fall back to lookup
+        // Empty names from resolver indicate "use KNOWN_ACCOUNTS lookup"
+        match (instruction.account_role(idx), pdas.get(pubkey)) {
+            (Some(role), Some(pda)) => format!("{} (PDA {}, bump {})", role, pda.label, pda.bump),
+            (None, Some(pda)) => format!("PDA {} (bump {})", pda.label, pda.bump),
+            // Keep sysvars recognizable when the decoder names them by role
+            (Some(role), None) if sysvar_name(pubkey).is_some() => format!("{} (sysvar)", role),
+            (Some(role), None) => role.to_string(),
+            (None, None) => self.get_account_name(pubkey),
         }
     }
 
-    /// Get human-readable name for known accounts using constants and test accounts
+    /// Get human-readable name for known accounts using constants, sysvars, and test accounts
     fn get_account_name(&self, pubkey: &Pubkey) -> String {
         // String-based matches for test accounts and other addresses
         let pubkey_str = pubkey.to_string();
@@ -1205,6 +2041,10 @@ impl TransactionFormatter {
             }
         }
 
+        if let Some(name) = sysvar_name(pubkey) {
+            return name.to_string();
+        }
+
         // Classify based on curve: on-curve = wallet, off-curve = pda (or program, but we can't tell without executable flag)
         if pubkey.is_on_curve() {
             "unknown wallet".to_string()
@@ -1216,7 +2056,55 @@ impl TransactionFormatter {
 
 #[cfg(test)]
 mod tests {
+    use solana_instruction::AccountMeta;
+    use solana_signature::Signature;
+
     use super::*;
+    use crate::{
+        config::{SignatureDisplay, TableWidth},
+        DecodedField, DecodedInstruction,
+    };
+
+    /// Transaction with one top-level instruction touching two accounts, with account states
+    fn sample_log() -> EnhancedTransactionLog {
+        let from = Pubkey::new_from_array([1; 32]);
+        let to = Pubkey::new_from_array([2; 32]);
+        let mut instruction =
+            EnhancedInstructionLog::new(0, Pubkey::default(), "System Program".to_string());
+        instruction.accounts = vec![AccountMeta::new(from, true), AccountMeta::new(to, false)];
+
+        let mut log = EnhancedTransactionLog::new(Signature::default(), 0);
+        log.instructions.push(instruction);
+        let mut states = HashMap::new();
+        states.insert(
+            from,
+            AccountStateSnapshot {
+                lamports_before: 10_000_000_000,
+                lamports_after: 8_999_995_000,
+                ..Default::default()
+            },
+        );
+        states.insert(
+            to,
+            AccountStateSnapshot {
+                lamports_after: 1_000_000_000,
+                ..Default::default()
+            },
+        );
+        log.account_states = Some(states);
+        log
+    }
+
+    /// Lines belonging to account tables (after the tree border prefix)
+    fn table_lines(output: &str) -> Vec<&str> {
+        output
+            .lines()
+            .filter(|line| {
+                let body = line.trim_start_matches(['│', ' ']);
+                body.starts_with('+') || body.starts_with('|')
+            })
+            .collect()
+    }
 
     #[test]
     fn test_format_with_thousands_separator() {
@@ -1241,4 +2129,622 @@ mod tests {
             "-1,000,000"
         );
     }
+
+    #[test]
+    fn test_account_table_fits_table_width() {
+        let mut config = EnhancedLoggingConfig::debug().with_table_width(TableWidth::Fixed(80));
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&sample_log(), 1);
+
+        assert!(!table_lines(&output).is_empty());
+        for line in output.lines() {
+            assert!(
+                line.chars().count() <= 80,
+                "line exceeds 80 columns: {line}"
+            );
+        }
+        // Low-priority columns are dropped before anything else
+        assert!(!output.contains("Owner"));
+        assert!(output.contains("Change"));
+    }
+
+    #[test]
+    fn test_instruction_paths() {
+        let log = crate::TransactionLogBuilder::new()
+            .instruction(crate::InstructionLogBuilder::new(Pubkey::default()))
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::default())
+                    .inner(crate::InstructionLogBuilder::new(Pubkey::default()))
+                    .inner(
+                        crate::InstructionLogBuilder::new(Pubkey::default())
+                            .inner(crate::InstructionLogBuilder::new(Pubkey::default()))
+                            .name("Transfer"),
+                    ),
+            )
+            .build();
+        let nested = &log.instructions[1].inner_instructions[1];
+        assert_eq!(nested.path, vec![1, 1]);
+        assert_eq!(nested.inner_instructions[0].path, vec![1, 1, 0]);
+
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(!output.contains("[#2.2]"));
+
+        let output = TransactionFormatter::new(&config.with_instruction_paths()).format(&log, 1);
+        assert!(output.contains("└─ [#2.2] #2.1 "));
+        assert!(output.contains("└─ [#2.2.1] #1 "));
+        // Top-level instructions keep their plain number
+        assert!(!output.contains("[#2]"));
+    }
+
+    #[test]
+    fn test_wide_characters_in_fields_and_labels() {
+        let to = Pubkey::new_from_array([2; 32]);
+        let mut log = sample_log();
+        log.instructions[0].decoded_instruction =
+            Some(DecodedInstruction::with_fields_and_accounts(
+                "Memo".to_string(),
+                vec![
+                    DecodedField::new("memo", "🎉 gm 🚀 ".repeat(12)),
+                    DecodedField::new("note", "🎉100 lamports"),
+                ],
+                Vec::new(),
+            ));
+        log.pdas.insert(
+            to,
+            PdaMatch {
+                label: "金库账户标签".repeat(3),
+                bump: 255,
+            },
+        );
+
+        for table_width in [None, Some(TableWidth::Fixed(60))] {
+            let mut config = EnhancedLoggingConfig::debug();
+            config.use_colors = false;
+            config.table_width = table_width;
+            config.lamport_display = LamportDisplay::Sol;
+            let output = TransactionFormatter::new(&config).format(&log, 1);
+            assert!(output.contains("note: 🎉0.000000100 SOL"));
+
+            // Every row of the account table has the same display width
+            let lines = table_lines(&output);
+            assert!(!lines.is_empty());
+            let width = get_string_width(lines[0]);
+            for line in lines {
+                assert_eq!(get_string_width(line), width, "misaligned row: {line}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_shorten_and_split_keep_characters_whole() {
+        assert_eq!(
+            shorten_pubkey("金库账户标签金库账户标签"),
+            "金库账户..账户标签"
+        );
+        assert_eq!(split_by_width("ab金库c", 3), vec!["ab", "金", "库c"]);
+        assert_eq!(split_by_width("金库", 1), vec!["金", "库"]);
+    }
+
+    #[test]
+    fn test_highlight_lamport_delta_threshold() {
+        let from = Pubkey::new_from_array([1; 32]).to_string();
+        let to = Pubkey::new_from_array([2; 32]).to_string();
+        let row = |output: &str, pubkey: &str| {
+            output
+                .lines()
+                .find(|line| line.contains(pubkey) && line.contains('|'))
+                .unwrap()
+                .to_string()
+        };
+
+        // -1,000,005,000 exceeds the threshold, +1,000,000,000 does not
+        let mut config =
+            EnhancedLoggingConfig::debug().with_highlight_lamport_delta_threshold(1_000_000_000);
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&sample_log(), 1);
+        assert!(row(&output, &from).contains("-1,000,005,000 ◀"));
+        assert!(!row(&output, &to).contains('◀'));
+
+        config.use_colors = true;
+        let output = TransactionFormatter::new(&config).format(&sample_log(), 1);
+        assert!(row(&output, &from).contains("\x1b[1m| #1"));
+        assert!(row(&output, &to).contains("\x1b[90m| #2"));
+
+        // No threshold: no marker
+        config.highlight_lamport_delta_threshold = None;
+        let output = TransactionFormatter::new(&config).format(&sample_log(), 1);
+        assert!(!output.contains('◀'));
+    }
+
+    #[test]
+    fn test_indent_style() {
+        let log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::new_from_array([7; 32]))
+                    .program_name("Router")
+                    .inner(
+                        crate::InstructionLogBuilder::new(Pubkey::default())
+                            .inner(crate::InstructionLogBuilder::new(Pubkey::default())),
+                    ),
+            )
+            .build();
+        let format = |indent: IndentStyle| {
+            let mut config = EnhancedLoggingConfig::default().with_indent(indent);
+            config.use_colors = false;
+            let output = TransactionFormatter::new(&config).format(&log, 1);
+            output
+                .lines()
+                .filter(|line| line.contains("(System Program)"))
+                .map(|line| line.split(" #").next().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(format(IndentStyle::Bars), ["│ │  └─", "│ │  │  └─"]);
+        assert_eq!(format(IndentStyle::Spaces(2)), ["│   └─", "│     └─"]);
+        assert_eq!(format(IndentStyle::Tree), ["│    └─", "│       └─"]);
+    }
+
+    #[test]
+    fn test_account_owner_filter() {
+        let from = Pubkey::new_from_array([1; 32]);
+        let to = Pubkey::new_from_array([2; 32]);
+        let program = Pubkey::new_from_array([9; 32]);
+        let mut log = sample_log();
+        log.account_states
+            .as_mut()
+            .unwrap()
+            .get_mut(&to)
+            .unwrap()
+            .owner = program;
+
+        let mut config = EnhancedLoggingConfig::debug().with_account_owner_filter([program]);
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        let rows = table_lines(&output);
+        assert!(rows.iter().any(|line| line.contains(&to.to_string())));
+        assert!(!rows.iter().any(|line| line.contains(&from.to_string())));
+        assert!(output.contains("1 account hidden by owner filter"));
+
+        config.account_owner_filter = None;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains(&from.to_string()));
+        assert!(!output.contains("hidden by owner filter"));
+    }
+
+    #[test]
+    fn test_rent_status_column() {
+        let from = Pubkey::new_from_array([1; 32]);
+        let to = Pubkey::new_from_array([2; 32]);
+        let mut log = sample_log();
+        // 1,000 lamports with 165 bytes of data is far below the rent-exempt minimum
+        let state = log.account_states.as_mut().unwrap().get_mut(&to).unwrap();
+        state.lamports_after = 1_000;
+        state.data_len_after = 165;
+        let row = |output: &str, pubkey: &Pubkey| {
+            output
+                .lines()
+                .find(|line| line.contains(&pubkey.to_string()) && line.contains('|'))
+                .unwrap()
+                .to_string()
+        };
+
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(!output.contains("Rent"));
+
+        let config = config.with_rent_status();
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("| Rent"));
+        assert!(row(&output, &from).contains("| exempt"));
+        assert!(row(&output, &to).contains("| below 2,039,280"));
+    }
+
+    #[test]
+    fn test_account_table_full_width_by_default() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&sample_log(), 1);
+
+        assert!(output.contains("Owner"));
+        assert!(output.contains(&Pubkey::new_from_array([1; 32]).to_string()));
+    }
+
+    #[test]
+    fn test_format_batch_summary() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+
+        let mut first = sample_log();
+        first.status = TransactionStatus::Success;
+        first.compute_used = 1_500;
+        first.fee = 5_000;
+        let mut second = sample_log();
+        second.status = TransactionStatus::Failed("custom program error: 0x1".to_string());
+        second.compute_used = 300;
+        second.fee = 10_000;
+
+        let output = TransactionFormatter::new(&config).format_batch(&[first, second]);
+
+        assert!(output.contains("Transaction #1"));
+        assert!(output.contains("Transaction #2"));
+        assert!(output.contains("Batch Summary (2 transactions):"));
+        assert!(output.contains("Status: 1 succeeded | 1 failed"));
+        assert!(output.contains("Total Compute Used: 1,800 CU | Total Fees: 0.000015 SOL"));
+    }
+
+    #[test]
+    fn test_lamport_display_format() {
+        assert_eq!(LamportDisplay::Lamports.format(1_500_000_000), "1500000000");
+        assert_eq!(LamportDisplay::Sol.format(1_500_000_000), "1.500000000 SOL");
+        assert_eq!(LamportDisplay::Sol.format(5_000), "0.000005000 SOL");
+        assert_eq!(
+            LamportDisplay::Both.format(1_500_000_000),
+            "1500000000 (1.5 SOL)"
+        );
+        assert_eq!(
+            LamportDisplay::Both.format(2_000_000_000),
+            "2000000000 (2 SOL)"
+        );
+        assert_eq!(
+            LamportDisplay::Sol.format(u64::MAX),
+            "18446744073.709551615 SOL"
+        );
+    }
+
+    #[test]
+    fn test_account_table_lamports_as_sol() {
+        let mut config = EnhancedLoggingConfig::debug().with_lamport_display(LamportDisplay::Sol);
+        config.use_colors = false;
+        let mut log = sample_log();
+        log.fee = 5_000;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+
+        assert!(output.contains("Fee: 0.000005000 SOL"));
+        assert!(output.contains("10.000000000 SOL"));
+        assert!(output.contains("-1.000005000 SOL"));
+        assert!(output.contains("+1.000000000 SOL"));
+    }
+
+    #[test]
+    fn test_account_count_mismatch_is_highlighted() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let mut log = sample_log();
+        log.instructions[0].account_count_mismatch = Some(crate::AccountCountMismatch {
+            expected: 3,
+            actual: 2,
+        });
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+
+        assert!(output.contains("Account count mismatch: expected 3 accounts, got 2"));
+    }
+
+    #[test]
+    fn test_format_plain_has_no_ansi_codes() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = true;
+        let mut log = sample_log();
+        log.status = TransactionStatus::Failed("custom program error: 0x1".to_string());
+        let formatter = TransactionFormatter::new(&config);
+
+        assert!(formatter.format(&log, 1).contains('\x1b'));
+        let plain = formatter.format_plain(&log, 1);
+        assert!(!plain.contains('\x1b'));
+        assert!(plain.contains("custom program error: 0x1"));
+    }
+
+    #[test]
+    fn test_warnings_section() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let mut log = sample_log();
+        assert!(!TransactionFormatter::new(&config)
+            .format(&log, 1)
+            .contains("Warnings"));
+
+        log.warnings = vec![
+            crate::DecodeWarning::new(vec![0], "no decoder registered for program X"),
+            crate::DecodeWarning::new(vec![0, 1], "expected 3 accounts, got 2"),
+        ];
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+
+        assert!(output.contains("⚠ Warnings (2):"));
+        assert!(output.contains("instruction #1: no decoder registered for program X"));
+        assert!(output.contains("instruction #1.2: expected 3 accounts, got 2"));
+    }
+
+    #[test]
+    fn test_zero_pubkey_and_incinerator_labels() {
+        let incinerator = Pubkey::from_str_const("1nc1nerator11111111111111111111111111111111");
+        let log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::new_from_array([9; 32]))
+                    .account(Pubkey::default(), false, false)
+                    .account(incinerator, false, true),
+            )
+            .build();
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        let row = |pubkey: Pubkey| {
+            output
+                .lines()
+                .find(|line| line.contains(&format!("| {pubkey} ")))
+                .unwrap()
+                .to_string()
+        };
+        assert!(row(Pubkey::default()).contains("| ‹zero pubkey›"));
+        assert!(row(incinerator).contains("| incinerator "));
+    }
+
+    #[test]
+    fn test_sysvar_labels_in_account_table() {
+        let rent = Pubkey::from_str_const("SysvarRent111111111111111111111111111111111");
+        let clock = Pubkey::from_str_const("SysvarC1ock11111111111111111111111111111111");
+        let mint = Pubkey::new_from_array([3; 32]);
+        let mut log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::new_from_array([9; 32]))
+                    .account(mint, false, true)
+                    .account(rent, false, false)
+                    .account(clock, false, false),
+            )
+            .build();
+        // The decoder names the first two accounts, leaving the clock unlabeled
+        log.instructions[0].decoded_instruction =
+            Some(DecodedInstruction::with_fields_and_accounts(
+                "InitializeMint",
+                Vec::new(),
+                vec!["mint".to_string(), "rent".to_string()],
+            ));
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        let row = |pubkey: Pubkey| {
+            output
+                .lines()
+                .find(|line| line.contains(&format!("| {pubkey} ")))
+                .unwrap()
+                .to_string()
+        };
+        assert!(row(mint).contains("| mint "));
+        assert!(row(rent).contains("| rent (sysvar) "));
+        assert!(row(clock).contains("| Clock Sysvar "));
+    }
+
+    #[test]
+    fn test_slot_and_block_time_in_header() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let log = crate::TransactionLogBuilder::new()
+            .slot(42)
+            .block_time(1_700_000_000)
+            .build();
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("Slot: 42 | Block Time: 1700000000 | Status: Unknown"));
+
+        let log = crate::TransactionLogBuilder::new().slot(42).build();
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("Slot: 42 | Status: Unknown"));
+    }
+
+    #[test]
+    fn test_transaction_label_in_header() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let log = crate::TransactionLogBuilder::new()
+            .label("attack attempt")
+            .build();
+        let output = TransactionFormatter::new(&config).format(&log, 3);
+        let header = output.lines().nth(1).unwrap();
+        assert_eq!(header, "│ ▶ attack attempt");
+
+        let output = TransactionFormatter::new(&config).format(&sample_log(), 1);
+        assert!(!output.contains('▶'));
+    }
+
+    #[test]
+    fn test_account_roles_section() {
+        let payer = Pubkey::new_from_array([1; 32]);
+        let vault = Pubkey::new_from_array([2; 32]);
+        let log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::default())
+                    .name("Transfer")
+                    .account(payer, true, true)
+                    .account(vault, false, true),
+            )
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::new_from_array([9; 32]))
+                    .name("Check")
+                    .account(vault, false, false),
+            )
+            .build();
+
+        let roles = log.account_roles();
+        assert_eq!(roles.len(), 2);
+        assert_eq!(roles[0].pubkey, payer);
+        assert!(!roles[0].has_inconsistent_access());
+        assert_eq!(roles[1].usages.len(), 2);
+        assert_eq!(roles[1].usages[1].path, vec![1]);
+        assert!(roles[1].has_inconsistent_access());
+
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        assert!(!TransactionFormatter::new(&config)
+            .format(&log, 1)
+            .contains("Account Roles"));
+
+        let mut config = config.with_account_roles();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("Account Roles (2 accounts, 1 inconsistent):"));
+        assert!(output.contains(&format!(
+            "{vault} ⚠ signer/writable differs across instructions"
+        )));
+        assert!(output.contains("#1: signer, writable"));
+        assert!(output.contains("#2: readonly"));
+    }
+
+    #[test]
+    fn test_group_by_program() {
+        let system = Pubkey::default();
+        let other = Pubkey::new_from_array([9; 32]);
+        let mut log = EnhancedTransactionLog::new(Signature::default(), 0);
+        for (i, (program_id, name)) in [
+            (system, "System Program"),
+            (other, "Other Program"),
+            (system, "System Program"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            log.instructions
+                .push(EnhancedInstructionLog::new(i, program_id, name.to_string()));
+        }
+        let mut config = EnhancedLoggingConfig::debug().with_group_by_program();
+        config.use_colors = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+
+        let system_header = output.find("▸ System Program").unwrap();
+        let other_header = output.find("▸ Other Program").unwrap();
+        assert!(output.contains("- 2 instructions"));
+        assert!(output.contains("- 1 instruction\n"));
+        // Execution numbers are kept: #1 and #3 under System, #2 under Other
+        let position = |number: &str| output.find(&format!("├─ #{} ", number)).unwrap();
+        assert!(system_header < position("1"));
+        assert!(position("1") < position("3"));
+        assert!(position("3") < other_header);
+        assert!(other_header < position("2"));
+        // The log itself stays in execution order
+        assert_eq!(log.instructions[1].program_id, other);
+    }
+
+    #[test]
+    fn test_pda_label() {
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let (counter, bump) = Pubkey::find_program_address(&[b"counter"], &program_id);
+        let mut config =
+            EnhancedLoggingConfig::debug().with_pda_seeds(program_id, "counter", &[b"counter"]);
+        config.use_colors = false;
+        let mut log = sample_log();
+        log.instructions[0].accounts[1] = AccountMeta::new(counter, false);
+        log.label_pdas(&config.pda_seeds);
+
+        assert_eq!(
+            log.pdas.get(&counter),
+            Some(&PdaMatch {
+                label: "counter".to_string(),
+                bump,
+            })
+        );
+        assert_eq!(log.pdas.len(), 1);
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains(&format!("PDA counter (bump {})", bump)));
+    }
+
+    #[test]
+    fn test_signature_display() {
+        let mut log = sample_log();
+        log.signature = Signature::from([7; 64]);
+        let full = log.signature.to_string();
+        let format = |display: SignatureDisplay| {
+            let mut config = EnhancedLoggingConfig::debug().with_signature_display(display);
+            config.use_colors = false;
+            TransactionFormatter::new(&config).format(&log, 1)
+        };
+
+        // The full signature is long enough to be wrapped; check its start
+        assert!(format(SignatureDisplay::Full).contains(&format!("│ Transaction: {}", &full[..32])));
+        let short = format(SignatureDisplay::Short);
+        assert!(short.contains(&format!(
+            "│ Transaction: {}…{} | Slot: 0 | Status: Unknown",
+            &full[..4],
+            &full[full.len() - 4..]
+        )));
+        assert!(!short.contains(&full));
+        let hidden = format(SignatureDisplay::Hidden);
+        assert!(hidden.contains("│ Slot: 0 | Status: Unknown"));
+        assert!(!hidden.contains("Transaction:"));
+    }
+
+    #[test]
+    fn test_max_output_bytes() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let log = sample_log();
+        let full = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(!full.contains("output truncated"));
+
+        let config = config.with_max_output_bytes(1000);
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        let (kept, marker) = output.trim_end().rsplit_once('\n').unwrap();
+        assert!(kept.len() < 1000);
+        assert!(full.starts_with(kept));
+        assert_eq!(marker, "… output truncated at 1000 bytes");
+    }
+
+    #[test]
+    fn test_max_output_bytes_stops_rendering_instructions() {
+        let mut config = EnhancedLoggingConfig::debug().with_max_output_bytes(1000);
+        config.use_colors = false;
+        let formatter = TransactionFormatter::new(&config);
+        let single = sample_log();
+        let mut log = single.clone();
+        log.instructions = vec![single.instructions[0].clone(); 1_000];
+
+        // Instructions past the limit are skipped instead of rendered and cut
+        let section = |log: &EnhancedTransactionLog| {
+            let mut output = String::new();
+            formatter
+                .write_instructions_section(&mut output, log)
+                .unwrap();
+            output
+        };
+        assert!(section(&log).len() < 1000 + section(&single).len());
+
+        let output = formatter.format(&log, 1);
+        assert!(output.ends_with("… output truncated at 1000 bytes\n"));
+    }
+
+    #[test]
+    fn test_net_data_len_change_footer() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let mut log = sample_log();
+        let format =
+            |log: &EnhancedTransactionLog| TransactionFormatter::new(&config).format(log, 1);
+        assert!(!format(&log).contains("Account Data:"));
+
+        for state in log.account_states.as_mut().unwrap().values_mut() {
+            state.data_len_before = 100;
+            state.data_len_after = 1_200;
+        }
+        assert_eq!(log.net_data_len_change(), 2_200);
+        assert!(format(&log).contains("| Account Data: +2,200 bytes allocated"));
+
+        for state in log.account_states.as_mut().unwrap().values_mut() {
+            state.data_len_after = 0;
+        }
+        assert_eq!(log.net_data_len_change(), -200);
+        assert!(format(&log).contains("| Account Data: -200 bytes freed"));
+    }
+
+    #[test]
+    fn test_failed_instruction_marker() {
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+        let mut log = sample_log();
+        assert!(!TransactionFormatter::new(&config)
+            .format(&log, 1)
+            .contains("FAILED HERE"));
+
+        log.failed_instruction_index = Some(0);
+        log.instructions[0].success = false;
+        let output = TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("(System Program) ✗ FAILED HERE"));
+    }
 }
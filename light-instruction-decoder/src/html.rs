@@ -0,0 +1,232 @@
+//! HTML fragment rendering of a transaction log
+//!
+//! Meant for dashboards and reports: the log becomes a single `<table>` (no
+//! `<html>`, `<head>` or styles) with CSS classes to hook styling onto. Every
+//! string taken from the transaction (names, decoded values, pubkeys, labels) is
+//! escaped.
+//!
+//! ```
+//! use light_instruction_decoder::{
+//!     format_html, EnhancedLoggingConfig, InstructionLogBuilder, TransactionLogBuilder,
+//! };
+//! use solana_pubkey::Pubkey;
+//!
+//! let log = TransactionLogBuilder::new()
+//!     .instruction(
+//!         InstructionLogBuilder::new(Pubkey::default())
+//!             .name("Transfer")
+//!             .field("memo", "<b>hi</b>"),
+//!     )
+//!     .build();
+//! let html = format_html(&log, &EnhancedLoggingConfig::default());
+//! assert!(html.starts_with("<table class=\"tx tx-unknown\">"));
+//! assert!(html.contains("&lt;b&gt;hi&lt;/b&gt;"));
+//! ```
+//!
+//! Classes used:
+//! - `tx` plus the status: `tx-success`, `tx-failed`, `tx-unknown` or `tx-not-executed`
+//! - rows: `instruction` (with `instruction-failed` when it failed), `field`,
+//!   `account` and `warning`
+//! - account rows also carry their access: `account-signer-writable`,
+//!   `account-signer`, `account-writable` or `account-readonly`
+
+use std::fmt::Write;
+
+use crate::{
+    DecodedField, EnhancedInstructionLog, EnhancedLoggingConfig, EnhancedTransactionLog,
+    LamportDisplay, TransactionStatus,
+};
+
+/// Render a transaction log as a self-contained HTML `<table>` fragment
+///
+/// The caption summarizes label, status, signature (per `config.signature_display`),
+/// fee and compute units. Each instruction is followed by its decoded fields,
+/// accounts and inner instructions, then the warnings are listed.
+pub fn format_html(log: &EnhancedTransactionLog, config: &EnhancedLoggingConfig) -> String {
+    let mut output = String::new();
+    writeln!(output, "<table class=\"tx {}\">", status_class(&log.status))
+        .expect("Failed to write table");
+
+    output.push_str("<caption>");
+    if let Some(label) = &log.label {
+        write!(output, "<span class=\"tx-label\">{}</span> ", escape(label))
+            .expect("Failed to write label");
+    }
+    write!(
+        output,
+        "<span class=\"tx-status\">{}</span>",
+        escape(&log.status.text())
+    )
+    .expect("Failed to write status");
+    if let Some(signature) = config.signature_display.format(&log.signature) {
+        write!(
+            output,
+            " <span class=\"tx-signature\">{}</span>",
+            escape(&signature)
+        )
+        .expect("Failed to write signature");
+    }
+    if log.status != TransactionStatus::NotExecuted {
+        let fee = match config.lamport_display {
+            LamportDisplay::Lamports => format!("{} lamports", log.fee),
+            display => display.format(log.fee),
+        };
+        write!(
+            output,
+            " <span class=\"tx-fee\">{}</span> <span class=\"tx-compute\">{}/{} CU</span>",
+            escape(&fee),
+            log.compute_used,
+            log.compute_total
+        )
+        .expect("Failed to write fee");
+    }
+    output.push_str("</caption>\n");
+
+    output.push_str(
+        "<thead><tr><th>#</th><th>Name</th><th>Value</th><th>Access</th></tr></thead>\n<tbody>\n",
+    );
+    for instruction in &log.instructions {
+        write_instruction(&mut output, instruction);
+    }
+    for warning in &log.warnings {
+        row(
+            &mut output,
+            "warning",
+            [&warning.location(), "warning", &warning.message, ""],
+        );
+    }
+    output.push_str("</tbody>\n</table>\n");
+    output
+}
+
+fn status_class(status: &TransactionStatus) -> &'static str {
+    match status {
+        TransactionStatus::Success => "tx-success",
+        TransactionStatus::Failed(_) => "tx-failed",
+        TransactionStatus::Unknown => "tx-unknown",
+        TransactionStatus::NotExecuted => "tx-not-executed",
+    }
+}
+
+fn write_instruction(output: &mut String, instruction: &EnhancedInstructionLog) {
+    let class = if instruction.success {
+        "instruction"
+    } else {
+        "instruction instruction-failed"
+    };
+    row(
+        output,
+        class,
+        [
+            &instruction.location(),
+            &instruction.program_name,
+            instruction.instruction_name.as_deref().unwrap_or("Unknown"),
+            "",
+        ],
+    );
+    if let Some(decoded) = &instruction.decoded_instruction {
+        for field in &decoded.fields {
+            write_field(output, field, "");
+        }
+    }
+    for (i, account) in instruction.accounts.iter().enumerate() {
+        let (class, access) = match (account.is_signer, account.is_writable) {
+            (true, true) => ("account account-signer-writable", "signer, writable"),
+            (true, false) => ("account account-signer", "signer"),
+            (false, true) => ("account account-writable", "writable"),
+            (false, false) => ("account account-readonly", "readonly"),
+        };
+        let name = match instruction.account_role(i) {
+            Some(role) => format!("account #{} {}", i + 1, role),
+            None => format!("account #{}", i + 1),
+        };
+        row(
+            output,
+            class,
+            ["", &name, &account.pubkey.to_string(), access],
+        );
+    }
+    for inner in &instruction.inner_instructions {
+        write_instruction(output, inner);
+    }
+}
+
+/// Field row named by its path through parent fields, e.g. `recipient[0].amount`
+fn write_field(output: &mut String, field: &DecodedField, parent: &str) {
+    let name = if parent.is_empty() {
+        field.name.clone()
+    } else {
+        format!("{}.{}", parent, field.name)
+    };
+    if !field.value.is_empty() || field.children.is_empty() {
+        row(output, "field", ["", &name, &field.value, ""]);
+    }
+    for child in &field.children {
+        write_field(output, child, &name);
+    }
+}
+
+fn row(output: &mut String, class: &str, cells: [&str; 4]) {
+    write!(output, "<tr class=\"{}\">", class).expect("Failed to write row");
+    for cell in cells {
+        write!(output, "<td>{}</td>", escape(cell)).expect("Failed to write cell");
+    }
+    output.push_str("</tr>\n");
+}
+
+/// Escape text for use in HTML element content and quoted attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_pubkey::Pubkey;
+
+    use super::*;
+    use crate::{InstructionLogBuilder, SignatureDisplay, TransactionLogBuilder};
+
+    #[test]
+    fn test_format_html_escapes_user_strings() {
+        let mut config = EnhancedLoggingConfig::default();
+        config.signature_display = SignatureDisplay::Hidden;
+        let log = TransactionLogBuilder::new()
+            .status(TransactionStatus::Failed(
+                "custom program error: 0x1".to_string(),
+            ))
+            .label("attack & \"retry\"")
+            .instruction(
+                InstructionLogBuilder::new(Pubkey::new_from_array([9; 32]))
+                    .program_name("Memo")
+                    .name("Memo")
+                    .field("text", "<script>alert('x')</script>")
+                    .account(Pubkey::new_from_array([1; 32]), true, false)
+                    .failed(),
+            )
+            .build();
+
+        let html = format_html(&log, &config);
+        assert!(html.starts_with("<table class=\"tx tx-failed\">\n<caption>"));
+        assert!(html.ends_with("</tbody>\n</table>\n"));
+        assert!(!html.contains("<html") && !html.contains("<head"));
+        assert!(html.contains("<span class=\"tx-label\">attack &amp; &quot;retry&quot;</span>"));
+        assert!(html.contains(
+            "<tr class=\"field\"><td></td><td>text</td>\
+             <td>&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;</td><td></td></tr>"
+        ));
+        assert!(html.contains("<tr class=\"instruction instruction-failed\"><td>#1</td>"));
+        assert!(html.contains("<tr class=\"account account-signer\">"));
+        assert!(!html.contains("tx-signature"));
+    }
+}
@@ -15,6 +15,12 @@
 //! | [`EnhancedLoggingConfig`] | Transaction logging configuration |
 //! | [`TransactionFormatter`] | Format transaction logs with ANSI colors |
 //! | [`instruction_decoder`] | Derive macro for decoder implementations |
+//! | [`anchor_instruction_discriminator`] | Anchor instruction discriminator for a name |
+//! | [`TransactionLogBuilder`] | Build transaction logs by hand, e.g. in formatter tests |
+//! | [`decode_fixture`] | Decode hex instruction data in decoder unit tests |
+//! | [`format_log_diff`] | Unified diff of two transaction logs for regression snapshots |
+//! | [`format_html`] | Transaction log as an HTML table fragment for dashboards |
+//! | [`coverage_report`] | Decoded vs unknown instruction counts across many transactions |
 //!
 //! Note: Most functionality is only available off-chain (not on Solana targets).
 
@@ -27,7 +33,14 @@ pub use solana_signature;
 
 // Core types available on all targets (needed by derive macros)
 mod core;
-pub use core::{DecodedField, DecodedInstruction, InstructionDecoder};
+pub use core::{
+    format_packed_pubkeys, AccountCountMismatch, DecodedField, DecodedInstruction,
+    InstructionDecoder,
+};
+
+// Anchor discriminators, matching what the derive macro computes (available on all targets)
+mod discriminator;
+pub use discriminator::{anchor_account_discriminator, anchor_instruction_discriminator};
 
 // LiteSVM integration (off-chain only, behind feature flag)
 #[cfg(all(feature = "litesvm", not(target_os = "solana")))]
@@ -35,10 +48,22 @@ pub mod litesvm;
 
 // Off-chain only modules (uses tabled, derive macros, DecoderRegistry)
 #[cfg(not(target_os = "solana"))]
+pub mod builder;
+#[cfg(not(target_os = "solana"))]
 pub mod config;
 #[cfg(not(target_os = "solana"))]
+pub mod coverage;
+#[cfg(not(target_os = "solana"))]
+pub mod diff;
+#[cfg(not(target_os = "solana"))]
+pub mod fixture;
+#[cfg(not(target_os = "solana"))]
 pub mod formatter;
 #[cfg(not(target_os = "solana"))]
+pub mod html;
+#[cfg(not(target_os = "solana"))]
+pub mod log_tree;
+#[cfg(not(target_os = "solana"))]
 pub mod programs;
 #[cfg(not(target_os = "solana"))]
 pub mod registry;
@@ -46,25 +71,52 @@ pub mod registry;
 pub mod types;
 
 // Re-export main types from types module
+// Re-export log builders
+#[cfg(not(target_os = "solana"))]
+pub use builder::{InstructionLogBuilder, TransactionLogBuilder};
 // Re-export config types
 #[cfg(not(target_os = "solana"))]
-pub use config::{EnhancedLoggingConfig, LogVerbosity};
+pub use config::{
+    EnhancedLoggingConfig, IndentStyle, InstructionCallback, LamportDisplay, LogVerbosity,
+    PdaSeeds, SignatureDisplay, TableWidth, DEFAULT_TRANSACTION_SEPARATOR,
+};
+// Re-export decoder coverage report
+#[cfg(not(target_os = "solana"))]
+pub use coverage::{coverage_report, CoverageReport, ProgramCoverage, UnknownInstruction};
+// Re-export log diff
+#[cfg(not(target_os = "solana"))]
+pub use diff::{format_log_diff, DiffLine};
+// Re-export hex fixture helpers for decoder tests
+#[cfg(not(target_os = "solana"))]
+pub use fixture::{decode_fixture, decode_fixture_with};
 // Re-export formatter
 #[cfg(not(target_os = "solana"))]
 pub use formatter::{Colors, TransactionFormatter};
+// Re-export HTML fragment rendering
+#[cfg(not(target_os = "solana"))]
+pub use html::format_html;
+
+#[cfg(not(target_os = "solana"))]
+pub use log_tree::{parse_program_log_tree, LogNode};
 // Re-export program decoders
 #[cfg(not(target_os = "solana"))]
 pub use programs::{
-    AccountCompressionInstructionDecoder, CTokenInstructionDecoder,
-    ComputeBudgetInstructionDecoder, LightSystemInstructionDecoder, RegistryInstructionDecoder,
-    SplTokenInstructionDecoder, SystemInstructionDecoder, Token2022InstructionDecoder,
+    AccountCompressionInstructionDecoder, AnchorFallbackDecoder, CTokenInstructionDecoder,
+    ComputeBudgetInstructionDecoder, Ed25519InstructionDecoder, LightSystemInstructionDecoder,
+    MemoInstructionDecoder, RegistryInstructionDecoder, Secp256k1InstructionDecoder,
+    SplTokenInstructionDecoder, StakeInstructionDecoder, SystemInstructionDecoder,
+    Token2022InstructionDecoder, VoteInstructionDecoder,
 };
+// Inlined Light Protocol types and their decode functions
+#[cfg(not(target_os = "solana"))]
+pub use programs::light_types;
 // Re-export registry
 #[cfg(not(target_os = "solana"))]
-pub use registry::DecoderRegistry;
+pub use registry::{DecoderConflict, DecoderRegistry};
 #[cfg(not(target_os = "solana"))]
 pub use types::{
-    AccountAccess, AccountChange, AccountStateSnapshot, CompressedAccountInfo,
-    EnhancedInstructionLog, EnhancedTransactionLog, LightProtocolEvent, MerkleTreeChange,
-    TransactionStatus,
+    AccountAccess, AccountChange, AccountRoles, AccountStateSnapshot, AccountUsage,
+    CompressedAccountInfo, DecodeWarning, DecodeWarningKind, DurableNonce, EnhancedInstructionLog,
+    EnhancedTransactionLog, LightProtocolEvent, MerkleTreeChange, PdaMatch, TransactionStats,
+    TransactionStatus, PACKET_DATA_SIZE,
 };
@@ -2,35 +2,65 @@
 //!
 //! Provides:
 //! - [`decode_transaction`] -- decode a transaction into an [`EnhancedTransactionLog`]
+//!   ([`decode_legacy_transaction`] for legacy [`Transaction`]s, [`decode_transaction_borrowed`]
+//!   to borrow instruction data instead of copying it)
+//! - [`decode_message`] -- preview-decode an unsent message (top-level instructions only)
+//! - [`decode_base64_transaction`] -- decode a base64 wire transaction, e.g. copied from logs
+//! - [`load_lookup_table_addresses`] / [`decode_transaction_with_loaded_addresses`] -- resolve
+//!   accounts loaded from address lookup tables in V0 transactions
+//! - [`decode_transactions`] / [`format_batch`] -- decode and report a sequence of transactions
+//!   ([`decode_session`] to look up their results in the LiteSVM transaction history)
 //! - [`capture_account_states`] -- capture pre/post account state (lamports, data len)
 //! - [`TransactionLogger`] -- one-line API that captures state, sends tx, decodes, formats, and logs
-//! - Snapshot types for insta JSON testing
-//! - File logging to `target/instruction_decoder.log` (ANSI-stripped)
+//! - Snapshot types for insta JSON testing (feature `serde`, on by default;
+//!   [`decode_transaction_snapshot_with_logs`] to include program logs,
+//!   [`to_yaml_string`] / [`to_ron_string`] for other formats)
+//! - File logging to `target/instruction_decoder.log` (ANSI-stripped; gzip-compressed to
+//!   `target/instruction_decoder.log.gz` with feature `flate2`)
+//! - [`create_tracing_callback`] -- emit decoded transactions to `tracing` (feature `tracing`)
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    fmt,
     fs::{self, OpenOptions},
     io::Write,
     sync::{
-        atomic::{AtomicBool, AtomicUsize, Ordering},
-        Once,
+        atomic::{AtomicUsize, Ordering},
+        Mutex, Once,
     },
 };
 
+use base64::Engine;
 use litesvm::{types::TransactionResult, LiteSVM};
+#[cfg(feature = "serde")]
 use serde::Serialize;
+use solana_clock::Clock;
 use solana_instruction::AccountMeta;
+use solana_message::{
+    compiled_instruction::CompiledInstruction, v0::LoadedAddresses, VersionedMessage,
+};
 use solana_pubkey::Pubkey;
-use solana_transaction::versioned::VersionedTransaction;
+use solana_signature::Signature;
+use solana_transaction::{versioned::VersionedTransaction, Transaction};
+use solana_transaction_error::TransactionError;
 
+pub use crate::log_tree::{parse_program_log_tree, LogNode};
 use crate::{
     config::EnhancedLoggingConfig,
     formatter::TransactionFormatter,
     types::{
-        get_program_name, AccountStateSnapshot, EnhancedInstructionLog, EnhancedTransactionLog,
-        TransactionStatus,
+        compact_u16_len, get_program_name, AccountStateSnapshot, DecodeWarning, DecodeWarningKind,
+        EnhancedInstructionLog, EnhancedTransactionLog, TransactionStatus,
     },
 };
+#[cfg(feature = "serde")]
+use crate::{
+    types::{
+        assert_compute_budget, instruction_location, sysvar_name, DurableNonce, PdaMatch,
+        TransactionStats,
+    },
+    AccountCountMismatch,
+};
 
 // ---------------------------------------------------------------------------
 // Account state capture
@@ -40,16 +70,14 @@ use crate::{
 pub type AccountStates = HashMap<Pubkey, (u64, usize, Pubkey)>;
 
 /// Capture the current account state (lamports, data length, owner) for every account
-/// referenced by the transaction.
+/// referenced by the transaction, including accounts loaded from address lookup tables.
 pub fn capture_account_states(svm: &LiteSVM, tx: &VersionedTransaction) -> AccountStates {
-    let account_keys = tx.message.static_account_keys();
+    let loaded_addresses = load_lookup_table_addresses(svm, &tx.message);
+    let account_keys = expand_account_keys(&tx.message, &loaded_addresses);
     let mut states = HashMap::new();
-    for key in account_keys {
+    for key in &account_keys {
         if let Some(account) = svm.get_account(key) {
-            states.insert(
-                *key,
-                (account.lamports, account.data.len(), account.owner),
-            );
+            states.insert(*key, (account.lamports, account.data.len(), account.owner));
         } else {
             states.insert(*key, (0, 0, Pubkey::default()));
         }
@@ -57,6 +85,71 @@ pub fn capture_account_states(svm: &LiteSVM, tx: &VersionedTransaction) -> Accou
     states
 }
 
+/// Read the slot and Unix timestamp from LiteSVM's clock sysvar.
+///
+/// Changes with `svm.warp_to_slot` and `svm.set_sysvar::<Clock>`.
+pub fn capture_clock(svm: &LiteSVM) -> (u64, i64) {
+    let clock = svm.get_sysvar::<Clock>();
+    (clock.slot, clock.unix_timestamp)
+}
+
+/// Set the log's slot and block time from a [`capture_clock`] reading.
+fn apply_clock(log: &mut EnhancedTransactionLog, (slot, unix_timestamp): (u64, i64)) {
+    log.slot = slot;
+    log.block_time = Some(unix_timestamp);
+}
+
+/// Size of the lookup table metadata preceding the addresses in a lookup table account.
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// Resolve the addresses a V0 message loads from address lookup tables, reading the
+/// tables from `svm`.
+///
+/// Must be called while the tables still hold the referenced addresses, i.e. before
+/// a transaction that extends or closes them. Addresses that cannot be resolved
+/// (missing table or index out of range) are the default pubkey so later addresses
+/// keep their position. Legacy messages load no addresses.
+pub fn load_lookup_table_addresses(svm: &LiteSVM, message: &VersionedMessage) -> LoadedAddresses {
+    let mut loaded = LoadedAddresses::default();
+    for lookup in message.address_table_lookups().unwrap_or_default() {
+        let table = svm.get_account(&lookup.account_key);
+        let addresses = table
+            .as_ref()
+            .and_then(|account| account.data.get(LOOKUP_TABLE_META_SIZE..))
+            .unwrap_or_default();
+        let address = |index: &u8| {
+            let start = *index as usize * 32;
+            addresses
+                .get(start..start + 32)
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .map(Pubkey::new_from_array)
+                .unwrap_or_default()
+        };
+        loaded
+            .writable
+            .extend(lookup.writable_indexes.iter().map(address));
+        loaded
+            .readonly
+            .extend(lookup.readonly_indexes.iter().map(address));
+    }
+    loaded
+}
+
+/// Account keys in the order compiled instructions index them: static keys, then
+/// loaded writable addresses, then loaded readonly addresses.
+fn expand_account_keys(
+    message: &VersionedMessage,
+    loaded_addresses: &LoadedAddresses,
+) -> Vec<Pubkey> {
+    message
+        .static_account_keys()
+        .iter()
+        .chain(&loaded_addresses.writable)
+        .chain(&loaded_addresses.readonly)
+        .copied()
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Transaction decoding
 // ---------------------------------------------------------------------------
@@ -66,6 +159,9 @@ pub fn capture_account_states(svm: &LiteSVM, tx: &VersionedTransaction) -> Accou
 /// When `pre_states` and `post_states` are provided, the returned log's
 /// `account_states` field is populated so the formatter can render the
 /// 8-column account table with Owner, Data Len, Lamports, and Change columns.
+///
+/// Accounts loaded from address lookup tables are not resolved; use
+/// [`decode_transaction_with_loaded_addresses`] for V0 transactions that use them.
 pub fn decode_transaction(
     tx: &VersionedTransaction,
     result: &TransactionResult,
@@ -73,13 +169,38 @@ pub fn decode_transaction(
     pre_states: Option<&AccountStates>,
     post_states: Option<&AccountStates>,
 ) -> EnhancedTransactionLog {
-    let mut log = decode_transaction_inner(tx, result, config);
+    decode_transaction_with_loaded_addresses(
+        tx,
+        result,
+        config,
+        pre_states,
+        post_states,
+        &LoadedAddresses::default(),
+    )
+}
+
+/// Decode a V0 transaction whose instructions reference accounts loaded from
+/// address lookup tables.
+///
+/// `loaded_addresses` (see [`load_lookup_table_addresses`]) is spliced after the
+/// static account keys, so top-level and inner (CPI) instructions resolve loaded
+/// accounts instead of the default pubkey. Otherwise the same as [`decode_transaction`].
+pub fn decode_transaction_with_loaded_addresses(
+    tx: &VersionedTransaction,
+    result: &TransactionResult,
+    config: &EnhancedLoggingConfig,
+    pre_states: Option<&AccountStates>,
+    post_states: Option<&AccountStates>,
+    loaded_addresses: &LoadedAddresses,
+) -> EnhancedTransactionLog {
+    let mut log =
+        decode_transaction_with_account_keys(tx, result, config, loaded_addresses).into_owned();
 
     // Populate account_states from pre/post diffs
     if let (Some(pre), Some(post)) = (pre_states, post_states) {
         let mut snapshots = HashMap::new();
         for (pubkey, &(pre_lamports, pre_data_len, owner)) in pre {
-            let (post_lamports, post_data_len, _) = post
+            let (post_lamports, post_data_len, owner_after) = post
                 .get(pubkey)
                 .copied()
                 .unwrap_or((0, 0, Pubkey::default()));
@@ -91,6 +212,7 @@ pub fn decode_transaction(
                     data_len_before: pre_data_len,
                     data_len_after: post_data_len,
                     owner,
+                    owner_after,
                 },
             );
         }
@@ -102,11 +224,13 @@ pub fn decode_transaction(
                 data_len_before: 0,
                 data_len_after: post_data_len,
                 owner,
+                owner_after: owner,
             });
         }
         log.account_states = Some(snapshots);
     }
 
+    finish_decoding(&log, config);
     log
 }
 
@@ -120,15 +244,153 @@ pub fn format_transaction(
     formatter.format(log, tx_number)
 }
 
-/// Core decode logic shared by both public APIs.
-fn decode_transaction_inner(
-    tx: &VersionedTransaction,
+/// Format a decoded transaction log without ANSI colors.
+pub fn format_transaction_plain(
+    log: &EnhancedTransactionLog,
+    config: &EnhancedLoggingConfig,
+    tx_number: usize,
+) -> String {
+    let formatter = TransactionFormatter::new(config);
+    formatter.format_plain(log, tx_number)
+}
+
+/// Decode a legacy (non-versioned) transaction with optional pre/post account states.
+///
+/// Converts to [`VersionedTransaction`] internally; see [`decode_transaction`].
+pub fn decode_legacy_transaction(
+    tx: &Transaction,
     result: &TransactionResult,
     config: &EnhancedLoggingConfig,
+    pre_states: Option<&AccountStates>,
+    post_states: Option<&AccountStates>,
 ) -> EnhancedTransactionLog {
+    let tx = VersionedTransaction::from(tx.clone());
+    decode_transaction(&tx, result, config, pre_states, post_states)
+}
+
+/// Decode a sequence of transactions (e.g. the steps of a multi-transaction scenario).
+///
+/// Account states are not captured; use [`decode_transaction`] per step for
+/// pre/post diffs.
+pub fn decode_transactions(
+    txs: &[(VersionedTransaction, TransactionResult)],
+    config: &EnhancedLoggingConfig,
+) -> Vec<EnhancedTransactionLog> {
+    txs.iter()
+        .map(|(tx, result)| decode_transaction(tx, result, config, None, None))
+        .collect()
+}
+
+/// Decode transactions sent earlier in a LiteSVM session, e.g. to report a whole test run.
+///
+/// LiteSVM's transaction history keeps the result of each transaction by signature,
+/// but not the transaction itself, so the transactions must still be passed in; the
+/// result of each is looked up by its first signature. Transactions missing from the
+/// history (never sent, or dropped by `LiteSVM::with_transaction_history`) are
+/// decoded like [`decode_message`] with status [`TransactionStatus::NotExecuted`],
+/// keeping their signature. Account states are not available after the fact.
+pub fn decode_session(
+    svm: &LiteSVM,
+    txs: &[VersionedTransaction],
+    config: &EnhancedLoggingConfig,
+) -> Vec<EnhancedTransactionLog> {
+    txs.iter()
+        .map(|tx| {
+            let signature = tx.signatures.first().copied().unwrap_or_default();
+            match svm.get_transaction(&signature) {
+                Some(result) => decode_transaction(tx, result, config, None, None),
+                None => {
+                    let mut log = decode_message(&tx.message, config);
+                    log.signature = signature;
+                    log
+                }
+            }
+        })
+        .collect()
+}
+
+/// Format a sequence of decoded transactions as one numbered report with an
+/// overall summary (total compute, total fees, success/failure counts) at the end.
+pub fn format_batch(logs: &[EnhancedTransactionLog], config: &EnhancedLoggingConfig) -> String {
+    let formatter = TransactionFormatter::new(config);
+    formatter.format_batch(logs)
+}
+
+/// Steps every decode path ends with once the log is complete (account states
+/// included): the instruction callback, then the `strict_unknown` check.
+fn finish_decoding(log: &EnhancedTransactionLog, config: &EnhancedLoggingConfig) {
+    config.notify_instructions(&log.instructions);
+    if config.strict_unknown {
+        assert_all_decoded(log, config);
+    }
+}
+
+/// Panic listing every instruction of `log` that no decoder handled
+fn assert_all_decoded(log: &EnhancedTransactionLog, config: &EnhancedLoggingConfig) {
+    fn collect(instructions: &[EnhancedInstructionLog], undecoded: &mut Vec<String>) {
+        for ix in instructions {
+            if ix.decoded_instruction.is_none() {
+                let discriminator = ix
+                    .discriminator
+                    .as_ref()
+                    .map_or_else(|| "none".to_string(), |bytes| crate::core::hex(bytes));
+                undecoded.push(format!(
+                    "  [{}] {}: program {}, discriminator {}\n",
+                    ix.location(),
+                    ix.program_name,
+                    ix.program_id,
+                    discriminator
+                ));
+            }
+            collect(&ix.inner_instructions, undecoded);
+        }
+    }
+
+    let mut undecoded = Vec::new();
+    collect(&log.instructions, &mut undecoded);
+    if undecoded.is_empty() {
+        return;
+    }
+    panic!(
+        "{} instruction(s) could not be decoded (strict_unknown is set):\n{}\n{}",
+        undecoded.len(),
+        undecoded.concat(),
+        format_transaction_plain(log, config, 1)
+    );
+}
+
+/// Decode a transaction without copying instruction data.
+///
+/// The returned log borrows each instruction's data from `tx` and `result`, which
+/// avoids one allocation per instruction when decoding in hot loops (benchmarks,
+/// bulk processing). Account states are not captured. Call
+/// [`EnhancedTransactionLog::into_owned`] to keep the log beyond the transaction
+/// or to format it.
+///
+/// The instruction callback and the `strict_unknown` check run on an owned copy
+/// of the log, so setting either gives up the saved allocations.
+pub fn decode_transaction_borrowed<'a>(
+    tx: &'a VersionedTransaction,
+    result: &'a TransactionResult,
+    config: &EnhancedLoggingConfig,
+) -> EnhancedTransactionLog<&'a [u8]> {
+    let log = decode_transaction_with_account_keys(tx, result, config, &LoadedAddresses::default());
+    if config.on_instruction.is_some() || config.strict_unknown {
+        finish_decoding(&log.clone().into_owned(), config);
+    }
+    log
+}
+
+/// Decode against the static account keys followed by `loaded_addresses`.
+fn decode_transaction_with_account_keys<'a>(
+    tx: &'a VersionedTransaction,
+    result: &'a TransactionResult,
+    config: &EnhancedLoggingConfig,
+    loaded_addresses: &LoadedAddresses,
+) -> EnhancedTransactionLog<&'a [u8]> {
     use litesvm::types::FailedTransactionMetadata;
 
-    let account_keys = tx.message.static_account_keys();
+    let account_keys = expand_account_keys(&tx.message, loaded_addresses);
     let signature = tx.signatures.first().copied().unwrap_or_default();
 
     let (status, meta) = match result {
@@ -138,53 +400,314 @@ fn decode_transaction_inner(
         }
     };
 
-    let mut log = EnhancedTransactionLog::new(signature, 0);
+    let mut log = EnhancedTransactionLog::empty(signature, 0);
+    if let Err(FailedTransactionMetadata {
+        err: TransactionError::InstructionError(index, _),
+        ..
+    }) = result
+    {
+        log.failed_instruction_index = Some(*index as usize);
+    }
     log.status = status;
+    log.record_signature_count(
+        tx.message.header().num_required_signatures as usize,
+        tx.signatures.len(),
+    );
+    record_message_size(&mut log, &tx.message);
     log.compute_used = meta.compute_units_consumed;
-    log.fee = (tx.signatures.len() as u64) * 5000;
     log.program_logs_pretty = meta.pretty_logs();
 
-    let registry = config.decoder_registry();
+    // Top-level invocations as seen in the program logs, to notice unrecorded CPIs
+    let log_tree = parse_program_log_tree(&meta.logs);
     for (ix_index, compiled_ix) in tx.message.instructions().iter().enumerate() {
-        let program_id = account_keys
-            .get(compiled_ix.program_id_index as usize)
-            .copied()
-            .unwrap_or_default();
-        let program_name = get_program_name(&program_id, registry);
-
-        let mut ix_log = EnhancedInstructionLog::new(ix_index, program_id, program_name);
-        ix_log.data = compiled_ix.data.clone();
-        ix_log.accounts = resolve_accounts(&compiled_ix.accounts, account_keys, &tx.message);
-        ix_log.depth = 0;
-        ix_log.decode(config);
+        let mut ix_log = decode_top_level_instruction(
+            ix_index,
+            compiled_ix,
+            &account_keys,
+            &tx.message,
+            config,
+            &mut log.warnings,
+        );
 
         if let Some(inner_ixs) = meta.inner_instructions.get(ix_index) {
-            parse_inner_instructions(inner_ixs, account_keys, &tx.message, config, &mut ix_log);
+            parse_inner_instructions(
+                inner_ixs,
+                &account_keys,
+                &tx.message,
+                config,
+                &mut ix_log,
+                &mut log.warnings,
+            );
+        }
+        if let Some(message) = unrecorded_cpi_warning(&ix_log, log_tree.get(ix_index)) {
+            log.warnings.push(
+                DecodeWarning::new(vec![ix_index], message)
+                    .with_kind(DecodeWarningKind::UnrecordedCpi),
+            );
         }
 
+        ix_log.success = log.failed_instruction_index != Some(ix_index);
         log.instructions.push(ix_log);
     }
+    log.attribute_compute_units(&meta.logs);
+    log.read_compute_budget();
+    log.record_fees(tx.signatures.len());
+    log.detect_durable_nonce(tx.message.recent_blockhash());
+    log.label_pdas(&config.pda_seeds);
 
     log
 }
 
+/// Decode a message that has not been sent, e.g. to preview what a transaction will do.
+///
+/// Only top-level instructions are decoded. Without an execution result there are
+/// no inner instructions, compute units, fee or program logs; the status is
+/// [`TransactionStatus::NotExecuted`] and the signature is the default one.
+pub fn decode_message(
+    message: &VersionedMessage,
+    config: &EnhancedLoggingConfig,
+) -> EnhancedTransactionLog {
+    let mut log = EnhancedTransactionLog::empty(Signature::default(), 0);
+    log.status = TransactionStatus::NotExecuted;
+    // Counted towards the transaction size once signed
+    log.signatures_required = message.header().num_required_signatures as usize;
+    record_message_size(&mut log, message);
+    for (ix_index, compiled_ix) in message.instructions().iter().enumerate() {
+        let ix_log = decode_top_level_instruction(
+            ix_index,
+            compiled_ix,
+            message.static_account_keys(),
+            message,
+            config,
+            &mut log.warnings,
+        );
+        log.instructions.push(ix_log);
+    }
+    log.read_compute_budget();
+    log.detect_durable_nonce(message.recent_blockhash());
+    log.label_pdas(&config.pda_seeds);
+
+    let log = log.into_owned();
+    finish_decoding(&log, config);
+    log
+}
+
+/// Error returned by [`decode_base64_transaction`] for input that is not a wire transaction.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input is not valid base64
+    Base64(base64::DecodeError),
+    /// The decoded bytes are not a bincode-serialized [`VersionedTransaction`]
+    Bincode(bincode::Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Base64(err) => write!(f, "invalid base64: {}", err),
+            DecodeError::Bincode(err) => write!(f, "invalid transaction bytes: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::Base64(err) => Some(err),
+            DecodeError::Bincode(err) => Some(err),
+        }
+    }
+}
+
+/// Decode a base64-encoded wire transaction (bincode-serialized [`VersionedTransaction`]).
+///
+/// With a `result`, decodes like [`decode_transaction`] (without account states).
+/// Without one, decodes like [`decode_message`]: status
+/// [`TransactionStatus::NotExecuted`] and no inner instructions, but keeping the
+/// transaction's signature.
+pub fn decode_base64_transaction(
+    b64: &str,
+    result: Option<&TransactionResult>,
+    config: &EnhancedLoggingConfig,
+) -> Result<EnhancedTransactionLog, DecodeError> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(b64.trim())
+        .map_err(DecodeError::Base64)?;
+    let tx: VersionedTransaction = bincode::deserialize(&bytes).map_err(DecodeError::Bincode)?;
+
+    Ok(match result {
+        Some(result) => decode_transaction(&tx, result, config, None, None),
+        None => {
+            let mut log = decode_message(&tx.message, config);
+            log.signature = tx.signatures.first().copied().unwrap_or_default();
+            log.record_signature_count(
+                tx.message.header().num_required_signatures as usize,
+                tx.signatures.len(),
+            );
+            log
+        }
+    })
+}
+
+/// Record the serialized size of `message` (see [`EnhancedTransactionLog::record_message_size`]).
+fn record_message_size<D: AsRef<[u8]>>(
+    log: &mut EnhancedTransactionLog<D>,
+    message: &VersionedMessage,
+) {
+    if let Ok(size) = bincode::serialized_size(message) {
+        log.record_message_size(size as usize);
+    }
+}
+
+/// Decode one top-level instruction (without inner instructions), collecting its warnings.
+fn decode_top_level_instruction<'a>(
+    ix_index: usize,
+    compiled_ix: &'a CompiledInstruction,
+    account_keys: &[Pubkey],
+    message: &VersionedMessage,
+    config: &EnhancedLoggingConfig,
+    warnings: &mut Vec<DecodeWarning>,
+) -> EnhancedInstructionLog<&'a [u8]> {
+    let mut messages = Vec::new();
+    let program_id = resolve_program_id(compiled_ix.program_id_index, account_keys, &mut messages);
+    let program_name = get_program_name(&program_id, config.decoder_registry());
+
+    let mut ix_log = EnhancedInstructionLog::with_data(
+        ix_index,
+        program_id,
+        program_name,
+        compiled_ix.data.as_slice(),
+    );
+    ix_log.accounts = resolve_accounts(&compiled_ix.accounts, account_keys, message, &mut messages);
+    ix_log.depth = 0;
+    ix_log.path = vec![ix_index];
+    ix_log.instruction_size_bytes = Some(
+        1 + compact_u16_len(compiled_ix.accounts.len())
+            + compiled_ix.accounts.len()
+            + compact_u16_len(compiled_ix.data.len())
+            + compiled_ix.data.len(),
+    );
+    ix_log.decode(config);
+    messages.extend(ix_log.decode_warnings(config));
+    warnings.extend(
+        messages
+            .into_iter()
+            .map(|(kind, message)| DecodeWarning::new(vec![ix_index], message).with_kind(kind)),
+    );
+    ix_log
+}
+
 // ---------------------------------------------------------------------------
-// Snapshot types (JSON-serializable for insta)
+// Snapshot types (JSON-serializable for insta, feature `serde`)
 // ---------------------------------------------------------------------------
 
 /// JSON-serializable snapshot of an entire transaction.
+#[cfg(feature = "serde")]
 #[derive(Debug, Serialize)]
 pub struct TransactionSnapshot {
+    /// See [`EnhancedTransactionLog::label`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
     pub signature: String,
+    /// Slot the transaction executed at; omitted when 0 (clock never advanced)
+    #[serde(skip_serializing_if = "is_zero")]
+    pub slot: u64,
+    /// Unix timestamp of the SVM clock, see [`EnhancedTransactionLog::block_time`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<i64>,
     pub status: String,
+    /// Top-level instruction a failed transaction stopped at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_instruction: Option<FailedInstructionSnapshot>,
     pub fee: u64,
     pub compute_used: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_price: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_unit_limit: Option<u32>,
+    /// See [`EnhancedTransactionLog::durable_nonce`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub durable_nonce: Option<DurableNonce>,
+    /// Serialized message size in bytes, only filled when `config.show_transaction_size` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_message_size: Option<usize>,
     pub instructions: Vec<InstructionSnapshot>,
+    pub stats: TransactionStats,
+    /// Net account data size change in bytes, see `EnhancedTransactionLog::net_data_len_change`
+    #[serde(skip_serializing_if = "is_zero")]
+    pub net_data_len_change: i64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<DecodeWarning>,
+    /// Program log lines, only filled by the `*_with_logs` snapshot functions
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub program_logs: Vec<String>,
+    /// Invoke tree parsed from the program logs, only filled when
+    /// `config.include_log_tree` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_log_tree: Option<Vec<LogNode>>,
+    /// Captured account states sorted by pubkey, only filled when
+    /// `config.show_rent_status` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_states: Option<Vec<AccountStateDiffSnapshot>>,
+}
+
+#[cfg(feature = "serde")]
+impl TransactionSnapshot {
+    /// Compute units consumed by the transaction
+    pub fn compute_used(&self) -> u64 {
+        self.compute_used
+    }
+
+    /// Panic if the transaction consumed more than `max` compute units
+    ///
+    /// For per-program budgets, use [`EnhancedTransactionLog::assert_program_compute_under`].
+    #[track_caller]
+    pub fn assert_compute_under(&self, max: u64) {
+        assert_compute_budget("transaction", self.compute_used, max);
+    }
+}
+
+/// JSON-serializable pre and post state of an account, with its rent-exemption status.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccountStateDiffSnapshot {
+    pub pubkey: String,
+    pub lamports_before: u64,
+    pub lamports_after: u64,
+    pub data_len_before: usize,
+    pub data_len_after: usize,
+    /// See [`AccountStateSnapshot::is_rent_exempt`]
+    pub rent_exempt: bool,
+    /// Rent-exempt minimum balance for `data_len_after`
+    pub rent_exempt_minimum: u64,
+}
+
+/// JSON-serializable reference to the instruction a transaction failed at.
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize)]
+pub struct FailedInstructionSnapshot {
+    /// 1-based position of the failed top-level instruction, e.g. "#2", in the
+    /// same form as [`InstructionSnapshot::path`]
+    pub path: String,
+    pub program_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_name: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
 }
 
 /// JSON-serializable snapshot of a single instruction (including inner/CPI).
+#[cfg(feature = "serde")]
 #[derive(Debug, Serialize)]
 pub struct InstructionSnapshot {
+    /// 1-based position in the instruction tree, e.g. "#1.3.2" for the second
+    /// inner instruction of the third inner instruction of the first top-level
+    /// instruction. See [`EnhancedInstructionLog::location`]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub path: String,
     pub program_id: String,
     pub program_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -192,26 +715,45 @@ pub struct InstructionSnapshot {
     pub accounts: Vec<AccountSnapshot>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub decoded_fields: Option<Vec<FieldSnapshot>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_count_mismatch: Option<AccountCountMismatch>,
+    /// Hex-encoded [`EnhancedInstructionLog::discriminator`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub inner_instructions: Vec<InstructionSnapshot>,
 }
 
 /// JSON-serializable snapshot of an account reference within an instruction.
+#[cfg(feature = "serde")]
 #[derive(Debug, Serialize)]
 pub struct AccountSnapshot {
     pub pubkey: String,
     pub is_signer: bool,
     pub is_writable: bool,
+    /// Built-in label for well-known accounts (e.g. "Rent Sysvar")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub known_name: Option<String>,
+    /// Role of the account in the instruction, as labeled by the decoder (e.g. "destination")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Registered PDA derivation this account matches (see [`EnhancedLoggingConfig::with_pda_seeds`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pda: Option<PdaMatch>,
 }
 
 /// JSON-serializable snapshot of a decoded instruction field.
-#[derive(Debug, Serialize)]
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize)]
 pub struct FieldSnapshot {
     pub name: String,
     pub value: String,
 }
 
 /// Convert a decoded transaction log into a JSON-serializable snapshot.
+///
+/// The signature is rendered according to `config.signature_display`.
+#[cfg(feature = "serde")]
 pub fn decode_transaction_snapshot(
     tx: &VersionedTransaction,
     result: &TransactionResult,
@@ -220,25 +762,189 @@ pub fn decode_transaction_snapshot(
     post_states: Option<&AccountStates>,
 ) -> TransactionSnapshot {
     let log = decode_transaction(tx, result, config, pre_states, post_states);
-    transaction_log_to_snapshot(&log)
+    let mut snapshot = transaction_log_to_snapshot(&log);
+    apply_snapshot_config(&mut snapshot, &log, result, config);
+    snapshot
 }
 
 /// Convert an [`EnhancedTransactionLog`] into a [`TransactionSnapshot`].
+#[cfg(feature = "serde")]
 pub fn transaction_log_to_snapshot(log: &EnhancedTransactionLog) -> TransactionSnapshot {
     TransactionSnapshot {
+        label: log.label.clone(),
         signature: log.signature.to_string(),
+        slot: log.slot,
+        block_time: log.block_time,
         status: log.status.text(),
+        failed_instruction: log.failed_instruction_index.map(|index| {
+            let instruction = log.instructions.get(index);
+            FailedInstructionSnapshot {
+                path: instruction_location(&[index]),
+                program_name: instruction
+                    .map(|ix| ix.program_name.clone())
+                    .unwrap_or_default(),
+                instruction_name: instruction.and_then(|ix| ix.instruction_name.clone()),
+            }
+        }),
         fee: log.fee,
         compute_used: log.compute_used,
+        compute_unit_price: log.compute_unit_price,
+        compute_unit_limit: log.compute_unit_limit,
+        durable_nonce: log.durable_nonce.clone(),
+        total_message_size: None,
         instructions: log
             .instructions
             .iter()
-            .map(instruction_to_snapshot)
+            .map(|ix| instruction_to_snapshot(ix, &log.pdas))
             .collect(),
+        stats: log.stats(),
+        net_data_len_change: log.net_data_len_change(),
+        warnings: log.warnings.clone(),
+        program_logs: Vec::new(),
+        program_log_tree: None,
+        account_states: None,
     }
 }
 
-fn instruction_to_snapshot(ix: &EnhancedInstructionLog) -> InstructionSnapshot {
+/// Like [`decode_transaction_snapshot`], but including the program logs.
+///
+/// See [`transaction_log_to_snapshot_with_logs`] for `redact_addresses`.
+#[cfg(feature = "serde")]
+pub fn decode_transaction_snapshot_with_logs(
+    tx: &VersionedTransaction,
+    result: &TransactionResult,
+    config: &EnhancedLoggingConfig,
+    pre_states: Option<&AccountStates>,
+    post_states: Option<&AccountStates>,
+    redact_addresses: bool,
+) -> TransactionSnapshot {
+    let log = decode_transaction(tx, result, config, pre_states, post_states);
+    let mut snapshot = transaction_log_to_snapshot_with_logs(&log, redact_addresses);
+    apply_snapshot_config(&mut snapshot, &log, result, config);
+    snapshot
+}
+
+/// Render the signature per `config.signature_display` (`<hidden>` if hidden), add
+/// the program log tree (parsed from `result`'s logs) if `config.include_log_tree` is
+/// set, the account states if `config.show_rent_status` is set and the message size if
+/// `config.show_transaction_size` is set, and keep only the decoded fields named in
+/// `config.field_filter` (without `proof` unless `config.show_proof_bytes` is set).
+#[cfg(feature = "serde")]
+fn apply_snapshot_config(
+    snapshot: &mut TransactionSnapshot,
+    log: &EnhancedTransactionLog,
+    result: &TransactionResult,
+    config: &EnhancedLoggingConfig,
+) {
+    snapshot.signature = config
+        .signature_display
+        .format(&log.signature)
+        .unwrap_or_else(|| "<hidden>".to_string());
+    if config.include_log_tree {
+        let logs = match result {
+            Ok(meta) => &meta.logs,
+            Err(failed) => &failed.meta.logs,
+        };
+        snapshot.program_log_tree = Some(parse_program_log_tree(logs));
+    }
+    if config.show_rent_status {
+        snapshot.account_states = log.account_states.as_ref().map(account_state_diffs);
+    }
+    if config.show_transaction_size {
+        snapshot.total_message_size = log.total_message_size;
+    }
+    filter_snapshot_fields(&mut snapshot.instructions, config);
+}
+
+/// Replace the decoded fields of `instructions` and their inner instructions by
+/// those named in `config.field_filter` (in its order, all fields when unset),
+/// dropping fields `config` hides.
+#[cfg(feature = "serde")]
+fn filter_snapshot_fields(
+    instructions: &mut [InstructionSnapshot],
+    config: &EnhancedLoggingConfig,
+) {
+    for ix in instructions {
+        if let Some(fields) = ix.decoded_fields.as_mut() {
+            if let Some(filter) = &config.field_filter {
+                *fields = filter
+                    .iter()
+                    .filter_map(|name| fields.iter().find(|field| &field.name == name).cloned())
+                    .collect();
+            }
+            fields.retain(|field| config.shows_field(&field.name));
+        }
+        filter_snapshot_fields(&mut ix.inner_instructions, config);
+    }
+}
+
+/// Account states as snapshots, sorted by pubkey for stable output.
+#[cfg(feature = "serde")]
+fn account_state_diffs(
+    states: &HashMap<Pubkey, AccountStateSnapshot>,
+) -> Vec<AccountStateDiffSnapshot> {
+    let mut diffs: Vec<AccountStateDiffSnapshot> = states
+        .iter()
+        .map(|(pubkey, state)| AccountStateDiffSnapshot {
+            pubkey: pubkey.to_string(),
+            lamports_before: state.lamports_before,
+            lamports_after: state.lamports_after,
+            data_len_before: state.data_len_before,
+            data_len_after: state.data_len_after,
+            rent_exempt: state.is_rent_exempt(),
+            rent_exempt_minimum: state.rent_exempt_minimum(),
+        })
+        .collect();
+    diffs.sort_by(|a, b| a.pubkey.cmp(&b.pubkey));
+    diffs
+}
+
+/// Convert an [`EnhancedTransactionLog`] into a [`TransactionSnapshot`] including
+/// its program logs (ANSI-stripped, one entry per non-empty line).
+///
+/// With `redact_addresses`, addresses other than the invoked program IDs (e.g.
+/// freshly generated keypairs) are replaced by `<address N>` placeholders,
+/// numbered in order of first appearance, so snapshots stay stable across runs.
+#[cfg(feature = "serde")]
+pub fn transaction_log_to_snapshot_with_logs(
+    log: &EnhancedTransactionLog,
+    redact_addresses: bool,
+) -> TransactionSnapshot {
+    let mut snapshot = transaction_log_to_snapshot(log);
+    let lines = strip_ansi_codes(&log.program_logs_pretty)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    snapshot.program_logs = if redact_addresses {
+        let mut programs = HashSet::new();
+        collect_program_ids(&log.instructions, &mut programs);
+        redact_log_addresses(lines, &programs)
+    } else {
+        lines
+    };
+    snapshot
+}
+
+/// Serialize a [`TransactionSnapshot`] as YAML (feature `yaml`).
+///
+/// For use with `insta::assert_yaml_snapshot!` or plain `assert_snapshot!`.
+#[cfg(feature = "yaml")]
+pub fn to_yaml_string(snapshot: &TransactionSnapshot) -> Result<String, serde_yaml::Error> {
+    serde_yaml::to_string(snapshot)
+}
+
+/// Serialize a [`TransactionSnapshot`] as pretty-printed RON (feature `ron`).
+#[cfg(feature = "ron")]
+pub fn to_ron_string(snapshot: &TransactionSnapshot) -> Result<String, ron::Error> {
+    ron::ser::to_string_pretty(snapshot, ron::ser::PrettyConfig::default())
+}
+
+#[cfg(feature = "serde")]
+fn instruction_to_snapshot(
+    ix: &EnhancedInstructionLog,
+    pdas: &HashMap<Pubkey, PdaMatch>,
+) -> InstructionSnapshot {
     let decoded_fields = ix.decoded_instruction.as_ref().map(|decoded| {
         decoded
             .fields
@@ -253,23 +959,37 @@ fn instruction_to_snapshot(ix: &EnhancedInstructionLog) -> InstructionSnapshot {
     let accounts: Vec<AccountSnapshot> = ix
         .accounts
         .iter()
-        .map(|a| AccountSnapshot {
+        .enumerate()
+        .map(|(idx, a)| AccountSnapshot {
             pubkey: a.pubkey.to_string(),
             is_signer: a.is_signer,
             is_writable: a.is_writable,
+            known_name: sysvar_name(&a.pubkey).map(str::to_string),
+            role: ix.account_role(idx).map(str::to_string),
+            pda: pdas.get(&a.pubkey).cloned(),
         })
         .collect();
 
     InstructionSnapshot {
+        path: if ix.path.is_empty() {
+            String::new()
+        } else {
+            ix.location()
+        },
         program_id: ix.program_id.to_string(),
         program_name: ix.program_name.clone(),
         instruction_name: ix.instruction_name.clone(),
         accounts,
         decoded_fields,
+        account_count_mismatch: ix.account_count_mismatch,
+        discriminator: ix
+            .discriminator
+            .as_ref()
+            .map(|bytes| crate::core::hex(bytes)),
         inner_instructions: ix
             .inner_instructions
             .iter()
-            .map(instruction_to_snapshot)
+            .map(|inner| instruction_to_snapshot(inner, pdas))
             .collect(),
     }
 }
@@ -279,8 +999,11 @@ fn instruction_to_snapshot(ix: &EnhancedInstructionLog) -> InstructionSnapshot {
 // ---------------------------------------------------------------------------
 
 static LOG_INIT: Once = Once::new();
-static LOG_INIT_DONE: AtomicBool = AtomicBool::new(false);
 const LOG_PATH: &str = "target/instruction_decoder.log";
+#[cfg(feature = "flate2")]
+static COMPRESSED_LOG_INIT: Once = Once::new();
+#[cfg(feature = "flate2")]
+const COMPRESSED_LOG_PATH: &str = "target/instruction_decoder.log.gz";
 
 /// Strip ANSI escape codes from text.
 pub fn strip_ansi_codes(text: &str) -> String {
@@ -310,24 +1033,63 @@ pub fn strip_ansi_codes(text: &str) -> String {
 /// On first call per process, the file is truncated (session init).
 /// Subsequent calls append.
 pub fn write_to_log_file(content: &str) {
-    LOG_INIT.call_once(|| {
+    append_to_log_file(&strip_ansi_codes(content), false);
+}
+
+/// Append ANSI-stripped content to `target/instruction_decoder.log.gz` (feature `flate2`).
+///
+/// Like [`write_to_log_file`], the file is truncated on the first call per process.
+/// Each call appends a complete gzip member, so the file stays readable with
+/// `zcat` or `gunzip` even if the process is killed mid-run.
+#[cfg(feature = "flate2")]
+pub fn write_to_compressed_log_file(content: &str) {
+    append_to_log_file(&strip_ansi_codes(content), true);
+}
+
+/// Plain log file block for transaction `tx_number`: the configured separator
+/// line, if any, followed by the `formatted` transaction.
+fn log_file_block(config: &EnhancedLoggingConfig, formatted: &str, tx_number: usize) -> String {
+    match &config.transaction_separator {
+        Some(separator) => {
+            let separator = separator.replace("{tx_number}", &tx_number.to_string());
+            format!("{}\n{}", strip_ansi_codes(&separator), formatted)
+        }
+        None => formatted.to_string(),
+    }
+}
+
+/// Append already plain content to the log file (see [`write_to_log_file`]),
+/// gzip-compressed if `compress` is set and the `flate2` feature is enabled.
+#[cfg_attr(not(feature = "flate2"), allow(unused_variables))]
+fn append_to_log_file(content: &str, compress: bool) {
+    #[cfg(feature = "flate2")]
+    if compress {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        if encoder.write_all(content.as_bytes()).is_ok() {
+            if let Ok(member) = encoder.finish() {
+                append_bytes(&COMPRESSED_LOG_INIT, COMPRESSED_LOG_PATH, &member);
+            }
+        }
+        return;
+    }
+    append_bytes(&LOG_INIT, LOG_PATH, content.as_bytes());
+}
+
+/// Append `bytes` to the file at `path` with a single write, truncating it on the
+/// first call per process (`init`).
+fn append_bytes(init: &Once, path: &str, bytes: &[u8]) {
+    // Blocks concurrent writers until the session's truncation is done
+    init.call_once(|| {
         // Ensure target/ directory exists
         let _ = fs::create_dir_all("target");
         // Truncate on first write of this session
-        if let Ok(mut f) = fs::File::create(LOG_PATH) {
+        if let Ok(mut f) = fs::File::create(path) {
             let _ = f.write_all(b"");
         }
-        LOG_INIT_DONE.store(true, Ordering::Release);
     });
 
-    // Wait for init (should be instant after Once completes)
-    while !LOG_INIT_DONE.load(Ordering::Acquire) {
-        std::hint::spin_loop();
-    }
-
-    let stripped = strip_ansi_codes(content);
-    if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(LOG_PATH) {
-        let _ = f.write_all(stripped.as_bytes());
+    if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = f.write_all(bytes);
     }
 }
 
@@ -345,7 +1107,10 @@ pub fn write_to_log_file(content: &str) {
 /// ```
 ///
 /// The callback writes to the log file and prints to stderr based on config
-/// (failed txs always print; all txs print when `config.log_events` is set).
+/// (failed txs always print; all txs print when `config.log_events` is set;
+/// nothing prints when `config.suppress_console_output` is set).
+///
+/// The slot and block time come from the SVM's clock sysvar (see [`capture_clock`]).
 ///
 /// Note: Since this fires after the transaction, it cannot capture pre-state.
 /// For pre/post account state diffs, use [`TransactionLogger`] instead.
@@ -353,20 +1118,101 @@ pub fn create_logging_callback(
     config: EnhancedLoggingConfig,
 ) -> impl Fn(&VersionedTransaction, &TransactionResult, &LiteSVM) {
     let counter = AtomicUsize::new(0);
-    move |tx: &VersionedTransaction, result: &TransactionResult, _svm: &LiteSVM| {
+    move |tx: &VersionedTransaction, result: &TransactionResult, svm: &LiteSVM| {
         let tx_number = counter.fetch_add(1, Ordering::Relaxed) + 1;
-        let log = decode_transaction(tx, result, &config, None, None);
-        let formatted = format_transaction(&log, &config, tx_number);
+        let mut log = decode_transaction(tx, result, &config, None, None);
+        apply_clock(&mut log, capture_clock(svm));
+        let formatter = TransactionFormatter::new(&config);
 
-        write_to_log_file(&formatted);
+        append_to_log_file(
+            &log_file_block(&config, &formatter.format_plain(&log, tx_number), tx_number),
+            config.compress_log,
+        );
 
-        let should_print = config.log_events || result.is_err();
+        let should_print =
+            !config.suppress_console_output && (config.log_events || result.is_err());
         if should_print {
-            eprint!("{}", formatted);
+            eprint!("{}", formatter.format(&log, tx_number));
         }
     }
 }
 
+/// Create a callback that decodes each transaction and emits it to `tracing`
+/// instead of stderr/file.
+///
+/// Each transaction gets a `transaction` span with `signature`, `status`,
+/// `compute_used`, and `fee` fields, containing one event per instruction
+/// (including inner instructions) and a final summary event. Failed
+/// transactions are logged at `WARN`, everything else at `INFO`.
+///
+/// ```ignore
+/// let callback = create_tracing_callback(config);
+/// let result = svm.send_transaction(tx.clone());
+/// callback(&tx, &result, &svm);
+/// ```
+#[cfg(feature = "tracing")]
+pub fn create_tracing_callback(
+    config: EnhancedLoggingConfig,
+) -> impl Fn(&VersionedTransaction, &TransactionResult, &LiteSVM) {
+    let counter = AtomicUsize::new(0);
+    move |tx: &VersionedTransaction, result: &TransactionResult, svm: &LiteSVM| {
+        let tx_number = counter.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut log = decode_transaction(tx, result, &config, None, None);
+        apply_clock(&mut log, capture_clock(svm));
+        trace_transaction(&log, tx_number);
+    }
+}
+
+/// Emit a decoded transaction as a `tracing` span with nested instruction events.
+#[cfg(feature = "tracing")]
+pub fn trace_transaction(log: &EnhancedTransactionLog, tx_number: usize) {
+    let span = tracing::info_span!(
+        "transaction",
+        tx_number,
+        signature = %log.signature,
+        status = %log.status.text(),
+        compute_used = log.compute_used,
+        fee = log.fee,
+    );
+    let _guard = span.enter();
+
+    for (i, instruction) in log.instructions.iter().enumerate() {
+        trace_instruction(instruction, &(i + 1).to_string());
+    }
+    for warning in &log.warnings {
+        tracing::warn!(location = %warning.location(), "{}", warning.message);
+    }
+
+    match &log.status {
+        TransactionStatus::Failed(error) => tracing::warn!(
+            compute_used = log.compute_used,
+            fee = log.fee,
+            error = %error,
+            "transaction failed"
+        ),
+        _ => tracing::info!(
+            compute_used = log.compute_used,
+            fee = log.fee,
+            "transaction succeeded"
+        ),
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn trace_instruction(instruction: &EnhancedInstructionLog, position: &str) {
+    tracing::info!(
+        position,
+        depth = instruction.depth,
+        program_id = %instruction.program_id,
+        program = %instruction.program_name,
+        instruction = instruction.instruction_name.as_deref().unwrap_or("unknown"),
+        "instruction"
+    );
+    for (i, inner) in instruction.inner_instructions.iter().enumerate() {
+        trace_instruction(inner, &format!("{}.{}", position, i + 1));
+    }
+}
+
 // ---------------------------------------------------------------------------
 // TransactionLogger
 // ---------------------------------------------------------------------------
@@ -383,6 +1229,23 @@ pub fn create_logging_callback(
 pub struct TransactionLogger {
     config: EnhancedLoggingConfig,
     counter: AtomicUsize,
+    /// Program IDs invoked so far (only tracked in diagnostics mode)
+    invoked_programs: Option<Mutex<HashSet<Pubkey>>>,
+    /// Outcome of every logged transaction, in order
+    records: Mutex<Vec<TransactionRecord>>,
+}
+
+/// Outcome of one transaction logged by a [`TransactionLogger`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionRecord {
+    pub tx_number: usize,
+    /// See [`TransactionLogger::send_transaction_labeled`]
+    pub label: Option<String>,
+    pub status: TransactionStatus,
+    /// Name of the transaction's primary program, see [`EnhancedTransactionLog::primary_program`]
+    pub primary_program: Option<String>,
+    /// Formatted (ANSI-free) transaction block, only kept for failed transactions
+    pub formatted: Option<String>,
 }
 
 impl TransactionLogger {
@@ -391,23 +1254,146 @@ impl TransactionLogger {
         Self {
             config,
             counter: AtomicUsize::new(0),
+            invoked_programs: None,
+            records: Mutex::new(Vec::new()),
         }
     }
 
+    /// Enable decoder diagnostics: track which program IDs are invoked so
+    /// [`unmatched_decoders`](Self::unmatched_decoders) can report custom decoders
+    /// that never matched (usually a wrong hardcoded program ID).
+    ///
+    /// When `log_events` is set, a summary is printed to stderr on drop.
+    pub fn with_diagnostics(mut self) -> Self {
+        self.invoked_programs = Some(Mutex::new(HashSet::new()));
+        self
+    }
+
+    /// Program IDs of custom (non built-in) decoders that never matched an
+    /// instruction during this logger's lifetime.
+    ///
+    /// Always empty unless diagnostics are enabled via [`with_diagnostics`](Self::with_diagnostics).
+    pub fn unmatched_decoders(&self) -> Vec<Pubkey> {
+        let (Some(invoked), Some(registry)) =
+            (&self.invoked_programs, self.config.decoder_registry())
+        else {
+            return Vec::new();
+        };
+        let invoked = invoked.lock().unwrap_or_else(|e| e.into_inner());
+        let mut unmatched: Vec<Pubkey> = registry
+            .program_ids()
+            .filter(|id| !registry.is_builtin(id) && !invoked.contains(id))
+            .copied()
+            .collect();
+        unmatched.sort();
+        unmatched
+    }
+
+    /// Outcomes of all transactions logged so far, in order.
+    pub fn records(&self) -> Vec<TransactionRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    /// Panic if any transaction logged so far failed.
+    ///
+    /// The message lists every failed transaction (number, primary program and
+    /// status) followed by the formatted block of the first failure, so one
+    /// assertion at the end of a multi-transaction test replaces checking each result.
+    #[track_caller]
+    pub fn assert_all_succeeded(&self) {
+        let records = self.records.lock().unwrap();
+        let failed: Vec<&TransactionRecord> = records
+            .iter()
+            .filter(|record| matches!(record.status, TransactionStatus::Failed(_)))
+            .collect();
+        let Some(first) = failed.first() else {
+            return;
+        };
+
+        let mut message = format!(
+            "{} of {} transactions failed:\n",
+            failed.len(),
+            records.len()
+        );
+        for record in &failed {
+            let label = record
+                .label
+                .as_ref()
+                .map(|label| format!(" ({label})"))
+                .unwrap_or_default();
+            message.push_str(&format!(
+                "  #{}{} {}: {}\n",
+                record.tx_number,
+                label,
+                record
+                    .primary_program
+                    .as_deref()
+                    .unwrap_or("<no instructions>"),
+                record.status.text()
+            ));
+        }
+        if let Some(formatted) = &first.formatted {
+            message.push_str(&format!(
+                "\nFirst failed transaction (#{}):\n{}",
+                first.tx_number, formatted
+            ));
+        }
+        panic!("{}", message);
+    }
+
     /// Capture pre-state, send transaction, capture post-state, decode, format, and log.
     ///
+    /// The log's slot and block time are read from the SVM's clock sysvar before
+    /// sending (see [`capture_clock`]).
+    ///
+    /// Accepts both legacy [`Transaction`]s and [`VersionedTransaction`]s.
     /// Returns the raw `TransactionResult` so callers can unwrap/assert as needed.
     pub fn send_transaction(
+        &self,
+        svm: &mut LiteSVM,
+        tx: impl Into<VersionedTransaction>,
+    ) -> TransactionResult {
+        self.send(svm, tx.into(), None)
+    }
+
+    /// Like [`send_transaction`](Self::send_transaction), but annotates the
+    /// transaction with `label` (e.g. "setup", "attack attempt").
+    ///
+    /// The label is shown at the top of the formatted block and included in
+    /// snapshots, which makes long multi-transaction logs easier to navigate.
+    pub fn send_transaction_labeled(
+        &self,
+        svm: &mut LiteSVM,
+        tx: impl Into<VersionedTransaction>,
+        label: impl Into<String>,
+    ) -> TransactionResult {
+        self.send(svm, tx.into(), Some(label.into()))
+    }
+
+    fn send(
         &self,
         svm: &mut LiteSVM,
         tx: VersionedTransaction,
+        label: Option<String>,
     ) -> TransactionResult {
+        let loaded_addresses = load_lookup_table_addresses(svm, &tx.message);
         let pre_states = capture_account_states(svm, &tx);
+        let clock = capture_clock(svm);
         let result = svm.send_transaction(tx.clone());
         let post_states = capture_account_states(svm, &tx);
         let tx_number = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
 
-        self.log_result(&tx, &result, tx_number, &pre_states, &post_states);
+        let mut log = decode_transaction_with_loaded_addresses(
+            &tx,
+            &result,
+            &self.config,
+            Some(&pre_states),
+            Some(&post_states),
+            &loaded_addresses,
+        );
+        log.label = label;
+        apply_clock(&mut log, clock);
+        self.log_decoded(&log, &result, tx_number);
         result
     }
 
@@ -423,16 +1409,77 @@ impl TransactionLogger {
         pre_states: &AccountStates,
         post_states: &AccountStates,
     ) {
-        let log = decode_transaction(tx, result, &self.config, Some(pre_states), Some(post_states));
-        let formatted = format_transaction(&log, &self.config, tx_number);
+        let log = decode_transaction(
+            tx,
+            result,
+            &self.config,
+            Some(pre_states),
+            Some(post_states),
+        );
+        self.log_decoded(&log, result, tx_number);
+    }
+
+    /// Record, format, and log an already decoded transaction.
+    fn log_decoded(
+        &self,
+        log: &EnhancedTransactionLog,
+        result: &TransactionResult,
+        tx_number: usize,
+    ) {
+        let formatter = TransactionFormatter::new(&self.config);
+
+        if let Some(invoked) = &self.invoked_programs {
+            collect_program_ids(&log.instructions, &mut invoked.lock().unwrap());
+        }
 
         // Always write to log file
-        write_to_log_file(&formatted);
+        let plain = formatter.format_plain(log, tx_number);
+        append_to_log_file(
+            &log_file_block(&self.config, &plain, tx_number),
+            self.config.compress_log,
+        );
+
+        let failed = matches!(log.status, TransactionStatus::Failed(_));
+        self.records.lock().unwrap().push(TransactionRecord {
+            tx_number,
+            label: log.label.clone(),
+            status: log.status.clone(),
+            primary_program: log.primary_program().map(|(_, name)| name.to_string()),
+            formatted: failed.then_some(plain),
+        });
 
         // Console output: failed txs always print; all txs print when log_events is set
-        let should_print = self.config.log_events || result.is_err();
+        let should_print =
+            !self.config.suppress_console_output && (self.config.log_events || result.is_err());
         if should_print {
-            eprint!("{}", formatted);
+            eprint!("{}", formatter.format(log, tx_number));
+        }
+    }
+}
+
+impl Drop for TransactionLogger {
+    fn drop(&mut self) {
+        // Same console gating as per-transaction output; stay quiet while unwinding
+        if !self.config.log_events
+            || self.config.suppress_console_output
+            || std::thread::panicking()
+        {
+            return;
+        }
+        let unmatched = self.unmatched_decoders();
+        if unmatched.is_empty() {
+            return;
+        }
+        let registry = self.config.decoder_registry();
+        eprintln!(
+            "instruction-decoder: {} registered decoder(s) never matched any instruction:",
+            unmatched.len()
+        );
+        for program_id in unmatched {
+            let name = registry
+                .and_then(|r| r.program_name(&program_id))
+                .unwrap_or_default();
+            eprintln!("  {} ({})", program_id, name);
         }
     }
 }
@@ -441,17 +1488,103 @@ impl TransactionLogger {
 // Internal helpers
 // ---------------------------------------------------------------------------
 
+/// Collect the program IDs of all instructions, including inner instructions.
+fn collect_program_ids(instructions: &[EnhancedInstructionLog], ids: &mut HashSet<Pubkey>) {
+    for ix in instructions {
+        ids.insert(ix.program_id);
+        collect_program_ids(&ix.inner_instructions, ids);
+    }
+}
+
+/// Replace base58 addresses not in `keep` by numbered `<address N>` placeholders.
+#[cfg(feature = "serde")]
+fn redact_log_addresses(lines: Vec<String>, keep: &HashSet<Pubkey>) -> Vec<String> {
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    let mut placeholders: HashMap<Pubkey, usize> = HashMap::new();
+
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut redacted = String::with_capacity(line.len());
+            let mut rest = line.as_str();
+            while !rest.is_empty() {
+                let token_len = rest
+                    .find(|c: char| !BASE58.contains(c))
+                    .unwrap_or(rest.len());
+                if token_len == 0 {
+                    let c = rest.chars().next().unwrap();
+                    redacted.push(c);
+                    rest = &rest[c.len_utf8()..];
+                    continue;
+                }
+                let (token, tail) = rest.split_at(token_len);
+                let pubkey = if token.len() >= 32 {
+                    token.parse::<Pubkey>().ok()
+                } else {
+                    None
+                };
+                match pubkey {
+                    Some(pubkey) if !keep.contains(&pubkey) => {
+                        let next = placeholders.len() + 1;
+                        let n = *placeholders.entry(pubkey).or_insert(next);
+                        redacted.push_str(&format!("<address {}>", n));
+                    }
+                    _ => redacted.push_str(token),
+                }
+                rest = tail;
+            }
+            redacted
+        })
+        .collect()
+}
+
+/// Resolve a compiled program id index, noting indices outside the account keys.
+fn resolve_program_id(
+    index: u8,
+    account_keys: &[Pubkey],
+    warnings: &mut Vec<(DecodeWarningKind, String)>,
+) -> Pubkey {
+    account_keys
+        .get(index as usize)
+        .copied()
+        .unwrap_or_else(|| {
+            warnings.push((
+                DecodeWarningKind::UnresolvedIndex,
+                format!(
+                    "program id index {} out of range for {} account keys",
+                    index,
+                    account_keys.len()
+                ),
+            ));
+            Pubkey::default()
+        })
+}
+
 /// Resolve compiled instruction account indices to `AccountMeta`.
+///
+/// Indices outside the account keys (e.g. lookup table addresses that were not
+/// loaded) resolve to the default pubkey and are noted in `warnings`.
 fn resolve_accounts(
     account_indices: &[u8],
     account_keys: &[Pubkey],
-    message: &solana_message::VersionedMessage,
+    message: &VersionedMessage,
+    warnings: &mut Vec<(DecodeWarningKind, String)>,
 ) -> Vec<AccountMeta> {
     account_indices
         .iter()
         .map(|&idx| {
             let idx = idx as usize;
-            let pubkey = account_keys.get(idx).copied().unwrap_or_default();
+            let pubkey = account_keys.get(idx).copied().unwrap_or_else(|| {
+                warnings.push((
+                    DecodeWarningKind::UnresolvedIndex,
+                    format!(
+                        "account index {} out of range for {} account keys",
+                        idx,
+                        account_keys.len()
+                    ),
+                ));
+                Pubkey::default()
+            });
             let is_signer = message.is_signer(idx);
             let is_writable = message.is_maybe_writable(idx, None);
             if is_writable {
@@ -463,43 +1596,1068 @@ fn resolve_accounts(
         .collect()
 }
 
+/// Note a top-level instruction whose program logs show CPIs although no inner
+/// instructions were recorded (the SVM was configured not to record them).
+///
+/// `node` is the log invocation at the same position; it is ignored when it belongs
+/// to another program, e.g. because a builtin logged nothing.
+fn unrecorded_cpi_warning<D>(
+    ix_log: &EnhancedInstructionLog<D>,
+    node: Option<&LogNode>,
+) -> Option<String> {
+    let node = node?;
+    if !ix_log.inner_instructions.is_empty()
+        || node.children.is_empty()
+        || node.program_id != ix_log.program_id.to_string()
+    {
+        return None;
+    }
+    Some(
+        "CPI detected in logs but inner instructions not recorded — enable inner instruction recording"
+            .to_string(),
+    )
+}
+
 /// Parse inner (CPI) instructions and attach them to the parent instruction log.
-fn parse_inner_instructions(
-    inner_ixs: &[solana_message::inner_instruction::InnerInstruction],
+fn parse_inner_instructions<'a>(
+    inner_ixs: &'a [solana_message::inner_instruction::InnerInstruction],
     account_keys: &[Pubkey],
-    message: &solana_message::VersionedMessage,
+    message: &VersionedMessage,
     config: &EnhancedLoggingConfig,
-    parent: &mut EnhancedInstructionLog,
+    parent: &mut EnhancedInstructionLog<&'a [u8]>,
+    warnings: &mut Vec<DecodeWarning>,
 ) {
     let registry = config.decoder_registry();
 
     for (inner_idx, inner_ix) in inner_ixs.iter().enumerate() {
-        let program_id = account_keys
-            .get(inner_ix.instruction.program_id_index as usize)
-            .copied()
-            .unwrap_or_default();
+        let mut messages = Vec::new();
+        let program_id = resolve_program_id(
+            inner_ix.instruction.program_id_index,
+            account_keys,
+            &mut messages,
+        );
         let program_name = get_program_name(&program_id, registry);
 
-        let mut ix_log = EnhancedInstructionLog::new(inner_idx, program_id, program_name);
-        ix_log.data = inner_ix.instruction.data.clone();
-        ix_log.accounts = resolve_accounts(&inner_ix.instruction.accounts, account_keys, message);
+        let mut ix_log = EnhancedInstructionLog::with_data(
+            inner_idx,
+            program_id,
+            program_name,
+            inner_ix.instruction.data.as_slice(),
+        );
+        ix_log.accounts = resolve_accounts(
+            &inner_ix.instruction.accounts,
+            account_keys,
+            message,
+            &mut messages,
+        );
 
-        let depth = (inner_ix.stack_height as usize).saturating_sub(1);
+        // Inner instructions run at stack height 2 or more; a lower reported height
+        // would make them look top-level, so treat them as direct children instead
+        if inner_ix.stack_height < 2 {
+            messages.push((
+                DecodeWarningKind::Other,
+                format!(
+                    "unexpected stack height {} for an inner instruction, nested directly under its top-level instruction",
+                    inner_ix.stack_height
+                ),
+            ));
+        }
+        let depth = (inner_ix.stack_height as usize).max(2) - 1;
         ix_log.depth = depth;
         ix_log.decode(config);
+        messages.extend(ix_log.decode_warnings(config));
 
-        if depth <= 1 {
-            parent.inner_instructions.push(ix_log);
-        } else {
-            let target_depth = depth - 1;
-            if let Some(nested_parent) = EnhancedInstructionLog::find_parent_for_instruction(
-                &mut parent.inner_instructions,
-                target_depth,
-            ) {
-                nested_parent.inner_instructions.push(ix_log);
-            } else {
-                parent.inner_instructions.push(ix_log);
+        // Nest under the most recent instruction one level up, else under the parent
+        let mut path = vec![parent.index];
+        let mut siblings = &mut parent.inner_instructions;
+        if depth > 1 {
+            if let Some(nested_path) = parent_path(siblings, depth - 1) {
+                for i in nested_path {
+                    path.push(i);
+                    siblings = &mut siblings[i].inner_instructions;
+                }
             }
         }
+        path.push(siblings.len());
+        ix_log.path = path.clone();
+        warnings.extend(
+            messages
+                .into_iter()
+                .map(|(kind, message)| DecodeWarning::new(path.clone(), message).with_kind(kind)),
+        );
+        siblings.push(ix_log);
+    }
+}
+
+/// Index path of the last instruction at `target_depth`, searched like
+/// [`EnhancedInstructionLog::find_parent_for_instruction`].
+fn parent_path<D>(
+    instructions: &[EnhancedInstructionLog<D>],
+    target_depth: usize,
+) -> Option<Vec<usize>> {
+    for (i, instruction) in instructions.iter().enumerate().rev() {
+        if instruction.depth == target_depth {
+            return Some(vec![i]);
+        }
+        if let Some(mut path) = parent_path(&instruction.inner_instructions, target_depth) {
+            path.insert(0, i);
+            return Some(path);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_snapshot_log_tree_requires_flag() {
+        let program = Pubkey::new_from_array([1; 32]);
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(solana_message::Message::default()),
+        };
+        let result: TransactionResult = Ok(litesvm::types::TransactionMetadata {
+            logs: vec![
+                format!("Program {program} invoke [1]"),
+                format!("Program {program} success"),
+            ],
+            ..Default::default()
+        });
+
+        let config = EnhancedLoggingConfig::debug();
+        let snapshot = decode_transaction_snapshot(&tx, &result, &config, None, None);
+        assert!(snapshot.program_log_tree.is_none());
+
+        let config = config.with_log_tree();
+        let snapshot = decode_transaction_snapshot(&tx, &result, &config, None, None);
+        let tree = snapshot.program_log_tree.unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].program_id, program.to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_snapshot_slot_and_block_time() {
+        let log = crate::TransactionLogBuilder::new()
+            .slot(100)
+            .block_time(1_700_000_000)
+            .build();
+        let snapshot = transaction_log_to_snapshot(&log);
+        assert_eq!(snapshot.slot, 100);
+        assert_eq!(snapshot.block_time, Some(1_700_000_000));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_snapshot_rent_status() {
+        let funded = Pubkey::new_from_array([1; 32]);
+        let underfunded = Pubkey::new_from_array([2; 32]);
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(solana_message::Message::default()),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let pre: AccountStates = [
+            (funded, (5_000_000, 0, Pubkey::default())),
+            (underfunded, (0, 0, Pubkey::default())),
+        ]
+        .into();
+        let post: AccountStates = [
+            (funded, (5_000_000, 0, Pubkey::default())),
+            (underfunded, (1_000_000, 165, Pubkey::default())),
+        ]
+        .into();
+
+        let config = EnhancedLoggingConfig::debug();
+        let snapshot = decode_transaction_snapshot(&tx, &result, &config, Some(&pre), Some(&post));
+        assert!(snapshot.account_states.is_none());
+
+        let config = config.with_rent_status();
+        let snapshot = decode_transaction_snapshot(&tx, &result, &config, Some(&pre), Some(&post));
+        let states = snapshot.account_states.unwrap();
+        assert_eq!(
+            states
+                .iter()
+                .map(|s| (s.pubkey.clone(), s.rent_exempt, s.rent_exempt_minimum))
+                .collect::<Vec<_>>(),
+            vec![
+                (funded.to_string(), true, 890_880),
+                (underfunded.to_string(), false, 2_039_280),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_owner_filter_keeps_created_accounts() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let created = Pubkey::new_from_array([2; 32]);
+        let program = Pubkey::new_from_array([3; 32]);
+        // System Program CreateAccount { lamports: 100, space: 8, owner: program }
+        let data = [
+            [0u8, 0, 0, 0].as_slice(),
+            &100u64.to_le_bytes(),
+            &8u64.to_le_bytes(),
+            program.as_ref(),
+        ]
+        .concat();
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &data,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(created, true),
+            ],
+        );
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default(), Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        // Missing accounts are captured with the default owner before the transaction
+        let pre: AccountStates = [
+            (payer, (1_000, 0, Pubkey::default())),
+            (created, (0, 0, Pubkey::default())),
+        ]
+        .into();
+        let post: AccountStates = [
+            (payer, (900, 0, Pubkey::default())),
+            (created, (100, 8, program)),
+        ]
+        .into();
+
+        let mut config = EnhancedLoggingConfig::debug().with_account_owner_filter([program]);
+        config.use_colors = false;
+        let log = decode_transaction(&tx, &result, &config, Some(&pre), Some(&post));
+        let state = &log.account_states.as_ref().unwrap()[&created];
+        assert_eq!(
+            (state.owner, state.owner_after),
+            (Pubkey::default(), program)
+        );
+
+        let output = format_transaction(&log, &config, 1);
+        assert!(output.contains(&created.to_string()));
+        assert!(output.contains("1 account hidden by owner filter"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_snapshot_hides_proof_field() {
+        let log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::default())
+                    .field("has_proof", "true")
+                    .field("proof", "0x01"),
+            )
+            .build();
+        let names = |config: &EnhancedLoggingConfig| -> Vec<String> {
+            let mut snapshot = transaction_log_to_snapshot(&log);
+            filter_snapshot_fields(&mut snapshot.instructions, config);
+            snapshot.instructions[0]
+                .decoded_fields
+                .iter()
+                .flatten()
+                .map(|field| field.name.clone())
+                .collect()
+        };
+
+        let config = EnhancedLoggingConfig::debug();
+        assert_eq!(names(&config), ["has_proof"]);
+        assert_eq!(names(&config.with_proof_bytes()), ["has_proof", "proof"]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_snapshot_field_filter() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        // System Program CreateAccount { lamports: 100, space: 8, owner: [3; 32] }
+        let data = [
+            [0u8, 0, 0, 0].as_slice(),
+            &100u64.to_le_bytes(),
+            &8u64.to_le_bytes(),
+            &[3; 32],
+        ]
+        .concat();
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &data,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(Pubkey::new_from_array([2; 32]), true),
+            ],
+        );
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default(), Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let names = |snapshot: &TransactionSnapshot| -> Vec<String> {
+            snapshot.instructions[0]
+                .decoded_fields
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|field| field.name.clone())
+                .collect()
+        };
+
+        let config = EnhancedLoggingConfig::debug();
+        let snapshot = decode_transaction_snapshot(&tx, &result, &config, None, None);
+        assert_eq!(names(&snapshot), ["lamports", "space"]);
+
+        let config = config.with_field_filter(["space", "missing", "lamports"]);
+        let snapshot = decode_transaction_snapshot(&tx, &result, &config, None, None);
+        assert_eq!(names(&snapshot), ["space", "lamports"]);
+        assert_eq!(
+            snapshot.instructions[0].decoded_fields.as_ref().unwrap()[0].value,
+            "8"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn test_write_to_compressed_log_file() {
+        use std::io::Read;
+
+        write_to_compressed_log_file("\x1b[32mfirst\x1b[0m transaction\n");
+        write_to_compressed_log_file("second transaction\n");
+
+        let compressed = fs::read(COMPRESSED_LOG_PATH).unwrap();
+        let mut text = String::new();
+        flate2::read::MultiGzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "first transaction\nsecond transaction\n");
+    }
+
+    #[test]
+    fn test_transaction_separator_in_log_file() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &[2, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(Pubkey::new_from_array([2; 32]), false),
+            ],
+        );
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let logger = TransactionLogger::new(
+            EnhancedLoggingConfig::default()
+                .with_transaction_separator("\x1b[1m---- separator test #{tx_number} ----\x1b[0m"),
+        );
+        let states = AccountStates::new();
+        logger.log_result(&tx, &result, 1, &states, &states);
+        logger.log_result(&tx, &result, 2, &states, &states);
+
+        // Other tests append to the same file concurrently, but each block is one write
+        let content = fs::read_to_string(LOG_PATH).unwrap();
+        let first = content.find("---- separator test #1 ----\n").unwrap();
+        let second = content.find("---- separator test #2 ----\n").unwrap();
+        assert!(first < second);
+        let block = &content[first..second];
+        assert!(block.contains("Transfer"));
+        assert!(!block.contains('\x1b'));
+
+        let mut config = EnhancedLoggingConfig::default();
+        config.transaction_separator = None;
+        assert_eq!(log_file_block(&config, "tx\n", 3), "tx\n");
+        assert_eq!(
+            log_file_block(&EnhancedLoggingConfig::default(), "tx\n", 3),
+            "==================== Transaction #3 ====================\ntx\n"
+        );
+    }
+
+    #[test]
+    fn test_decode_base64_transaction() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let transfer = [2, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0];
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &transfer,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(Pubkey::new_from_array([2; 32]), false),
+            ],
+        );
+        let signature = Signature::from([7; 64]);
+        let tx = VersionedTransaction {
+            signatures: vec![signature],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let b64 =
+            base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&tx).unwrap());
+        let config = EnhancedLoggingConfig::debug();
+
+        let log = decode_base64_transaction(&b64, None, &config).unwrap();
+        assert_eq!(log.status, TransactionStatus::NotExecuted);
+        assert_eq!(log.signature, signature);
+        assert_eq!(
+            log.instructions[0].instruction_name.as_deref(),
+            Some("Transfer")
+        );
+
+        let result: TransactionResult = Ok(Default::default());
+        let log = decode_base64_transaction(&b64, Some(&result), &config).unwrap();
+        assert_eq!(log.status, TransactionStatus::Success);
+        assert_eq!(
+            log.instructions[0].instruction_name.as_deref(),
+            Some("Transfer")
+        );
+
+        assert!(matches!(
+            decode_base64_transaction("not base64!", None, &config),
+            Err(DecodeError::Base64(_))
+        ));
+        assert!(matches!(
+            decode_base64_transaction("AAAA", None, &config),
+            Err(DecodeError::Bincode(_))
+        ));
+    }
+
+    #[test]
+    fn test_instruction_callback_visits_inner_instructions() {
+        use std::sync::Arc;
+
+        use solana_message::{inner_instruction::InnerInstruction, Message};
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let recipient = Pubkey::new_from_array([2; 32]);
+        let transfer = [2, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0];
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &transfer,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(recipient, false),
+            ],
+        );
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let result: TransactionResult = Ok(litesvm::types::TransactionMetadata {
+            inner_instructions: vec![vec![InnerInstruction {
+                instruction: CompiledInstruction::new_from_raw_parts(
+                    2,
+                    transfer.to_vec(),
+                    vec![0, 1],
+                ),
+                stack_height: 2,
+            }]],
+            ..Default::default()
+        });
+
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let config = EnhancedLoggingConfig::debug().with_instruction_callback({
+            let visited = visited.clone();
+            move |ix| {
+                visited
+                    .lock()
+                    .unwrap()
+                    .push((ix.depth, ix.instruction_name.clone()))
+            }
+        });
+        let log = decode_transaction(&tx, &result, &config, None, None);
+
+        let transfer = Some("Transfer".to_string());
+        let expected = vec![(0, transfer.clone()), (1, transfer)];
+        assert_eq!(*visited.lock().unwrap(), expected);
+        visited.lock().unwrap().clear();
+        decode_transaction_borrowed(&tx, &result, &config);
+        assert_eq!(*visited.lock().unwrap(), expected);
+        // Output is unaffected
+        let plain = EnhancedLoggingConfig::debug();
+        assert_eq!(
+            format_transaction_plain(&log, &plain, 1),
+            format_transaction_plain(
+                &decode_transaction(&tx, &result, &plain, None, None),
+                &plain,
+                1
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "1 instruction(s) could not be decoded")]
+    fn test_strict_unknown_panics_on_undecoded_instruction() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        // System Program transfer of 1 lamport
+        let transfer = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &[2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(payer, false),
+            ],
+        );
+        let unknown = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::new_from_array([7; 32]),
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9],
+            vec![],
+        );
+        let tx = |instructions: &[solana_instruction::Instruction]| VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(instructions, Some(&payer))),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let config = EnhancedLoggingConfig::debug().with_strict_unknown();
+
+        let log = decode_transaction(
+            &tx(std::slice::from_ref(&transfer)),
+            &result,
+            &config,
+            None,
+            None,
+        );
+        assert_eq!(
+            log.instructions[0].instruction_name.as_deref(),
+            Some("Transfer")
+        );
+
+        decode_transaction(&tx(&[transfer, unknown]), &result, &config, None, None);
+    }
+
+    #[test]
+    fn test_strict_unknown_applies_to_every_decode_path() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let unknown = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::new_from_array([7; 32]),
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9],
+            vec![],
+        );
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[unknown], Some(&payer))),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let config = EnhancedLoggingConfig::debug().with_strict_unknown();
+        let b64 =
+            base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&tx).unwrap());
+
+        let panics = |decode: &dyn Fn()| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(decode)).is_err()
+        };
+        assert!(panics(&|| {
+            decode_message(&tx.message, &config);
+        }));
+        assert!(panics(&|| {
+            decode_transaction_borrowed(&tx, &result, &config);
+        }));
+        assert!(panics(&|| {
+            let _ = decode_base64_transaction(&b64, None, &config);
+        }));
+        assert!(panics(&|| {
+            let _ = decode_base64_transaction(&b64, Some(&result), &config);
+        }));
+        assert!(!panics(&|| {
+            decode_message(&tx.message, &EnhancedLoggingConfig::debug());
+        }));
+    }
+
+    #[test]
+    fn test_unrecorded_inner_instructions_warning() {
+        use solana_message::{inner_instruction::InnerInstruction, Message};
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            program_id,
+            &[0; 8],
+            vec![AccountMeta::new(payer, true)],
+        );
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let logs = vec![
+            format!("Program {program_id} invoke [1]"),
+            "Program 11111111111111111111111111111111 invoke [2]".to_string(),
+            "Program 11111111111111111111111111111111 success".to_string(),
+            format!("Program {program_id} success"),
+        ];
+        let unrecorded: TransactionResult = Ok(litesvm::types::TransactionMetadata {
+            logs: logs.clone(),
+            ..Default::default()
+        });
+
+        let config = EnhancedLoggingConfig::debug();
+        let log = decode_transaction(&tx, &unrecorded, &config, None, None);
+        let warning = log
+            .warnings
+            .iter()
+            .find(|w| w.message.starts_with("CPI detected"))
+            .unwrap();
+        assert_eq!(warning.path, vec![0]);
+        assert_eq!(
+            warning.message,
+            "CPI detected in logs but inner instructions not recorded — enable inner instruction recording"
+        );
+
+        let recorded: TransactionResult = Ok(litesvm::types::TransactionMetadata {
+            logs,
+            inner_instructions: vec![vec![InnerInstruction {
+                instruction: CompiledInstruction::new_from_raw_parts(1, vec![], vec![0]),
+                stack_height: 2,
+            }]],
+            ..Default::default()
+        });
+        let log = decode_transaction(&tx, &recorded, &config, None, None);
+        assert!(!log
+            .warnings
+            .iter()
+            .any(|w| w.message.starts_with("CPI detected")));
+    }
+
+    #[test]
+    fn test_inner_instructions_resolve_loaded_addresses() {
+        use solana_message::{
+            inner_instruction::InnerInstruction,
+            v0::{self, MessageAddressTableLookup},
+            MessageHeader,
+        };
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let program = Pubkey::new_from_array([2; 32]);
+        let recipient = Pubkey::new_from_array([3; 32]);
+        let system_program = Pubkey::default();
+        let transfer = [2, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0];
+
+        // Keys: payer, program (static), recipient (loaded writable), system (loaded readonly)
+        let message = VersionedMessage::V0(v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![payer, program],
+            instructions: vec![CompiledInstruction::new_from_raw_parts(
+                1,
+                vec![],
+                vec![0, 2, 3],
+            )],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: Pubkey::new_from_array([4; 32]),
+                writable_indexes: vec![0],
+                readonly_indexes: vec![1],
+            }],
+            ..Default::default()
+        });
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message,
+        };
+        let result: TransactionResult = Ok(litesvm::types::TransactionMetadata {
+            inner_instructions: vec![vec![InnerInstruction {
+                instruction: CompiledInstruction::new_from_raw_parts(
+                    3,
+                    transfer.to_vec(),
+                    vec![0, 2],
+                ),
+                stack_height: 2,
+            }]],
+            ..Default::default()
+        });
+        let loaded_addresses = LoadedAddresses {
+            writable: vec![recipient],
+            readonly: vec![system_program],
+        };
+        let config = EnhancedLoggingConfig::debug();
+
+        let log = decode_transaction_with_loaded_addresses(
+            &tx,
+            &result,
+            &config,
+            None,
+            None,
+            &loaded_addresses,
+        );
+        let top_level = &log.instructions[0];
+        assert_eq!(top_level.accounts[1], AccountMeta::new(recipient, false));
+        assert_eq!(
+            top_level.accounts[2],
+            AccountMeta::new_readonly(system_program, false)
+        );
+        let inner = &top_level.inner_instructions[0];
+        assert_eq!(inner.program_name, "System Program");
+        assert_eq!(inner.instruction_name.as_deref(), Some("Transfer"));
+        assert_eq!(inner.accounts[1], AccountMeta::new(recipient, false));
+        assert!(
+            !log.warnings
+                .iter()
+                .any(|w| w.message.contains("out of range")),
+            "{:?}",
+            log.warnings
+        );
+
+        // Static keys only: loaded indices fall back to the default pubkey with a warning
+        let log = decode_transaction(&tx, &result, &config, None, None);
+        let inner = &log.instructions[0].inner_instructions[0];
+        assert_eq!(inner.accounts[1].pubkey, Pubkey::default());
+        assert!(log
+            .warnings
+            .iter()
+            .any(|w| w.message == "account index 2 out of range for 2 account keys"));
+    }
+
+    #[test]
+    fn test_inner_instruction_with_low_stack_height() {
+        use solana_message::{inner_instruction::InnerInstruction, Message};
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let program = Pubkey::new_from_array([2; 32]);
+        let recipient = Pubkey::new_from_array([3; 32]);
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            program,
+            &[],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(recipient, false),
+                AccountMeta::new_readonly(Pubkey::default(), false),
+            ],
+        );
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let keys = tx.message.static_account_keys();
+        let index = |pubkey: &Pubkey| keys.iter().position(|key| key == pubkey).unwrap() as u8;
+        let transfer = |stack_height: u8| InnerInstruction {
+            instruction: CompiledInstruction::new_from_raw_parts(
+                index(&Pubkey::default()),
+                vec![2, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0],
+                vec![index(&payer), index(&recipient)],
+            ),
+            stack_height,
+        };
+        // A transfer reported at stack height 0, then one it invokes at height 3
+        let result: TransactionResult = Ok(litesvm::types::TransactionMetadata {
+            inner_instructions: vec![vec![transfer(0), transfer(3)]],
+            ..Default::default()
+        });
+
+        let log = decode_transaction(&tx, &result, &EnhancedLoggingConfig::debug(), None, None);
+        let inner = &log.instructions[0].inner_instructions;
+        assert_eq!(inner.len(), 1);
+        assert_eq!((inner[0].depth, inner[0].location()), (1, "#1.1".into()));
+        assert_eq!(inner[0].instruction_name.as_deref(), Some("Transfer"));
+        let nested = &inner[0].inner_instructions[0];
+        assert_eq!((nested.depth, nested.location()), (2, "#1.1.1".into()));
+        let warnings: Vec<&str> = log
+            .warnings
+            .iter()
+            .filter(|w| w.message.contains("stack height"))
+            .map(|w| w.message.as_str())
+            .collect();
+        assert_eq!(
+            warnings,
+            ["unexpected stack height 0 for an inner instruction, nested directly under its top-level instruction"]
+        );
+    }
+
+    #[test]
+    fn test_under_signed_transaction() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let authority = Pubkey::new_from_array([2; 32]);
+        let ix = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::new_from_array([3; 32]),
+            &[],
+            vec![AccountMeta::new_readonly(authority, true)],
+        );
+        // Payer and authority must sign, but only the payer did
+        let mut tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let config = EnhancedLoggingConfig::debug();
+
+        let log = decode_transaction(&tx, &result, &config, None, None);
+        assert_eq!((log.signatures_required, log.signatures_provided), (2, 1));
+        assert!(!log.signature_count_ok);
+        let warning = log.warnings.iter().find(|w| w.path.is_empty()).unwrap();
+        assert_eq!(
+            warning.message,
+            "message requires 2 signature(s), transaction has 1"
+        );
+
+        tx.signatures.push(Signature::default());
+        let log = decode_transaction(&tx, &result, &config, None, None);
+        assert!(log.signature_count_ok);
+        assert!(log.warnings.iter().all(|w| !w.path.is_empty()));
+    }
+
+    #[test]
+    fn test_transaction_size() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let tx = |data_len: usize| {
+            let ix = solana_instruction::Instruction::new_with_bytes(
+                Pubkey::new_from_array([3; 32]),
+                &vec![0; data_len],
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(Pubkey::new_from_array([2; 32]), false),
+                ],
+            );
+            VersionedTransaction {
+                signatures: vec![Signature::default()],
+                message: VersionedMessage::Legacy(Message::new(&[ix], Some(&payer))),
+            }
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let mut config = EnhancedLoggingConfig::debug().with_transaction_size();
+        config.use_colors = false;
+
+        let small = tx(12);
+        let log = decode_transaction(&small, &result, &config, None, None);
+        // program id index, 2 account indices and 12 data bytes, plus both length prefixes
+        assert_eq!(log.instructions[0].instruction_size_bytes, Some(17));
+        let wire_size = bincode::serialized_size(&small).unwrap() as usize;
+        assert_eq!(log.transaction_size(), Some(wire_size));
+        assert_eq!(log.total_message_size, Some(wire_size - 65));
+        assert!(!log.near_packet_limit());
+        assert!(log.warnings.iter().all(|w| !w.path.is_empty()));
+
+        let formatted = format_transaction_plain(&log, &config, 1);
+        assert!(formatted.contains(&format!(
+            "│ Size: {}/1232 bytes (message: {} bytes)\n",
+            wire_size,
+            wire_size - 65
+        )));
+        assert!(formatted.contains("(17 bytes)"));
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            decode_transaction_snapshot(&small, &result, &config, None, None).total_message_size,
+            log.total_message_size
+        );
+
+        // 1000 data bytes (two-byte length prefix) push the transaction close to the limit
+        let large = tx(1000);
+        let log = decode_transaction(&large, &result, &config, None, None);
+        assert_eq!(log.instructions[0].instruction_size_bytes, Some(1006));
+        let wire_size = bincode::serialized_size(&large).unwrap() as usize;
+        assert!(log.near_packet_limit());
+        let warning = log.warnings.iter().find(|w| w.path.is_empty()).unwrap();
+        assert_eq!(
+            warning.message,
+            format!(
+                "transaction is {wire_size} bytes, which is close to the 1232-byte packet limit"
+            )
+        );
+
+        // Previewing the unsigned message counts the signature it will need
+        let preview = decode_message(&large.message, &config);
+        assert_eq!(preview.transaction_size(), Some(wire_size));
+    }
+
+    #[test]
+    fn test_fee_includes_priority_fee() {
+        use solana_message::Message;
+
+        use crate::{ComputeBudgetInstructionDecoder, InstructionDecoder};
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let compute_budget = ComputeBudgetInstructionDecoder.program_id();
+        let budget_ix = |data: Vec<u8>| {
+            solana_instruction::Instruction::new_with_bytes(compute_budget, &data, Vec::new())
+        };
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new(
+                &[
+                    budget_ix([[2u8].as_slice(), &300_000u32.to_le_bytes()].concat()),
+                    budget_ix([[3u8].as_slice(), &50_000u64.to_le_bytes()].concat()),
+                    solana_instruction::Instruction::new_with_bytes(
+                        Pubkey::new_from_array([3; 32]),
+                        &[0],
+                        vec![AccountMeta::new(payer, true)],
+                    ),
+                ],
+                Some(&payer),
+            )),
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+
+        let log = decode_transaction(&tx, &result, &config, None, None);
+        // 50,000 µlam/CU * 300,000 CU / 1,000,000
+        assert_eq!(log.base_fee, 5_000);
+        assert_eq!(log.prioritization_fee, 15_000);
+        assert_eq!(log.fee, 20_000);
+
+        let formatted = format_transaction_plain(&log, &config, 1);
+        assert!(formatted.contains(
+            "Fee: 0.000020 SOL (base 0.000005 SOL + priority 0.000015 SOL) | Compute Used"
+        ));
+    }
+
+    #[test]
+    fn test_durable_nonce_transaction() {
+        use solana_message::Message;
+
+        let payer = Pubkey::new_from_array([1; 32]);
+        let nonce_account = Pubkey::new_from_array([2; 32]);
+        let recent_blockhashes: Pubkey = "SysvarRecentB1ockHashes11111111111111111111"
+            .parse()
+            .unwrap();
+        let advance_nonce = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &[4, 0, 0, 0],
+            vec![
+                AccountMeta::new(nonce_account, false),
+                AccountMeta::new_readonly(recent_blockhashes, false),
+                AccountMeta::new_readonly(payer, true),
+            ],
+        );
+        let transfer = solana_instruction::Instruction::new_with_bytes(
+            Pubkey::default(),
+            &[2, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(Pubkey::new_from_array([3; 32]), false),
+            ],
+        );
+        let tx = |instructions: &[solana_instruction::Instruction]| {
+            let mut message = Message::new(instructions, Some(&payer));
+            message.recent_blockhash = [7; 32].into();
+            VersionedTransaction {
+                signatures: vec![Signature::default()],
+                message: VersionedMessage::Legacy(message),
+            }
+        };
+        let result: TransactionResult = Ok(Default::default());
+        let mut config = EnhancedLoggingConfig::debug();
+        config.use_colors = false;
+
+        let log = decode_transaction(
+            &tx(&[advance_nonce, transfer.clone()]),
+            &result,
+            &config,
+            None,
+            None,
+        );
+        let nonce = Pubkey::new_from_array([7; 32]).to_string();
+        assert_eq!(
+            log.durable_nonce,
+            Some(crate::DurableNonce {
+                nonce: nonce.clone(),
+                nonce_account,
+                nonce_authority: payer,
+            })
+        );
+        let formatted = format_transaction_plain(&log, &config, 1);
+        assert!(formatted.contains(&format!(
+            "│ Durable Nonce: {} (nonce account: {}, authority: {})\n",
+            nonce, nonce_account, payer
+        )));
+
+        // A regular blockhash is not shown
+        let log = decode_transaction(&tx(&[transfer]), &result, &config, None, None);
+        assert_eq!(log.durable_nonce, None);
+        assert!(!format_transaction_plain(&log, &config, 1).contains("Durable Nonce"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_redact_log_addresses() {
+        let program = Pubkey::new_from_array([1; 32]);
+        let first = Pubkey::new_from_array([2; 32]);
+        let second = Pubkey::new_from_array([3; 32]);
+        let lines = vec![
+            format!("Program {} invoke [1]", program),
+            format!("Program log: from {} to {}", first, second),
+            format!("Program log: refund {}, fee 5000", first),
+            format!("Program {} success", program),
+        ];
+
+        let keep = HashSet::from([program]);
+        assert_eq!(
+            redact_log_addresses(lines, &keep),
+            [
+                format!("Program {} invoke [1]", program),
+                "Program log: from <address 1> to <address 2>".to_string(),
+                "Program log: refund <address 1>, fee 5000".to_string(),
+                format!("Program {} success", program),
+            ]
+        );
+    }
+
+    #[cfg(any(feature = "yaml", feature = "ron"))]
+    fn sample_snapshot() -> TransactionSnapshot {
+        TransactionSnapshot {
+            label: None,
+            signature: "sig".to_string(),
+            slot: 0,
+            block_time: None,
+            status: "Success".to_string(),
+            failed_instruction: None,
+            fee: 5000,
+            compute_used: 150,
+            compute_unit_price: None,
+            compute_unit_limit: None,
+            durable_nonce: None,
+            total_message_size: None,
+            instructions: vec![InstructionSnapshot {
+                path: "#1".to_string(),
+                program_id: Pubkey::default().to_string(),
+                program_name: "System Program".to_string(),
+                instruction_name: Some("Transfer".to_string()),
+                accounts: Vec::new(),
+                decoded_fields: Some(vec![FieldSnapshot {
+                    name: "lamports".to_string(),
+                    value: "1000".to_string(),
+                }]),
+                account_count_mismatch: None,
+                discriminator: None,
+                inner_instructions: Vec::new(),
+            }],
+            stats: TransactionStats::default(),
+            net_data_len_change: 0,
+            warnings: Vec::new(),
+            program_logs: Vec::new(),
+            program_log_tree: None,
+            account_states: None,
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_to_yaml_string() {
+        let yaml = to_yaml_string(&sample_snapshot()).unwrap();
+        assert!(yaml.starts_with("signature: sig\n"));
+        assert!(yaml.contains("instruction_name: Transfer"));
+        assert!(yaml.contains("- name: lamports\n    value: '1000'"));
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn test_to_ron_string() {
+        let ron = to_ron_string(&sample_snapshot()).unwrap();
+        assert!(ron.contains("signature: \"sig\""));
+        assert!(ron.contains("instruction_name: Some(\"Transfer\")"));
+        assert!(ron.contains("fee: 5000"));
     }
 }
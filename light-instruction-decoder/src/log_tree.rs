@@ -0,0 +1,159 @@
+//! Program log lines parsed into the tree of invocations they describe
+//!
+//! Used for compute unit attribution
+//! ([`crate::EnhancedTransactionLog::attribute_compute_units`]), to notice CPIs
+//! that weren't recorded as inner instructions, and for the `program_log_tree`
+//! of transaction snapshots.
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use solana_pubkey::Pubkey;
+
+/// One program invocation in the program logs, with the invocations it made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct LogNode {
+    pub program_id: String,
+    /// 0 for top-level invocations, like [`EnhancedInstructionLog::depth`]
+    pub depth: usize,
+    /// None when the logs end before the invocation finished (e.g. truncated logs)
+    pub success: Option<bool>,
+    /// Error text of a `Program <id> failed: <error>` line
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub error: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub consumed: Option<u64>,
+    /// Other lines emitted during this invocation (`Program log: ...`, `Program data: ...`)
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub logs: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub children: Vec<LogNode>,
+}
+
+/// Parse program log lines into a tree of invocations.
+///
+/// Every `Program <id> invoke [n]` line opens an invocation, closed by the next
+/// `Program <id> success` or `Program <id> failed: <error>` line. A `Program <id>
+/// consumed N of M compute units` line sets the consumed units of the open
+/// invocation of that program. Lines outside any invocation are dropped.
+pub fn parse_program_log_tree(logs: &[String]) -> Vec<LogNode> {
+    let mut roots: Vec<LogNode> = Vec::new();
+    // Open invocations, innermost last
+    let mut open: Vec<LogNode> = Vec::new();
+
+    for line in logs {
+        let event = line
+            .strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(' '))
+            .and_then(|(id, event)| Some((id.parse::<Pubkey>().ok()?, event)));
+        match event {
+            Some((program_id, event)) if event.starts_with("invoke [") => {
+                open.push(LogNode {
+                    program_id: program_id.to_string(),
+                    depth: open.len(),
+                    success: None,
+                    error: None,
+                    consumed: None,
+                    logs: Vec::new(),
+                    children: Vec::new(),
+                });
+            }
+            Some((program_id, event)) if event.starts_with("consumed ") => {
+                if let Some(node) = open
+                    .last_mut()
+                    .filter(|node| node.program_id == program_id.to_string())
+                {
+                    node.consumed = event
+                        .strip_prefix("consumed ")
+                        .and_then(|rest| rest.split(' ').next())
+                        .and_then(|units| units.parse().ok());
+                }
+            }
+            Some((_, event)) if event == "success" || event.starts_with("failed") => {
+                let Some(mut node) = open.pop() else {
+                    continue;
+                };
+                node.success = Some(event == "success");
+                node.error = event.strip_prefix("failed: ").map(str::to_string);
+                match open.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => roots.push(node),
+                }
+            }
+            _ => {
+                if let Some(node) = open.last_mut() {
+                    node.logs.push(line.clone());
+                }
+            }
+        }
+    }
+
+    // Unfinished invocations: attach innermost first
+    while let Some(node) = open.pop() {
+        match open.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+    roots
+}
+
+/// Invocations of `tree` in invoke order, parents before their children
+pub(crate) fn flatten_log_tree(tree: &[LogNode]) -> Vec<&LogNode> {
+    fn visit<'a>(nodes: &'a [LogNode], flat: &mut Vec<&'a LogNode>) {
+        for node in nodes {
+            flat.push(node);
+            visit(&node.children, flat);
+        }
+    }
+    let mut flat = Vec::new();
+    visit(tree, &mut flat);
+    flat
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_program_log_tree() {
+        let program = Pubkey::new_from_array([1; 32]);
+        let system = Pubkey::default();
+        let logs: Vec<String> = [
+            format!("Program {program} invoke [1]"),
+            "Program log: Instruction: Initialize".to_string(),
+            format!("Program {system} invoke [2]"),
+            format!("Program {system} success"),
+            format!("Program {program} consumed 4413 of 200000 compute units"),
+            format!("Program {program} success"),
+            format!("Program {program} invoke [1]"),
+            "Program log: boom".to_string(),
+            format!("Program {program} consumed 100 of 195587 compute units"),
+            format!("Program {program} failed: custom program error: 0x1"),
+        ]
+        .into();
+
+        let tree = parse_program_log_tree(&logs);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].program_id, program.to_string());
+        assert_eq!(
+            (tree[0].depth, tree[0].success, tree[0].consumed),
+            (0, Some(true), Some(4413))
+        );
+        assert_eq!(tree[0].logs, vec!["Program log: Instruction: Initialize"]);
+        let cpi = &tree[0].children[0];
+        assert_eq!(cpi.program_id, system.to_string());
+        assert_eq!(
+            (cpi.depth, cpi.success, cpi.consumed),
+            (1, Some(true), None)
+        );
+        assert_eq!(tree[1].success, Some(false));
+        assert_eq!(tree[1].error.as_deref(), Some("custom program error: 0x1"));
+        assert_eq!(tree[1].logs, vec!["Program log: boom"]);
+
+        // Truncated logs leave the invocation unfinished
+        let tree = parse_program_log_tree(&logs[..2]);
+        assert_eq!(tree[0].success, None);
+        assert_eq!(tree[0].logs, vec!["Program log: Instruction: Initialize"]);
+    }
+}
@@ -14,7 +14,150 @@
 // Allow the macro-generated code to reference types from this crate
 extern crate self as light_instruction_decoder;
 
+use borsh::{io::Read, BorshDeserialize};
 use light_instruction_decoder_derive::InstructionDecoder;
+use solana_instruction::AccountMeta;
+
+use crate::programs::light_types::{
+    InstructionDataBatchProofInputs, MigrateLeafParams, NullifyLeavesInstructionData,
+    RolloverBatchedAddressMerkleTreeInstructionData, RolloverBatchedStateMerkleTreeInstructionData,
+    UpdateAddressMerkleTreeInstructionData,
+};
+use crate::DecodedField;
+
+// ============================================================================
+// Params and Fields Formatters
+// ============================================================================
+
+/// Proof inputs of the batched tree instructions, which take them as a borsh `Vec<u8>`.
+#[derive(Debug)]
+pub struct BatchProofInputsWrapper(pub InstructionDataBatchProofInputs);
+
+impl BorshDeserialize for BatchProofInputsWrapper {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        // Skip 4-byte Vec<u8> length prefix
+        let mut prefix = [0u8; 4];
+        reader.read_exact(&mut prefix)?;
+        let inner = InstructionDataBatchProofInputs::deserialize_reader(reader)?;
+        Ok(BatchProofInputsWrapper(inner))
+    }
+}
+
+/// Base58 rendering of a root, leaf or other 32-byte hash
+#[cfg(not(target_os = "solana"))]
+fn base58(bytes: &[u8; 32]) -> String {
+    bs58::encode(bytes).into_string()
+}
+
+/// `network_fee` field of the rollover instructions
+#[cfg(not(target_os = "solana"))]
+fn network_fee_field(network_fee: Option<u64>) -> DecodedField {
+    DecodedField::new(
+        "network_fee",
+        network_fee.map_or_else(|| "None".to_string(), |fee| fee.to_string()),
+    )
+}
+
+/// Fields for NullifyLeaves: one `leaf[i]` group per nullified leaf.
+#[cfg(not(target_os = "solana"))]
+pub fn nullify_leaves_fields(
+    data: &NullifyLeavesInstructionData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    data.leaf_indices
+        .iter()
+        .enumerate()
+        .map(|(i, leaf_index)| {
+            let mut children = vec![DecodedField::new("leaf_index", leaf_index.to_string())];
+            if let Some(queue_index) = data.leaves_queue_indices.get(i) {
+                children.push(DecodedField::new("queue_index", queue_index.to_string()));
+            }
+            if let Some(change_log_index) = data.change_log_indices.get(i) {
+                children.push(DecodedField::new(
+                    "change_log_index",
+                    change_log_index.to_string(),
+                ));
+            }
+            if let Some(proof) = data.proofs.get(i) {
+                children.push(DecodedField::new("proof_len", proof.len().to_string()));
+            }
+            DecodedField::with_children(format!("leaf[{}]", i), children)
+        })
+        .collect()
+}
+
+/// Fields for UpdateAddressMerkleTree.
+#[cfg(not(target_os = "solana"))]
+pub fn update_address_merkle_tree_fields(
+    data: &UpdateAddressMerkleTreeInstructionData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("queue_index", data.value.to_string()),
+        DecodedField::new("changelog_index", data.changelog_index.to_string()),
+        DecodedField::new(
+            "indexed_changelog_index",
+            data.indexed_changelog_index.to_string(),
+        ),
+        DecodedField::new("low_address_index", data.low_address_index.to_string()),
+        DecodedField::new(
+            "low_address_next_index",
+            data.low_address_next_index.to_string(),
+        ),
+    ]
+}
+
+/// Fields for BatchNullify, BatchAppend and BatchUpdateAddressTree: the new root
+/// and the proof (hidden unless proof bytes are requested).
+#[cfg(not(target_os = "solana"))]
+pub fn batch_proof_inputs_fields(
+    data: &BatchProofInputsWrapper,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("new_root", base58(&data.0.new_root)),
+        DecodedField::new("proof", data.0.compressed_proof.to_hex()),
+    ]
+}
+
+/// Fields for MigrateState.
+#[cfg(not(target_os = "solana"))]
+pub fn migrate_state_fields(
+    data: &MigrateLeafParams,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("leaf_index", data.leaf_index.to_string()),
+        DecodedField::new("change_log_index", data.change_log_index.to_string()),
+        DecodedField::new("leaf", base58(&data.leaf)),
+        DecodedField::new("proof_len", data.proof.len().to_string()),
+    ]
+}
+
+/// Fields for RolloverBatchedStateMerkleTree.
+#[cfg(not(target_os = "solana"))]
+pub fn rollover_batched_state_merkle_tree_fields(
+    data: &RolloverBatchedStateMerkleTreeInstructionData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("additional_bytes", data.additional_bytes.to_string()),
+        network_fee_field(data.network_fee),
+    ]
+}
+
+/// Fields for RolloverBatchedAddressMerkleTree.
+#[cfg(not(target_os = "solana"))]
+pub fn rollover_batched_address_merkle_tree_fields(
+    data: &RolloverBatchedAddressMerkleTreeInstructionData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![network_fee_field(data.network_fee)]
+}
+
+// ============================================================================
+// Instruction Enum
+// ============================================================================
 
 /// Account Compression program instructions.
 ///
@@ -61,7 +204,11 @@ pub enum AccountCompressionInstruction {
     InitializeStateMerkleTreeAndNullifierQueue,
 
     /// Nullify leaves in a state Merkle tree
-    #[instruction_decoder(account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree", "nullifier_queue"])]
+    #[instruction_decoder(
+        account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree", "nullifier_queue"],
+        params = NullifyLeavesInstructionData,
+        fields_formatter = crate::programs::account_compression::nullify_leaves_fields
+    )]
     NullifyLeaves,
 
     /// Rollover a state Merkle tree and nullifier queue
@@ -76,7 +223,11 @@ pub enum AccountCompressionInstruction {
     InitializeAddressMerkleTreeAndQueue,
 
     /// Update an address Merkle tree with a new address
-    #[instruction_decoder(account_names = ["authority", "registered_program_pda", "queue", "merkle_tree", "log_wrapper"])]
+    #[instruction_decoder(
+        account_names = ["authority", "registered_program_pda", "queue", "merkle_tree", "log_wrapper"],
+        params = UpdateAddressMerkleTreeInstructionData,
+        fields_formatter = crate::programs::account_compression::update_address_merkle_tree_fields
+    )]
     UpdateAddressMerkleTree,
 
     /// Rollover an address Merkle tree and queue
@@ -102,32 +253,233 @@ pub enum AccountCompressionInstruction {
     InitializeBatchedAddressMerkleTree,
 
     /// Nullify a batch of leaves from input queue to state Merkle tree with ZK proof
-    #[instruction_decoder(account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree"])]
+    #[instruction_decoder(
+        account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree"],
+        params = BatchProofInputsWrapper,
+        fields_formatter = crate::programs::account_compression::batch_proof_inputs_fields
+    )]
     BatchNullify,
 
     /// Append a batch of leaves from output queue to state Merkle tree with ZK proof
-    #[instruction_decoder(account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree", "output_queue"])]
+    #[instruction_decoder(
+        account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree", "output_queue"],
+        params = BatchProofInputsWrapper,
+        fields_formatter = crate::programs::account_compression::batch_proof_inputs_fields
+    )]
     BatchAppend,
 
     /// Insert a batch of addresses into a batched address Merkle tree with ZK proof
-    #[instruction_decoder(account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree"])]
+    #[instruction_decoder(
+        account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree"],
+        params = BatchProofInputsWrapper,
+        fields_formatter = crate::programs::account_compression::batch_proof_inputs_fields
+    )]
     BatchUpdateAddressTree,
 
     // ========================================================================
     // Batched Rollover Operations
     // ========================================================================
     /// Rollover a batched address Merkle tree
-    #[instruction_decoder(account_names = ["fee_payer", "authority", "registered_program_pda", "new_address_merkle_tree", "old_address_merkle_tree"])]
+    #[instruction_decoder(
+        account_names = ["fee_payer", "authority", "registered_program_pda", "new_address_merkle_tree", "old_address_merkle_tree"],
+        params = RolloverBatchedAddressMerkleTreeInstructionData,
+        fields_formatter = crate::programs::account_compression::rollover_batched_address_merkle_tree_fields
+    )]
     RolloverBatchedAddressMerkleTree,
 
     /// Rollover a batched state Merkle tree and output queue
-    #[instruction_decoder(account_names = ["fee_payer", "authority", "registered_program_pda", "new_state_merkle_tree", "old_state_merkle_tree", "new_output_queue", "old_output_queue"])]
+    #[instruction_decoder(
+        account_names = ["fee_payer", "authority", "registered_program_pda", "new_state_merkle_tree", "old_state_merkle_tree", "new_output_queue", "old_output_queue"],
+        params = RolloverBatchedStateMerkleTreeInstructionData,
+        fields_formatter = crate::programs::account_compression::rollover_batched_state_merkle_tree_fields
+    )]
     RolloverBatchedStateMerkleTree,
 
     // ========================================================================
     // Migration
     // ========================================================================
     /// Migrate state from a v1 state Merkle tree to a v2 state Merkle tree
-    #[instruction_decoder(account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree", "output_queue"])]
+    #[instruction_decoder(
+        account_names = ["authority", "registered_program_pda", "log_wrapper", "merkle_tree", "output_queue"],
+        params = MigrateLeafParams,
+        fields_formatter = crate::programs::account_compression::migrate_state_fields
+    )]
     MigrateState,
 }
+
+#[cfg(test)]
+mod tests {
+    use solana_pubkey::Pubkey;
+
+    use super::*;
+    use crate::InstructionDecoder;
+
+    /// Anchor discriminator followed by the borsh-serialized arguments.
+    fn instruction_data(discriminator: [u8; 8], args: impl borsh::BorshSerialize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.extend(borsh::to_vec(&args).unwrap());
+        data
+    }
+
+    fn accounts(count: usize) -> Vec<AccountMeta> {
+        (0..count)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i as u8 + 1; 32]), false))
+            .collect()
+    }
+
+    fn decode(data: &[u8], accounts: &[AccountMeta]) -> (String, Vec<DecodedField>) {
+        let decoded = AccountCompressionInstructionDecoder
+            .decode(data, accounts)
+            .unwrap();
+        assert!(
+            !decoded.fields.iter().any(DecodedField::is_decode_error),
+            "params failed to decode"
+        );
+        (decoded.name, decoded.fields)
+    }
+
+    /// Top-level `(name, value)` pairs
+    fn values(fields: &[DecodedField]) -> Vec<(&str, &str)> {
+        fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_nullify_leaves() {
+        let accounts = accounts(5);
+        let data = instruction_data(
+            [158, 91, 21, 224, 159, 65, 177, 67],
+            (
+                vec![12u64, 13],
+                vec![3u16, 4],
+                vec![5u64, 6],
+                vec![vec![[0u8; 32]; 26], vec![[0u8; 32]; 26]],
+            ),
+        );
+        let (name, fields) = decode(&data, &accounts);
+
+        assert_eq!(name, "NullifyLeaves");
+        assert_eq!(fields.len(), 2);
+        assert_eq!(
+            fields[1],
+            DecodedField::with_children(
+                "leaf[1]",
+                vec![
+                    DecodedField::new("leaf_index", "6"),
+                    DecodedField::new("queue_index", "4"),
+                    DecodedField::new("change_log_index", "13"),
+                    DecodedField::new("proof_len", "26"),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_update_address_merkle_tree() {
+        let accounts = accounts(5);
+        let data = instruction_data(
+            [75, 208, 63, 56, 207, 74, 124, 18],
+            (
+                1u16,
+                2u16,
+                9u16,
+                7u64,
+                [0u8; 32],
+                8u64,
+                [0u8; 32],
+                [[0u8; 32]; 16],
+            ),
+        );
+        let (name, fields) = decode(&data, &accounts);
+
+        assert_eq!(name, "UpdateAddressMerkleTree");
+        assert_eq!(
+            values(&fields),
+            [
+                ("queue_index", "9"),
+                ("changelog_index", "1"),
+                ("indexed_changelog_index", "2"),
+                ("low_address_index", "7"),
+                ("low_address_next_index", "8"),
+            ]
+        );
+    }
+
+    /// `Vec<u8>` argument holding `new_root` and a compressed proof.
+    fn batch_proof_inputs() -> Vec<u8> {
+        let mut inputs = vec![9u8; 32];
+        inputs.extend([0u8; 128]);
+        inputs
+    }
+
+    #[test]
+    fn test_batch_instructions() {
+        let new_root = bs58::encode([9u8; 32]).into_string();
+        for (discriminator, expected) in [
+            ([134, 158, 210, 240, 252, 35, 253, 247], "BatchNullify"),
+            ([221, 194, 3, 216, 37, 62, 220, 147], "BatchAppend"),
+            (
+                [171, 127, 242, 65, 125, 189, 121, 117],
+                "BatchUpdateAddressTree",
+            ),
+        ] {
+            let data = instruction_data(discriminator, batch_proof_inputs());
+            let (name, fields) = decode(&data, &accounts(5));
+
+            assert_eq!(name, expected);
+            assert_eq!(fields[0], DecodedField::new("new_root", new_root.as_str()));
+            assert_eq!(fields[1].name, "proof");
+            assert_eq!(fields[1].value, format!("0x{}", "00".repeat(128)));
+        }
+    }
+
+    #[test]
+    fn test_migrate_state() {
+        let accounts = accounts(5);
+        let data = instruction_data(
+            [34, 189, 226, 222, 218, 156, 19, 213],
+            (3u64, [1u8; 32], 42u64, [[0u8; 32]; 26]),
+        );
+        let (name, fields) = decode(&data, &accounts);
+
+        assert_eq!(name, "MigrateState");
+        let leaf = bs58::encode([1u8; 32]).into_string();
+        assert_eq!(
+            values(&fields),
+            [
+                ("leaf_index", "42"),
+                ("change_log_index", "3"),
+                ("leaf", leaf.as_str()),
+                ("proof_len", "26"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rollover_batched_state_merkle_tree() {
+        let accounts = accounts(7);
+        let data = instruction_data(
+            [240, 48, 219, 180, 206, 253, 80, 140],
+            (100u64, Some(5_000u64)),
+        );
+        let (name, fields) = decode(&data, &accounts);
+
+        assert_eq!(name, "RolloverBatchedStateMerkleTree");
+        assert_eq!(
+            values(&fields),
+            [("additional_bytes", "100"), ("network_fee", "5000")]
+        );
+    }
+
+    #[test]
+    fn test_rollover_batched_address_merkle_tree() {
+        let accounts = accounts(5);
+        let data = instruction_data([81, 119, 117, 4, 194, 36, 127, 208], None::<u64>);
+        let (name, fields) = decode(&data, &accounts);
+
+        assert_eq!(name, "RolloverBatchedAddressMerkleTree");
+        assert_eq!(values(&fields), [("network_fee", "None")]);
+    }
+}
@@ -19,8 +19,8 @@ use light_instruction_decoder_derive::InstructionDecoder;
     discriminator_size = 1
 )]
 pub enum ComputeBudgetInstruction {
-    /// Deprecated variant (index 0)
-    Unused,
+    /// Deprecated combined units and fee request, found in pre-1.10 transactions (index 0)
+    RequestUnitsDeprecated { units: u32, additional_fee: u32 },
 
     /// Request a specific heap frame size (index 1)
     RequestHeapFrame { bytes: u32 },
@@ -34,3 +34,28 @@ pub enum ComputeBudgetInstruction {
     /// Set loaded accounts data size limit (index 4)
     SetLoadedAccountsDataSizeLimit { bytes: u32 },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DecodedField, InstructionDecoder};
+
+    #[test]
+    fn test_request_units_deprecated() {
+        let data = [
+            [0u8].as_slice(),
+            &200_000u32.to_le_bytes(),
+            &5_000u32.to_le_bytes(),
+        ]
+        .concat();
+        let decoded = ComputeBudgetInstructionDecoder.decode(&data, &[]).unwrap();
+        assert_eq!(decoded.name, "RequestUnitsDeprecated");
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("units", "200000"),
+                DecodedField::new("additional_fee", "5000"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! Catch-all decoder for programs without a registered decoder.
+//!
+//! Install it with [`DecoderRegistry::set_fallback`](crate::DecoderRegistry::set_fallback)
+//! to give every unknown program a uniform rendering.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{DecodedField, DecodedInstruction, InstructionDecoder};
+
+/// Fallback decoder that names instructions after their likely Anchor discriminator.
+///
+/// Instructions with at least 8 bytes of data are named `anchor_disc:0x<hex>` after
+/// the first 8 bytes and show a `data_len` field. Shorter instructions are left
+/// undecoded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnchorFallbackDecoder;
+
+impl InstructionDecoder for AnchorFallbackDecoder {
+    /// Not used: a fallback is consulted for any program without a decoder
+    fn program_id(&self) -> Pubkey {
+        Pubkey::default()
+    }
+
+    fn program_name(&self) -> &'static str {
+        "Unknown Program"
+    }
+
+    fn discriminator_size(&self) -> Option<usize> {
+        Some(8)
+    }
+
+    fn decode(&self, data: &[u8], _accounts: &[AccountMeta]) -> Option<DecodedInstruction> {
+        let discriminator = data.get(..8)?;
+        let hex = crate::core::hex(discriminator);
+        Some(DecodedInstruction::with_fields_and_accounts(
+            format!("anchor_disc:0x{hex}"),
+            vec![DecodedField::new("data_len", data.len().to_string())],
+            Vec::new(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_fallback_decoder() {
+        let data = [[1, 2, 3, 4, 5, 6, 7, 0xab].as_slice(), &[0; 4]].concat();
+        let decoded = AnchorFallbackDecoder.decode(&data, &[]).unwrap();
+        assert_eq!(decoded.name, "anchor_disc:0x01020304050607ab");
+        assert_eq!(decoded.fields, vec![DecodedField::new("data_len", "12")]);
+
+        assert!(AnchorFallbackDecoder.decode(&[1, 2, 3], &[]).is_none());
+    }
+}
@@ -9,17 +9,26 @@
 //! - `InvokeCpi`: CPI invocation from another program (has 4-byte Anchor prefix after discriminator)
 //! - `InvokeCpiWithReadOnly`: CPI with read-only accounts (no prefix, borsh-only)
 //! - `InvokeCpiWithAccountInfo`: CPI with full account info (no prefix, borsh-only)
+//!
+//! ## Administrative instructions
+//!
+//! - `InitCpiContextAccount`: initialize a CPI context account for a state tree
+//! - `ReInitCpiContextAccount`: migrate a CPI context account to the current layout
+//!
+//! Neither takes arguments; only their accounts are labeled. The Light System program
+//! has no config account of its own (protocol config lives in the Light Registry).
 
 // Allow the macro-generated code to reference types from this crate
 extern crate self as light_instruction_decoder;
 
 use crate::programs::light_types::{
-    CompressedAccountInfo, InAccount, InstructionDataInvoke, InstructionDataInvokeCpi,
-    InstructionDataInvokeCpiWithAccountInfo, InstructionDataInvokeCpiWithReadOnly,
-    NewAddressParamsAssignedPacked, NewAddressParamsPacked,
+    CompressedAccountInfo, CompressedCpiContext, CompressedProof, InAccount, InstructionDataInvoke,
+    InstructionDataInvokeCpi, InstructionDataInvokeCpiWithAccountInfo,
+    InstructionDataInvokeCpiWithReadOnly, NewAddressParamsAssignedPacked, NewAddressParamsPacked,
     OutputCompressedAccountWithPackedContext, PackedCompressedAccountWithMerkleContext,
     PackedReadOnlyAddress,
 };
+use crate::DecodedField;
 use borsh::BorshDeserialize;
 use light_instruction_decoder_derive::InstructionDecoder;
 use solana_instruction::AccountMeta;
@@ -236,6 +245,42 @@ fn format_readonly_output_accounts_section(
     }
 }
 
+/// Format one new address param entry: base58 seed, queue and tree (account
+/// index and resolved pubkey) and root index.
+#[cfg(not(target_os = "solana"))]
+fn format_new_address_param(
+    output: &mut String,
+    index: usize,
+    seed: &[u8; 32],
+    queue_index: u8,
+    tree_index: u8,
+    root_index: u16,
+    instruction_accounts: &[AccountMeta],
+) {
+    use std::fmt::Write;
+
+    let (tree_pubkey, queue_pubkey) =
+        resolve_tree_and_queue_pubkeys(instruction_accounts, Some(tree_index), Some(queue_index));
+    let resolved = |account_index: u8, pubkey: Option<Pubkey>| match pubkey {
+        Some(pubkey) => format!("#{} → {}", account_index, pubkey),
+        None => format!("#{}", account_index),
+    };
+
+    let _ = writeln!(output, "  [{}]", index);
+    let _ = writeln!(output, "      seed: {}", bs58::encode(seed).into_string());
+    let _ = writeln!(
+        output,
+        "      address_queue: {}",
+        resolved(queue_index, queue_pubkey)
+    );
+    let _ = writeln!(
+        output,
+        "      address_tree: {}",
+        resolved(tree_index, tree_pubkey)
+    );
+    let _ = writeln!(output, "      root_index: {}", root_index);
+}
+
 /// Format new address params section for Invoke/InvokeCpi.
 #[cfg(not(target_os = "solana"))]
 fn format_new_address_params_section(
@@ -251,25 +296,15 @@ fn format_new_address_params_section(
 
     let _ = writeln!(output, "New Addresses ({}):", params.len());
     for (i, param) in params.iter().enumerate() {
-        let _ = writeln!(output, "  [{}] seed: {:?}", i, param.seed);
-        let tree_idx = Some(param.address_merkle_tree_account_index);
-        let queue_idx = Some(param.address_queue_account_index);
-        let (tree_pubkey, queue_pubkey) =
-            resolve_tree_and_queue_pubkeys(instruction_accounts, tree_idx, queue_idx);
-        if let Some(qp) = queue_pubkey {
-            let _ = writeln!(
-                output,
-                "      queue[{}]: {}",
-                param.address_queue_account_index, qp
-            );
-        }
-        if let Some(tp) = tree_pubkey {
-            let _ = writeln!(
-                output,
-                "      tree[{}]: {}",
-                param.address_merkle_tree_account_index, tp
-            );
-        }
+        format_new_address_param(
+            output,
+            i,
+            &param.seed,
+            param.address_queue_account_index,
+            param.address_merkle_tree_account_index,
+            param.address_merkle_tree_root_index,
+            instruction_accounts,
+        );
     }
 }
 
@@ -288,25 +323,15 @@ fn format_new_address_params_assigned_section(
 
     let _ = writeln!(output, "New Addresses ({}):", params.len());
     for (i, param) in params.iter().enumerate() {
-        let _ = writeln!(output, "  [{}] seed: {:?}", i, param.seed);
-        let tree_idx = Some(param.address_merkle_tree_account_index);
-        let queue_idx = Some(param.address_queue_account_index);
-        let (tree_pubkey, queue_pubkey) =
-            resolve_tree_and_queue_pubkeys(instruction_accounts, tree_idx, queue_idx);
-        if let Some(qp) = queue_pubkey {
-            let _ = writeln!(
-                output,
-                "      queue[{}]: {}",
-                param.address_queue_account_index, qp
-            );
-        }
-        if let Some(tp) = tree_pubkey {
-            let _ = writeln!(
-                output,
-                "      tree[{}]: {}",
-                param.address_merkle_tree_account_index, tp
-            );
-        }
+        format_new_address_param(
+            output,
+            i,
+            &param.seed,
+            param.address_queue_account_index,
+            param.address_merkle_tree_account_index,
+            param.address_merkle_tree_root_index,
+            instruction_accounts,
+        );
         let assigned = if param.assigned_to_account {
             format!("account[{}]", param.assigned_account_index)
         } else {
@@ -344,6 +369,86 @@ fn format_read_only_addresses_section(
     }
 }
 
+/// Name of the account layout selected by the `mode` byte of the V2 invoke instructions.
+pub fn account_mode_name(mode: u8) -> &'static str {
+    match mode {
+        0 => "Anchor",
+        1 => "V2",
+        _ => "Unknown",
+    }
+}
+
+/// Format mode, flags and section counts for ReadOnly/AccountInfo variants.
+#[cfg(not(target_os = "solana"))]
+#[allow(clippy::too_many_arguments)]
+fn format_mode_and_flags_section(
+    output: &mut String,
+    mode: u8,
+    is_compress: bool,
+    with_cpi_context: bool,
+    with_transaction_hash: bool,
+    new_addresses: usize,
+    read_only_addresses: usize,
+    read_only_accounts: usize,
+) {
+    use std::fmt::Write;
+
+    let _ = writeln!(output, "Mode: {} ({})", account_mode_name(mode), mode);
+    let _ = writeln!(
+        output,
+        "Flags: is_compress: {}, with_cpi_context: {}, with_transaction_hash: {}",
+        is_compress, with_cpi_context, with_transaction_hash
+    );
+    let _ = writeln!(
+        output,
+        "Counts: new_addresses: {}, read_only_addresses: {}, read_only_accounts: {}",
+        new_addresses, read_only_addresses, read_only_accounts
+    );
+}
+
+/// Format the CPI context section: write flags and the context account.
+///
+/// When writing to the CPI context, V2 instructions pass the context account at
+/// `write_mode_position`. Otherwise `cpi_context_account_index` is resolved like
+/// the tree account indices.
+#[cfg(not(target_os = "solana"))]
+fn format_cpi_context_section(
+    output: &mut String,
+    cpi_context: &CompressedCpiContext,
+    instruction_accounts: &[AccountMeta],
+    write_mode_position: Option<usize>,
+) {
+    use std::fmt::Write;
+
+    let write_mode = cpi_context.set_context || cpi_context.first_set_context;
+    let index = cpi_context.cpi_context_account_index;
+    let account = match write_mode_position.filter(|_| write_mode) {
+        Some(position) => instruction_accounts
+            .get(position)
+            .map(|account| account.pubkey.to_string())
+            .unwrap_or_else(|| "None".to_string()),
+        None => match resolve_tree_and_queue_pubkeys(instruction_accounts, Some(index), None).0 {
+            Some(pubkey) => format!("#{} → {}", index, pubkey),
+            None => format!("#{}", index),
+        },
+    };
+
+    let _ = writeln!(output, "CPI Context:");
+    let _ = writeln!(output, "  set_context: {}", cpi_context.set_context);
+    let _ = writeln!(
+        output,
+        "  first_set_context: {}",
+        cpi_context.first_set_context
+    );
+    let _ = writeln!(output, "  account: {}", account);
+}
+
+/// Whether a V2 invoke instruction uses its CPI context.
+#[cfg(not(target_os = "solana"))]
+fn uses_cpi_context(with_cpi_context: bool, cpi_context: &CompressedCpiContext) -> bool {
+    with_cpi_context || cpi_context.set_context || cpi_context.first_set_context
+}
+
 /// Format compress/decompress and relay fee section for Invoke/InvokeCpi.
 #[cfg(not(target_os = "solana"))]
 fn format_fee_section(
@@ -486,6 +591,28 @@ fn resolve_tree_and_queue_pubkeys(
     (tree_pubkey, queue_pubkey)
 }
 
+/// Write `has_proof: true/false`; the proof bytes themselves go in a separate
+/// `proof` field, see [`summary_and_proof_fields`].
+#[cfg(not(target_os = "solana"))]
+pub(crate) fn write_proof(output: &mut String, proof: &Option<CompressedProof>) {
+    use std::fmt::Write;
+    let _ = writeln!(output, "has_proof: {}", proof.is_some());
+}
+
+/// The formatted summary as an unnamed field, followed by the proof bytes as a
+/// `proof` field when there is one.
+///
+/// Formatted output and snapshots leave the `proof` field out unless
+/// [`crate::EnhancedLoggingConfig::show_proof_bytes`] is set.
+#[cfg(not(target_os = "solana"))]
+fn summary_and_proof_fields(summary: String, proof: &Option<CompressedProof>) -> Vec<DecodedField> {
+    let mut fields = vec![DecodedField::new("", summary)];
+    if let Some(proof) = proof {
+        fields.push(DecodedField::new("proof", proof.to_hex()));
+    }
+    fields
+}
+
 /// Format InvokeCpiWithReadOnly instruction data.
 #[cfg(not(target_os = "solana"))]
 pub fn format_invoke_cpi_readonly(
@@ -495,13 +622,23 @@ pub fn format_invoke_cpi_readonly(
     use std::fmt::Write;
     let mut output = String::new();
 
+    format_mode_and_flags_section(
+        &mut output,
+        data.mode,
+        data.is_compress,
+        data.with_cpi_context,
+        data.with_transaction_hash,
+        data.new_address_params.len(),
+        data.read_only_addresses.len(),
+        data.read_only_accounts.len(),
+    );
     let _ = writeln!(
         output,
         "Accounts: in: {}, out: {}",
         data.input_compressed_accounts.len(),
         data.output_compressed_accounts.len()
     );
-    let _ = writeln!(output, "Proof: Validity proof");
+    write_proof(&mut output, &data.proof);
 
     format_readonly_input_accounts_section(
         &mut output,
@@ -517,6 +654,10 @@ pub fn format_invoke_cpi_readonly(
     );
     format_new_address_params_assigned_section(&mut output, &data.new_address_params, accounts);
     format_read_only_addresses_section(&mut output, &data.read_only_addresses, accounts);
+    if uses_cpi_context(data.with_cpi_context, &data.cpi_context) {
+        // Write mode accounts: fee_payer, cpi_authority_pda, cpi_context
+        format_cpi_context_section(&mut output, &data.cpi_context, accounts, Some(2));
+    }
     format_compress_decompress_section(
         &mut output,
         data.compress_or_decompress_lamports,
@@ -608,16 +749,30 @@ pub fn format_invoke_cpi_account_info(
         .filter(|a| a.output.is_some())
         .count();
 
+    format_mode_and_flags_section(
+        &mut output,
+        data.mode,
+        data.is_compress,
+        data.with_cpi_context,
+        data.with_transaction_hash,
+        data.new_address_params.len(),
+        data.read_only_addresses.len(),
+        data.read_only_accounts.len(),
+    );
     let _ = writeln!(
         output,
         "Accounts: in: {}, out: {}",
         input_count, output_count
     );
-    let _ = writeln!(output, "Proof: Validity proof");
+    write_proof(&mut output, &data.proof);
 
     format_account_infos_section(&mut output, &data.account_infos, accounts);
     format_new_address_params_assigned_section(&mut output, &data.new_address_params, accounts);
     format_read_only_addresses_section(&mut output, &data.read_only_addresses, accounts);
+    if uses_cpi_context(data.with_cpi_context, &data.cpi_context) {
+        // Write mode accounts: fee_payer, cpi_authority_pda, cpi_context
+        format_cpi_context_section(&mut output, &data.cpi_context, accounts, Some(2));
+    }
     format_compress_decompress_section(
         &mut output,
         data.compress_or_decompress_lamports,
@@ -728,6 +883,41 @@ pub fn format_invoke_cpi_wrapper(data: &InvokeCpiWrapper, accounts: &[AccountMet
     format_invoke_cpi_inner(&data.0, accounts)
 }
 
+/// Fields of Invoke: the [`format_invoke_wrapper`] summary and the proof.
+#[cfg(not(target_os = "solana"))]
+pub fn invoke_wrapper_fields(data: &InvokeWrapper, accounts: &[AccountMeta]) -> Vec<DecodedField> {
+    summary_and_proof_fields(format_invoke_wrapper(data, accounts), &data.0.proof)
+}
+
+/// Fields of InvokeCpi: the [`format_invoke_cpi_wrapper`] summary and the proof.
+#[cfg(not(target_os = "solana"))]
+pub fn invoke_cpi_wrapper_fields(
+    data: &InvokeCpiWrapper,
+    accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    summary_and_proof_fields(format_invoke_cpi_wrapper(data, accounts), &data.0.proof)
+}
+
+/// Fields of InvokeCpiWithReadOnly: the [`format_invoke_cpi_readonly`] summary and
+/// the proof.
+#[cfg(not(target_os = "solana"))]
+pub fn invoke_cpi_readonly_fields(
+    data: &InstructionDataInvokeCpiWithReadOnly,
+    accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    summary_and_proof_fields(format_invoke_cpi_readonly(data, accounts), &data.proof)
+}
+
+/// Fields of InvokeCpiWithAccountInfo: the [`format_invoke_cpi_account_info`]
+/// summary and the proof.
+#[cfg(not(target_os = "solana"))]
+pub fn invoke_cpi_account_info_fields(
+    data: &InstructionDataInvokeCpiWithAccountInfo,
+    accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    summary_and_proof_fields(format_invoke_cpi_account_info(data, accounts), &data.proof)
+}
+
 /// Format InstructionDataInvoke (internal helper).
 #[cfg(not(target_os = "solana"))]
 fn format_invoke_inner(data: &InstructionDataInvoke, accounts: &[AccountMeta]) -> String {
@@ -741,9 +931,7 @@ fn format_invoke_inner(data: &InstructionDataInvoke, accounts: &[AccountMeta]) -
         data.output_compressed_accounts.len()
     );
 
-    if data.proof.is_some() {
-        let _ = writeln!(output, "Proof: Validity proof");
-    }
+    write_proof(&mut output, &data.proof);
 
     format_input_accounts_section(
         &mut output,
@@ -775,9 +963,7 @@ fn format_invoke_cpi_inner(data: &InstructionDataInvokeCpi, accounts: &[AccountM
         data.output_compressed_accounts.len()
     );
 
-    if data.proof.is_some() {
-        let _ = writeln!(output, "Proof: Validity proof");
-    }
+    write_proof(&mut output, &data.proof);
 
     format_input_accounts_section(
         &mut output,
@@ -786,6 +972,9 @@ fn format_invoke_cpi_inner(data: &InstructionDataInvokeCpi, accounts: &[AccountM
     );
     format_output_accounts_section(&mut output, &data.output_compressed_accounts, accounts);
     format_new_address_params_section(&mut output, &data.new_address_params, accounts);
+    if let Some(cpi_context) = &data.cpi_context {
+        format_cpi_context_section(&mut output, cpi_context, accounts, None);
+    }
     format_fee_section(
         &mut output,
         data.compress_or_decompress_lamports,
@@ -809,7 +998,7 @@ pub enum LightSystemInstruction {
     #[instruction_decoder(
         account_names = ["fee_payer", "authority", "registered_program_pda", "log_program", "account_compression_authority", "account_compression_program", "self_program"],
         params = InvokeWrapper,
-        pretty_formatter = crate::programs::light_system::format_invoke_wrapper
+        fields_formatter = crate::programs::light_system::invoke_wrapper_fields
     )]
     Invoke,
 
@@ -818,7 +1007,7 @@ pub enum LightSystemInstruction {
     #[instruction_decoder(
         account_names = ["fee_payer", "authority", "registered_program_pda", "log_program", "account_compression_authority", "account_compression_program", "invoking_program", "cpi_signer"],
         params = InvokeCpiWrapper,
-        pretty_formatter = crate::programs::light_system::format_invoke_cpi_wrapper
+        fields_formatter = crate::programs::light_system::invoke_cpi_wrapper_fields
     )]
     InvokeCpi,
 
@@ -827,7 +1016,7 @@ pub enum LightSystemInstruction {
     #[instruction_decoder(
         params = InstructionDataInvokeCpiWithReadOnly,
         account_names_resolver_from_params = crate::programs::light_system::resolve_invoke_cpi_readonly_account_names,
-        pretty_formatter = crate::programs::light_system::format_invoke_cpi_readonly
+        fields_formatter = crate::programs::light_system::invoke_cpi_readonly_fields
     )]
     InvokeCpiWithReadOnly,
 
@@ -836,7 +1025,259 @@ pub enum LightSystemInstruction {
     #[instruction_decoder(
         params = InstructionDataInvokeCpiWithAccountInfo,
         account_names_resolver_from_params = crate::programs::light_system::resolve_invoke_cpi_account_info_account_names,
-        pretty_formatter = crate::programs::light_system::format_invoke_cpi_account_info
+        fields_formatter = crate::programs::light_system::invoke_cpi_account_info_fields
     )]
     InvokeCpiWithAccountInfo,
+
+    // ========================================================================
+    // Administrative (discriminators computed from the snake_case name)
+    // ========================================================================
+    /// Initialize a CPI context account associated with a state Merkle tree.
+    #[instruction_decoder(account_names = ["fee_payer", "cpi_context_account", "associated_merkle_tree"])]
+    InitCpiContextAccount,
+
+    /// Resize and reinitialize a CPI context account in the current account layout.
+    #[instruction_decoder(account_names = ["cpi_context_account"])]
+    ReInitCpiContextAccount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InstructionDecoder;
+
+    #[test]
+    fn test_account_mode_name() {
+        assert_eq!(account_mode_name(0), "Anchor");
+        assert_eq!(account_mode_name(1), "V2");
+        assert_eq!(account_mode_name(7), "Unknown");
+    }
+
+    #[test]
+    fn test_invoke_cpi_with_read_only_mode_and_flags() {
+        let mut data = vec![86, 47, 163, 166, 21, 223, 92, 8];
+        // mode, bump, invoking_program_id, compress_or_decompress_lamports,
+        // is_compress, with_cpi_context, with_transaction_hash
+        data.extend(borsh::to_vec(&(1u8, 255u8, [7u8; 32], 0u64, false, true, false)).unwrap());
+        // cpi_context (set_context, first_set_context, account_index), no proof
+        data.extend(borsh::to_vec(&(false, false, 0u8, 0u8)).unwrap());
+        // new_address_params, input and output accounts: empty
+        data.extend(borsh::to_vec(&(0u32, 0u32, 0u32)).unwrap());
+        // read_only_addresses: address, root index, tree account index
+        data.extend(
+            borsh::to_vec(&vec![([3u8; 32], 10u16, 0u8), ([4u8; 32], 11u16, 1u8)]).unwrap(),
+        );
+        // read_only_accounts: hash, merkle context (tree, queue, leaf, prove_by_index), root index
+        data.extend(borsh::to_vec(&vec![([5u8; 32], (0u8, 1u8, 42u32, false), 12u16)]).unwrap());
+
+        let accounts: Vec<AccountMeta> = (0..6)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i + 1; 32]), false))
+            .collect();
+        let decoded = LightSystemInstructionDecoder
+            .decode(&data, &accounts)
+            .unwrap();
+        assert_eq!(decoded.name, "InvokeCpiWithReadOnly");
+        let formatted = &decoded.fields[0].value;
+
+        assert!(formatted.contains("Mode: V2 (1)"));
+        assert!(formatted.contains(
+            "Flags: is_compress: false, with_cpi_context: true, with_transaction_hash: false"
+        ));
+        assert!(formatted
+            .contains("Counts: new_addresses: 0, read_only_addresses: 2, read_only_accounts: 1"));
+        assert!(formatted.contains("Accounts: in: 0, out: 0"));
+    }
+
+    #[test]
+    fn test_invoke_new_address_params() {
+        let mut data = vec![26, 16, 169, 7, 21, 202, 242, 25];
+        // Anchor vec length prefix (skipped), no proof, input and output accounts: empty,
+        // no relay fee
+        data.extend(borsh::to_vec(&(0u32, 0u8, 0u32, 0u32, 0u8)).unwrap());
+        // new_address_params: seed, queue index, tree index, root index
+        data.extend(
+            borsh::to_vec(&vec![
+                ([1u8; 32], 1u8, 0u8, 7u16),
+                ([2u8; 32], 3u8, 2u8, 8u16),
+            ])
+            .unwrap(),
+        );
+        // no compress_or_decompress_lamports, is_compress
+        data.extend(borsh::to_vec(&(0u8, false)).unwrap());
+
+        // fee_payer, authority, then the system program and one account before the trees
+        let system_program = Pubkey::default();
+        let trees: Vec<Pubkey> = (0..4)
+            .map(|i| Pubkey::new_from_array([i + 10; 32]))
+            .collect();
+        let accounts: Vec<AccountMeta> = [
+            Pubkey::new_from_array([8; 32]),
+            Pubkey::new_from_array([9; 32]),
+            system_program,
+            Pubkey::new_from_array([20; 32]),
+        ]
+        .iter()
+        .chain(&trees)
+        .map(|pubkey| AccountMeta::new(*pubkey, false))
+        .collect();
+
+        let decoded = LightSystemInstructionDecoder
+            .decode(&data, &accounts)
+            .unwrap();
+        assert_eq!(decoded.name, "Invoke");
+        let formatted = &decoded.fields[0].value;
+
+        let expected = [
+            "New Addresses (2):".to_string(),
+            "  [0]".to_string(),
+            format!("      seed: {}", Pubkey::new_from_array([1; 32])),
+            format!("      address_queue: #1 → {}", trees[1]),
+            format!("      address_tree: #0 → {}", trees[0]),
+            "      root_index: 7".to_string(),
+            "  [1]".to_string(),
+            format!("      seed: {}", Pubkey::new_from_array([2; 32])),
+            format!("      address_queue: #3 → {}", trees[3]),
+            format!("      address_tree: #2 → {}", trees[2]),
+            "      root_index: 8".to_string(),
+        ]
+        .join("\n");
+        assert!(formatted.contains(&expected), "{formatted}");
+    }
+
+    /// InvokeCpiWithReadOnly data with the given CPI context and empty sections.
+    fn invoke_cpi_with_read_only_fixture(set_context: bool, account_index: u8) -> Vec<u8> {
+        let mut data = vec![86, 47, 163, 166, 21, 223, 92, 8];
+        // mode, bump, invoking_program_id, compress_or_decompress_lamports,
+        // is_compress, with_cpi_context, with_transaction_hash
+        data.extend(borsh::to_vec(&(1u8, 255u8, [7u8; 32], 0u64, false, true, false)).unwrap());
+        // cpi_context (set_context, first_set_context, account_index), no proof
+        data.extend(borsh::to_vec(&(set_context, false, account_index, 0u8)).unwrap());
+        // new_address_params, input and output accounts, read-only addresses and accounts: empty
+        data.extend(borsh::to_vec(&(0u32, 0u32, 0u32, 0u32, 0u32)).unwrap());
+        data
+    }
+
+    #[test]
+    fn test_administrative_instructions() {
+        let accounts: Vec<AccountMeta> = (0..3)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i + 1; 32]), i == 0))
+            .collect();
+        let data = crate::anchor_instruction_discriminator("init_cpi_context_account");
+        let decoded = LightSystemInstructionDecoder
+            .decode(&data, &accounts)
+            .unwrap();
+        assert_eq!(decoded.name, "InitCpiContextAccount");
+        assert!(decoded.fields.is_empty());
+        assert_eq!(
+            decoded.account_names,
+            ["fee_payer", "cpi_context_account", "associated_merkle_tree"]
+        );
+        assert_eq!(
+            LightSystemInstructionDecoder.validate(&decoded, &accounts),
+            None
+        );
+
+        let data = crate::anchor_instruction_discriminator("re_init_cpi_context_account");
+        let decoded = LightSystemInstructionDecoder
+            .decode(&data, &accounts[1..2])
+            .unwrap();
+        assert_eq!(decoded.name, "ReInitCpiContextAccount");
+        assert_eq!(decoded.account_names, ["cpi_context_account"]);
+    }
+
+    #[test]
+    fn test_invoke_cpi_context() {
+        // Write mode: fee_payer, cpi_authority_pda, cpi_context
+        let write_accounts: Vec<AccountMeta> = (0..3)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i + 1; 32]), false))
+            .collect();
+        let decoded = LightSystemInstructionDecoder
+            .decode(&invoke_cpi_with_read_only_fixture(true, 0), &write_accounts)
+            .unwrap();
+        let expected = format!(
+            "CPI Context:\n  set_context: true\n  first_set_context: false\n  account: {}\n",
+            write_accounts[2].pubkey
+        );
+        assert!(
+            decoded.fields[0].value.contains(&expected),
+            "{}",
+            decoded.fields[0].value
+        );
+
+        // Executing with the context: the index points past the system program
+        let accounts: Vec<AccountMeta> = [
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::default(),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([3; 32]),
+            Pubkey::new_from_array([4; 32]),
+        ]
+        .iter()
+        .map(|pubkey| AccountMeta::new(*pubkey, false))
+        .collect();
+        let decoded = LightSystemInstructionDecoder
+            .decode(&invoke_cpi_with_read_only_fixture(false, 1), &accounts)
+            .unwrap();
+        assert!(decoded.fields[0].value.contains(&format!(
+            "  set_context: false\n  first_set_context: false\n  account: #1 → {}\n",
+            accounts[4].pubkey
+        )));
+    }
+
+    #[test]
+    fn test_proof_presence() {
+        let accounts: Vec<AccountMeta> = (0..6)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i + 1; 32]), false))
+            .collect();
+        let without_proof = invoke_cpi_with_read_only_fixture(false, 0);
+        // Same instruction with Some(proof) in place of the None tag after the CPI context
+        let mut with_proof = without_proof.clone();
+        let proof = [vec![1u8], vec![0xaa; 32], vec![0xbb; 64], vec![0xcc; 32]].concat();
+        with_proof.splice(56..57, proof);
+
+        let fields = |data: &[u8]| {
+            LightSystemInstructionDecoder
+                .decode(data, &accounts)
+                .unwrap()
+                .fields
+        };
+        let proof_hex = format!(
+            "0x{}{}{}",
+            "aa".repeat(32),
+            "bb".repeat(64),
+            "cc".repeat(32)
+        );
+        let fields_without = fields(&without_proof);
+        assert_eq!(fields_without.len(), 1);
+        assert!(fields_without[0].value.contains("\nhas_proof: false\n"));
+        let fields_with = fields(&with_proof);
+        assert!(fields_with[0].value.contains("\nhas_proof: true\n"));
+        assert!(!fields_with[0].value.contains("0x"));
+        assert_eq!(
+            fields_with[1],
+            DecodedField::new("proof", proof_hex.as_str())
+        );
+
+        // Formatted output drops the proof field unless proof bytes are requested
+        let program_id = LightSystemInstructionDecoder.program_id();
+        let format = |config: crate::EnhancedLoggingConfig| {
+            let mut config = config;
+            config.use_colors = false;
+            let mut builder =
+                crate::InstructionLogBuilder::new(program_id).data(with_proof.clone());
+            for account in &accounts {
+                builder = builder.account(account.pubkey, false, false);
+            }
+            let mut log = crate::TransactionLogBuilder::new()
+                .instruction(builder)
+                .build();
+            log.redecode(&config);
+            crate::TransactionFormatter::new(&config).format(&log, 1)
+        };
+        let output = format(crate::EnhancedLoggingConfig::debug());
+        assert!(output.contains("has_proof: true"));
+        assert!(!output.contains(&proof_hex));
+        let output = format(crate::EnhancedLoggingConfig::debug().with_proof_bytes());
+        assert!(output.contains(&proof_hex));
+    }
 }
@@ -20,10 +20,29 @@ extern crate self as light_instruction_decoder;
 use light_instruction_decoder_derive::InstructionDecoder;
 use solana_instruction::AccountMeta;
 
-use crate::programs::light_types::{
-    Action, CompressedTokenInstructionDataTransfer2, MintActionCompressedInstructionData,
+use crate::{
+    format_packed_pubkeys,
+    programs::light_system::write_proof,
+    programs::light_types::{
+        Action, CompressedTokenInstructionDataTransfer2, ExtensionInstructionData,
+        MintActionCompressedInstructionData,
+    },
+    DecodedField,
 };
 
+/// Render the account at `position` as `#N (ABCD…WXYZ)`, where `N` is its
+/// 1-based number in the instruction's account table.
+#[cfg(not(target_os = "solana"))]
+fn account_at(accounts: &[AccountMeta], position: usize) -> Option<String> {
+    let pubkey = accounts.get(position)?.pubkey.to_string();
+    Some(format!(
+        "#{} ({}…{})",
+        position + 1,
+        &pubkey[..4],
+        &pubkey[pubkey.len() - 4..]
+    ))
+}
+
 /// Calculate the packed accounts start position for Transfer2.
 ///
 /// The start position depends on the instruction path and optional accounts:
@@ -86,6 +105,112 @@ fn calculate_packed_accounts_start(data: &CompressedTokenInstructionDataTransfer
     }
 }
 
+/// Position of the CPI context account in a Transfer2 instruction, if it has one.
+///
+/// It is `cpi_context` in the CPI context write path and the optional
+/// `cpi_context_account` right before the packed accounts in the full transfer path.
+#[cfg(not(target_os = "solana"))]
+fn transfer2_cpi_context_account_position(
+    data: &CompressedTokenInstructionDataTransfer2,
+) -> Option<usize> {
+    let ctx = data.cpi_context.as_ref()?;
+    if data.in_token_data.is_empty() && data.out_token_data.is_empty() {
+        // Compressions-only path has no CPI context account
+        None
+    } else if ctx.set_context || ctx.first_set_context {
+        Some(3)
+    } else {
+        Some(calculate_packed_accounts_start(data) - 1)
+    }
+}
+
+/// Write decoded TLV extensions (per input/output token account) at the given indent.
+#[cfg(not(target_os = "solana"))]
+fn write_extensions(output: &mut String, extensions: &[ExtensionInstructionData], indent: &str) {
+    use std::fmt::Write;
+
+    let _ = writeln!(output, "{}extensions ({}):", indent, extensions.len());
+    for (i, extension) in extensions.iter().enumerate() {
+        match extension {
+            ExtensionInstructionData::TokenMetadata(metadata) => {
+                let _ = writeln!(output, "{}  [{}] TokenMetadata:", indent, i);
+                let _ = writeln!(
+                    output,
+                    "{}    name: {:?}",
+                    indent,
+                    String::from_utf8_lossy(&metadata.name)
+                );
+                let _ = writeln!(
+                    output,
+                    "{}    symbol: {:?}",
+                    indent,
+                    String::from_utf8_lossy(&metadata.symbol)
+                );
+                let _ = writeln!(
+                    output,
+                    "{}    uri: {:?}",
+                    indent,
+                    String::from_utf8_lossy(&metadata.uri)
+                );
+                if let Some(authority) = &metadata.update_authority {
+                    let _ = writeln!(
+                        output,
+                        "{}    update_authority: {}",
+                        indent,
+                        bs58::encode(authority).into_string()
+                    );
+                }
+                let _ = writeln!(
+                    output,
+                    "{}    additional_metadata: {}",
+                    indent,
+                    metadata.additional_metadata.as_ref().map_or(0, Vec::len)
+                );
+            }
+            ExtensionInstructionData::Compressible(info) => {
+                let rent = &info.rent_config;
+                let _ = writeln!(output, "{}  [{}] Compressible:", indent, i);
+                let _ = writeln!(
+                    output,
+                    "{}    rent_sponsor: {}",
+                    indent,
+                    bs58::encode(&info.rent_sponsor).into_string()
+                );
+                let _ = writeln!(
+                    output,
+                    "{}    lamports_per_write: {}",
+                    indent, info.lamports_per_write
+                );
+                let _ = writeln!(
+                    output,
+                    "{}    rent_config: base_rent={} compression_cost={} lamports_per_byte_per_epoch={} max_funded_epochs={} max_top_up={}",
+                    indent,
+                    rent.base_rent,
+                    rent.compression_cost,
+                    rent.lamports_per_byte_per_epoch,
+                    rent.max_funded_epochs,
+                    rent.max_top_up
+                );
+            }
+            ExtensionInstructionData::CompressedOnly(data) => {
+                let _ = writeln!(
+                    output,
+                    "{}  [{}] CompressedOnly: delegated_amount={} withheld_transfer_fee={} is_frozen={} is_ata={}",
+                    indent,
+                    i,
+                    data.delegated_amount,
+                    data.withheld_transfer_fee,
+                    data.is_frozen,
+                    data.is_ata
+                );
+            }
+            other => {
+                let _ = writeln!(output, "{}  [{}] {:?}", indent, i, other);
+            }
+        }
+    }
+}
+
 /// Format Transfer2 instruction data with resolved pubkeys.
 ///
 /// This formatter provides a human-readable view of the transfer instruction,
@@ -118,9 +243,7 @@ pub fn format_transfer2(
             // All accounts are in CPI context
             format!("packed[{}]", index)
         } else {
-            accounts
-                .get(packed_accounts_start + index as usize)
-                .map(|a| a.pubkey.to_string())
+            account_at(accounts, packed_accounts_start + index as usize)
                 .unwrap_or_else(|| format!("OUT_OF_BOUNDS({})", index))
         }
     };
@@ -141,6 +264,7 @@ pub fn format_transfer2(
     if data.with_transaction_hash {
         let _ = writeln!(output, "with_transaction_hash: true");
     }
+    write_proof(&mut output, &data.proof);
 
     // Input tokens
     let _ = writeln!(output, "Input Tokens ({}):", data.in_token_data.len());
@@ -170,6 +294,11 @@ pub fn format_transfer2(
             token.merkle_context.leaf_index
         );
         let _ = writeln!(output, "    root_index: {}", token.root_index);
+        if let Some(extensions) = data.in_tlv.as_ref().and_then(|tlv| tlv.get(i)) {
+            if !extensions.is_empty() {
+                write_extensions(&mut output, extensions, "    ");
+            }
+        }
     }
 
     // Output tokens
@@ -183,6 +312,11 @@ pub fn format_transfer2(
             let _ = writeln!(output, "    delegate: {}", resolve(token.delegate));
         }
         let _ = writeln!(output, "    version: {}", token.version);
+        if let Some(extensions) = data.out_tlv.as_ref().and_then(|tlv| tlv.get(i)) {
+            if !extensions.is_empty() {
+                write_extensions(&mut output, extensions, "    ");
+            }
+        }
     }
 
     // Compressions if present
@@ -199,9 +333,32 @@ pub fn format_transfer2(
                 resolve(comp.source_or_recipient)
             );
             let _ = writeln!(output, "    authority: {}", resolve(comp.authority));
+            // SPL token compressions go through a token pool PDA; Light Token
+            // compressions leave the pool fields zeroed
+            if comp.bump != 0 {
+                let _ = writeln!(
+                    output,
+                    "    pool_account: {} (pool_index: {}, bump: {})",
+                    resolve(comp.pool_account_index),
+                    comp.pool_index,
+                    comp.bump
+                );
+                let _ = writeln!(output, "    decimals: {}", comp.decimals);
+            }
         }
     }
 
+    // CPI context flags and account (if present)
+    if let Some(ctx) = &data.cpi_context {
+        let account = transfer2_cpi_context_account_position(data)
+            .and_then(|position| account_at(accounts, position))
+            .unwrap_or_else(|| "None".to_string());
+        let _ = writeln!(output, "CPI Context:");
+        let _ = writeln!(output, "  set_context: {}", ctx.set_context);
+        let _ = writeln!(output, "  first_set_context: {}", ctx.first_set_context);
+        let _ = writeln!(output, "  account: {}", account);
+    }
+
     output
 }
 
@@ -698,18 +855,28 @@ fn calculate_mint_action_packed_accounts_start(
 ///
 /// This formatter provides a human-readable view of the mint action instruction,
 /// resolving account indices to actual pubkeys from the instruction accounts.
+/// The text is a plain rendering of [`format_mint_action_fields`].
+#[cfg(not(target_os = "solana"))]
+pub fn format_mint_action(
+    data: &MintActionCompressedInstructionData,
+    accounts: &[AccountMeta],
+) -> String {
+    let mut output = String::new();
+    write_fields(&mut output, &format_mint_action_fields(data, accounts), 0);
+    output
+}
+
+/// Decode MintAction instruction data into structured fields, one nested entry per action.
 ///
 /// Mode detection:
 /// - CPI context write mode (cpi_context.set_context || first_set_context): Shows raw indices
 /// - Direct mode: Resolves packed account indices using dynamically calculated start position
 #[cfg(not(target_os = "solana"))]
-pub fn format_mint_action(
+pub fn format_mint_action_fields(
     data: &MintActionCompressedInstructionData,
     accounts: &[AccountMeta],
-) -> String {
-    use std::fmt::Write;
-
-    let mut output = String::new();
+) -> Vec<DecodedField> {
+    let mut fields = Vec::new();
 
     // CPI context write mode: set_context OR first_set_context means packed accounts in CPI context
     let cpi_context_write_mode = data
@@ -726,186 +893,276 @@ pub fn format_mint_action(
         if cpi_context_write_mode {
             format!("packed[{}]", index)
         } else {
-            accounts
-                .get(packed_accounts_start + index as usize)
-                .map(|a| a.pubkey.to_string())
+            account_at(accounts, packed_accounts_start + index as usize)
                 .unwrap_or_else(|| format!("OUT_OF_BOUNDS({})", index))
         }
     };
+    // Same for arrays of tree indices
+    let resolve_trees = |indices: &[u8]| -> String {
+        if cpi_context_write_mode {
+            let packed: Vec<String> = indices.iter().map(|i| format!("packed[{}]", i)).collect();
+            format!("[{}]", packed.join(", "))
+        } else {
+            format_packed_pubkeys(indices, accounts, packed_accounts_start)
+        }
+    };
 
     // Header with mode indicator
     if cpi_context_write_mode {
-        let _ = writeln!(
-            output,
-            "[CPI Context Write Mode - packed accounts in CPI context]"
-        );
+        fields.push(DecodedField::new(
+            "",
+            "[CPI Context Write Mode - packed accounts in CPI context]",
+        ));
     }
 
     // Top-level fields
-    if data.create_mint.is_some() {
-        let _ = writeln!(output, "create_mint: true");
+    if let Some(create_mint) = &data.create_mint {
+        fields.push(DecodedField::new("create_mint", "true"));
+        fields.push(DecodedField::new(
+            "read_only_address_trees",
+            resolve_trees(&create_mint.read_only_address_trees),
+        ));
     } else {
-        let _ = writeln!(output, "leaf_index: {}", data.leaf_index);
+        fields.push(DecodedField::new("leaf_index", data.leaf_index.to_string()));
         if data.prove_by_index {
-            let _ = writeln!(output, "prove_by_index: true");
+            fields.push(DecodedField::new("prove_by_index", "true"));
         }
     }
-    let _ = writeln!(output, "root_index: {}", data.root_index);
+    fields.push(DecodedField::new("root_index", data.root_index.to_string()));
     if data.max_top_up > 0 {
-        let _ = writeln!(output, "max_top_up: {}", data.max_top_up);
+        fields.push(DecodedField::new("max_top_up", data.max_top_up.to_string()));
+    }
+    fields.push(DecodedField::new(
+        "has_proof",
+        data.proof.is_some().to_string(),
+    ));
+    if let Some(proof) = &data.proof {
+        fields.push(DecodedField::new("proof", proof.to_hex()));
     }
 
     // Mint data summary (if present)
     if let Some(mint) = &data.mint {
-        let _ = writeln!(output, "Mint:");
-        let _ = writeln!(output, "  supply: {}", mint.supply);
-        let _ = writeln!(output, "  decimals: {}", mint.decimals);
+        let mut mint_fields = vec![
+            DecodedField::new("supply", mint.supply.to_string()),
+            DecodedField::new("decimals", mint.decimals.to_string()),
+        ];
         if let Some(auth) = &mint.mint_authority {
-            let _ = writeln!(
-                output,
-                "  mint_authority: {}",
-                bs58::encode(auth).into_string()
-            );
+            mint_fields.push(DecodedField::new(
+                "mint_authority",
+                bs58::encode(auth).into_string(),
+            ));
         }
         if let Some(auth) = &mint.freeze_authority {
-            let _ = writeln!(
-                output,
-                "  freeze_authority: {}",
-                bs58::encode(auth).into_string()
-            );
+            mint_fields.push(DecodedField::new(
+                "freeze_authority",
+                bs58::encode(auth).into_string(),
+            ));
         }
         if let Some(exts) = &mint.extensions {
-            let _ = writeln!(output, "  extensions: {}", exts.len());
+            mint_fields.push(DecodedField::new("extensions", exts.len().to_string()));
         }
+        fields.push(DecodedField::with_children("Mint", mint_fields));
     }
 
     // Actions
-    let _ = writeln!(output, "Actions ({}):", data.actions.len());
-    for (i, action) in data.actions.iter().enumerate() {
-        match action {
-            Action::MintToCompressed(a) => {
-                let _ = writeln!(output, "  [{}] MintToCompressed:", i);
-                let _ = writeln!(output, "    version: {}", a.token_account_version);
-                for (j, r) in a.recipients.iter().enumerate() {
-                    let _ = writeln!(
-                        output,
-                        "    recipient[{}]: {} amount: {}",
-                        j,
-                        bs58::encode(&r.recipient).into_string(),
-                        r.amount
-                    );
-                }
-            }
-            Action::UpdateMintAuthority(a) => {
-                let authority_str = a
-                    .new_authority
-                    .as_ref()
-                    .map(|p| bs58::encode(p).into_string())
-                    .unwrap_or_else(|| "None".to_string());
-                let _ = writeln!(output, "  [{}] UpdateMintAuthority: {}", i, authority_str);
-            }
-            Action::UpdateFreezeAuthority(a) => {
-                let authority_str = a
-                    .new_authority
-                    .as_ref()
-                    .map(|p| bs58::encode(p).into_string())
-                    .unwrap_or_else(|| "None".to_string());
-                let _ = writeln!(output, "  [{}] UpdateFreezeAuthority: {}", i, authority_str);
-            }
-            Action::MintTo(a) => {
-                let _ = writeln!(
-                    output,
-                    "  [{}] MintTo: account: {}, amount: {}",
-                    i,
-                    resolve(a.account_index),
-                    a.amount
-                );
-            }
-            Action::UpdateMetadataField(a) => {
-                let field_name = match a.field_type {
-                    0 => "Name",
-                    1 => "Symbol",
-                    2 => "Uri",
-                    _ => "Custom",
-                };
-                let _ = writeln!(
-                    output,
-                    "  [{}] UpdateMetadataField: ext[{}] {} = {:?}",
-                    i,
-                    a.extension_index,
-                    field_name,
-                    String::from_utf8_lossy(&a.value)
-                );
-            }
-            Action::UpdateMetadataAuthority(a) => {
-                let _ = writeln!(
-                    output,
-                    "  [{}] UpdateMetadataAuthority: ext[{}] = {}",
-                    i,
-                    a.extension_index,
-                    bs58::encode(&a.new_authority).into_string()
-                );
-            }
-            Action::RemoveMetadataKey(a) => {
-                let _ = writeln!(
-                    output,
-                    "  [{}] RemoveMetadataKey: ext[{}] key={:?} idempotent={}",
-                    i,
-                    a.extension_index,
-                    String::from_utf8_lossy(&a.key),
-                    a.idempotent != 0
-                );
-            }
-            Action::DecompressMint(a) => {
-                let _ = writeln!(
-                    output,
-                    "  [{}] DecompressMint: rent_payment={} write_top_up={}",
-                    i, a.rent_payment, a.write_top_up
-                );
-            }
-            Action::CompressAndCloseMint(a) => {
-                let _ = writeln!(
-                    output,
-                    "  [{}] CompressAndCloseMint: idempotent={}",
-                    i,
-                    a.idempotent != 0
-                );
-            }
-        }
+    let actions: Vec<DecodedField> = data
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| mint_action_entry(i, action, &resolve))
+        .collect();
+    if actions.is_empty() {
+        fields.push(DecodedField::new("Actions (0)", "none"));
+    } else {
+        fields.push(DecodedField::with_children(
+            format!("Actions ({})", actions.len()),
+            actions,
+        ));
     }
 
     // CPI context details (if present)
     if let Some(ctx) = &data.cpi_context {
-        let _ = writeln!(output, "CPI Context:");
-        let _ = writeln!(
-            output,
-            "  mode: {}",
-            if ctx.first_set_context {
-                "first_set_context"
-            } else if ctx.set_context {
-                "set_context"
-            } else {
-                "read"
-            }
-        );
-        let _ = writeln!(output, "  in_tree: packed[{}]", ctx.in_tree_index);
-        let _ = writeln!(output, "  in_queue: packed[{}]", ctx.in_queue_index);
-        let _ = writeln!(output, "  out_queue: packed[{}]", ctx.out_queue_index);
+        let mode = if ctx.first_set_context {
+            "first_set_context"
+        } else if ctx.set_context {
+            "set_context"
+        } else {
+            "read"
+        };
+        // The context account is `cpi_context` when writing, else the optional
+        // account right after the LightSystemAccounts
+        let names = resolve_mint_action_account_names(data, accounts);
+        let position = if cpi_context_write_mode {
+            names.iter().position(|name| name == "cpi_context")
+        } else {
+            Some(names.len())
+        };
+        let account = position
+            .and_then(|position| account_at(accounts, position))
+            .unwrap_or_else(|| "None".to_string());
+        let mut ctx_fields = vec![
+            DecodedField::new("mode", mode),
+            DecodedField::new("account", account),
+            DecodedField::new("in_tree", format!("packed[{}]", ctx.in_tree_index)),
+            DecodedField::new("in_queue", format!("packed[{}]", ctx.in_queue_index)),
+            DecodedField::new("out_queue", format!("packed[{}]", ctx.out_queue_index)),
+        ];
         if ctx.token_out_queue_index > 0 {
-            let _ = writeln!(
-                output,
-                "  token_out_queue: packed[{}]",
-                ctx.token_out_queue_index
-            );
+            ctx_fields.push(DecodedField::new(
+                "token_out_queue",
+                format!("packed[{}]", ctx.token_out_queue_index),
+            ));
         }
-        let _ = writeln!(
-            output,
-            "  address_tree: {}",
-            bs58::encode(&ctx.address_tree_pubkey).into_string()
-        );
+        ctx_fields.push(DecodedField::new(
+            "read_only_address_trees",
+            resolve_trees(&ctx.read_only_address_trees),
+        ));
+        ctx_fields.push(DecodedField::new(
+            "address_tree",
+            bs58::encode(&ctx.address_tree_pubkey).into_string(),
+        ));
+        fields.push(DecodedField::with_children("CPI Context", ctx_fields));
     }
 
-    output
+    fields
+}
+
+/// Token metadata key or value as a quoted string, or `0x` hex when it isn't UTF-8.
+#[cfg(not(target_os = "solana"))]
+fn metadata_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => format!("{:?}", text),
+        Err(_) => format!("0x{}", crate::core::hex(bytes)),
+    }
+}
+
+/// Nested field group for a single MintAction action, named `[index] Variant`.
+#[cfg(not(target_os = "solana"))]
+fn mint_action_entry(
+    index: usize,
+    action: &Action,
+    resolve: &dyn Fn(u8) -> String,
+) -> DecodedField {
+    let authority = |authority: &Option<[u8; 32]>| {
+        authority
+            .as_ref()
+            .map(|p| bs58::encode(p).into_string())
+            .unwrap_or_else(|| "None".to_string())
+    };
+    let (name, children) = match action {
+        Action::MintToCompressed(a) => {
+            let mut children = vec![DecodedField::new(
+                "version",
+                a.token_account_version.to_string(),
+            )];
+            children.extend(a.recipients.iter().enumerate().map(|(j, r)| {
+                DecodedField::with_children(
+                    format!("recipient[{}]", j),
+                    vec![
+                        DecodedField::new("recipient", bs58::encode(&r.recipient).into_string()),
+                        DecodedField::new("amount", r.amount.to_string()),
+                    ],
+                )
+            }));
+            if a.recipients.is_empty() {
+                children.push(DecodedField::new("recipients", "none"));
+            }
+            // Summed as u128: amounts for different recipients may add up past u64::MAX
+            let total: u128 = a.recipients.iter().map(|r| r.amount as u128).sum();
+            children.push(DecodedField::new("total_minted", total.to_string()));
+            ("MintToCompressed", children)
+        }
+        Action::UpdateMintAuthority(a) => (
+            "UpdateMintAuthority",
+            vec![DecodedField::new(
+                "new_authority",
+                authority(&a.new_authority),
+            )],
+        ),
+        Action::UpdateFreezeAuthority(a) => (
+            "UpdateFreezeAuthority",
+            vec![DecodedField::new(
+                "new_authority",
+                authority(&a.new_authority),
+            )],
+        ),
+        Action::MintTo(a) => (
+            "MintTo",
+            vec![
+                DecodedField::new("account", resolve(a.account_index)),
+                DecodedField::new("amount", a.amount.to_string()),
+            ],
+        ),
+        Action::UpdateMetadataField(a) => {
+            let field_name = match a.field_type {
+                0 => "Name".to_string(),
+                1 => "Symbol".to_string(),
+                2 => "Uri".to_string(),
+                3 => "Custom".to_string(),
+                other => format!("Unknown ({})", other),
+            };
+            let mut children = vec![
+                DecodedField::new("extension_index", a.extension_index.to_string()),
+                DecodedField::new("field", field_name),
+            ];
+            if !a.key.is_empty() {
+                children.push(DecodedField::new("key", metadata_bytes(&a.key)));
+            }
+            children.push(DecodedField::new("value", metadata_bytes(&a.value)));
+            ("UpdateMetadataField", children)
+        }
+        Action::UpdateMetadataAuthority(a) => (
+            "UpdateMetadataAuthority",
+            vec![
+                DecodedField::new("extension_index", a.extension_index.to_string()),
+                DecodedField::new(
+                    "new_authority",
+                    bs58::encode(&a.new_authority).into_string(),
+                ),
+            ],
+        ),
+        Action::RemoveMetadataKey(a) => (
+            "RemoveMetadataKey",
+            vec![
+                DecodedField::new("extension_index", a.extension_index.to_string()),
+                DecodedField::new("key", metadata_bytes(&a.key)),
+                DecodedField::new("idempotent", (a.idempotent != 0).to_string()),
+            ],
+        ),
+        Action::DecompressMint(a) => (
+            "DecompressMint",
+            vec![
+                DecodedField::new("rent_payment", a.rent_payment.to_string()),
+                DecodedField::new("write_top_up", a.write_top_up.to_string()),
+            ],
+        ),
+        Action::CompressAndCloseMint(a) => (
+            "CompressAndCloseMint",
+            vec![DecodedField::new(
+                "idempotent",
+                (a.idempotent != 0).to_string(),
+            )],
+        ),
+    };
+    DecodedField::with_children(format!("[{}] {}", index, name), children)
+}
+
+/// Render fields as indented `name: value` lines (two spaces per nesting level).
+#[cfg(not(target_os = "solana"))]
+fn write_fields(output: &mut String, fields: &[DecodedField], depth: usize) {
+    use std::fmt::Write;
+
+    let indent = "  ".repeat(depth);
+    for field in fields {
+        if !field.children.is_empty() {
+            let _ = writeln!(output, "{}{}:", indent, field.name);
+            write_fields(output, &field.children, depth + 1);
+        } else if field.name.is_empty() {
+            let _ = writeln!(output, "{}{}", indent, field.value);
+        } else {
+            let _ = writeln!(output, "{}{}: {}", indent, field.name, field.value);
+        }
+    }
 }
 
 /// Compressed Token (CToken) program instructions.
@@ -1015,7 +1272,7 @@ pub enum CTokenInstruction {
     #[instruction_decoder(
         params = MintActionCompressedInstructionData,
         account_names_resolver_from_params = crate::programs::light_token::resolve_mint_action_account_names,
-        pretty_formatter = crate::programs::light_token::format_mint_action
+        fields_formatter = crate::programs::light_token::format_mint_action_fields
     )]
     MintAction,
 
@@ -1029,3 +1286,465 @@ pub enum CTokenInstruction {
     #[instruction_decoder(account_names = ["authority", "rent_recipient", "config", "destination"])]
     WithdrawFundingPool,
 }
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshDeserialize;
+
+    use super::*;
+
+    fn push_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+        buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        buf.extend_from_slice(bytes);
+    }
+
+    /// Borsh fixture: one input token with a TokenMetadata extension and one
+    /// output token with a Compressible extension.
+    fn transfer2_with_tlv_fixture() -> Vec<u8> {
+        let mut buf = vec![
+            0, 0, // with_transaction_hash, with_lamports_change_account_merkle_tree_index
+            0, 0, // lamports_change merkle tree index, lamports_change owner index
+            0, // output_queue
+            0, 0, // max_top_up
+            0, 0, 0, // cpi_context, compressions, proof: None
+        ];
+        // in_token_data: [{ owner: 1, amount: 100, no delegate, mint: 2, version: 3, merkle_context, root_index: 7 }]
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.push(1);
+        buf.extend_from_slice(&100u64.to_le_bytes());
+        buf.extend_from_slice(&[0, 0, 2, 3]);
+        buf.extend_from_slice(&[4, 5]);
+        buf.extend_from_slice(&9u32.to_le_bytes());
+        buf.push(0);
+        buf.extend_from_slice(&7u16.to_le_bytes());
+        // out_token_data: [{ owner: 1, amount: 100, no delegate, mint: 2, version: 3 }]
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.push(1);
+        buf.extend_from_slice(&100u64.to_le_bytes());
+        buf.extend_from_slice(&[0, 0, 2, 3]);
+        // in_lamports, out_lamports: None
+        buf.extend_from_slice(&[0, 0]);
+        // in_tlv: Some([[TokenMetadata]])
+        buf.push(1);
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.push(19);
+        buf.push(1);
+        buf.extend_from_slice(&[8; 32]);
+        push_bytes(&mut buf, b"Light Token");
+        push_bytes(&mut buf, b"LT");
+        push_bytes(&mut buf, b"https://example.com/lt.json");
+        buf.push(1);
+        buf.extend_from_slice(&2u32.to_le_bytes());
+        for (key, value) in [(b"k1", b"v1"), (b"k2", b"v2")] {
+            push_bytes(&mut buf, key);
+            push_bytes(&mut buf, value);
+        }
+        // out_tlv: Some([[Compressible]])
+        buf.push(1);
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.push(32);
+        buf.extend_from_slice(&1u16.to_le_bytes()); // config_account_version
+        buf.extend_from_slice(&[0, 1]); // compress_to_pubkey, account_version
+        buf.extend_from_slice(&766u32.to_le_bytes()); // lamports_per_write
+        buf.extend_from_slice(&[9; 32]); // compression_authority
+        buf.extend_from_slice(&[10; 32]); // rent_sponsor
+        buf.extend_from_slice(&0u64.to_le_bytes()); // last_claimed_slot
+        buf.extend_from_slice(&0u32.to_le_bytes()); // rent_exemption_paid
+        buf.extend_from_slice(&0u32.to_le_bytes()); // _reserved
+        buf.extend_from_slice(&128u16.to_le_bytes()); // base_rent
+        buf.extend_from_slice(&11u16.to_le_bytes()); // compression_cost
+        buf.extend_from_slice(&[1, 2]); // lamports_per_byte_per_epoch, max_funded_epochs
+        buf.extend_from_slice(&4096u16.to_le_bytes()); // max_top_up
+        buf
+    }
+
+    #[test]
+    fn test_format_transfer2_decodes_tlv_extensions() {
+        let data =
+            CompressedTokenInstructionDataTransfer2::try_from_slice(&transfer2_with_tlv_fixture())
+                .unwrap();
+        let output = format_transfer2(&data, &[]);
+
+        assert!(output.contains("    extensions (1):\n      [0] TokenMetadata:\n"));
+        assert!(output.contains("        name: \"Light Token\"\n"));
+        assert!(output.contains("        symbol: \"LT\"\n"));
+        assert!(output.contains("        uri: \"https://example.com/lt.json\"\n"));
+        assert!(output.contains(&format!(
+            "        update_authority: {}\n",
+            bs58::encode([8u8; 32]).into_string()
+        )));
+        assert!(output.contains("        additional_metadata: 2\n"));
+        assert!(output.contains("      [0] Compressible:\n"));
+        assert!(output.contains("        lamports_per_write: 766\n"));
+        assert!(output.contains(
+            "        rent_config: base_rent=128 compression_cost=11 lamports_per_byte_per_epoch=1 max_funded_epochs=2 max_top_up=4096\n"
+        ));
+    }
+
+    #[test]
+    fn test_mint_action_decodes_actions_as_nested_fields() {
+        let mut data = vec![103];
+        // leaf_index, prove_by_index, root_index, max_top_up, create_mint: None
+        data.extend(borsh::to_vec(&(3u32, false, 5u16, 0u16, 0u8)).unwrap());
+        // actions: [MintToCompressed { version 1, two recipients }, UpdateMetadataField { Name }]
+        data.extend(borsh::to_vec(&2u32).unwrap());
+        data.extend(
+            borsh::to_vec(&(0u8, 1u8, vec![([1u8; 32], 100u64), ([2u8; 32], 200u64)])).unwrap(),
+        );
+        data.extend(
+            borsh::to_vec(&(4u8, 0u8, 0u8, Vec::<u8>::new(), b"My Token".to_vec())).unwrap(),
+        );
+        // proof, cpi_context, mint: None
+        data.extend([0, 0, 0]);
+
+        let decoded =
+            crate::InstructionDecoder::decode(&CTokenInstructionDecoder, &data, &[]).unwrap();
+        assert_eq!(decoded.name, "MintAction");
+        let actions = decoded
+            .fields
+            .iter()
+            .find(|f| f.name == "Actions (2)")
+            .unwrap();
+
+        let mint_to = &actions.children[0];
+        assert_eq!(mint_to.name, "[0] MintToCompressed");
+        assert_eq!(mint_to.children[0].value, "1");
+        let recipient = &mint_to.children[2];
+        assert_eq!(recipient.name, "recipient[1]");
+        assert_eq!(
+            recipient.children[0].value,
+            bs58::encode([2u8; 32]).into_string()
+        );
+        assert_eq!(recipient.children[1].name, "amount");
+        assert_eq!(recipient.children[1].value, "200");
+
+        let metadata = &actions.children[1];
+        assert_eq!(metadata.name, "[1] UpdateMetadataField");
+        let values: Vec<(&str, &str)> = metadata
+            .children
+            .iter()
+            .map(|f| (f.name.as_str(), f.value.as_str()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("extension_index", "0"),
+                ("field", "Name"),
+                ("value", "\"My Token\""),
+            ]
+        );
+
+        let text = format_mint_action(
+            &MintActionCompressedInstructionData::try_from_slice(&data[1..]).unwrap(),
+            &[],
+        );
+        assert!(text.contains("Actions (2):\n  [0] MintToCompressed:\n    version: 1\n"));
+        assert!(
+            text.contains("  [1] UpdateMetadataField:\n    extension_index: 0\n    field: Name\n")
+        );
+    }
+
+    #[test]
+    fn test_mint_action_proof_presence() {
+        // leaf_index, prove_by_index, root_index, max_top_up, create_mint: None, no actions
+        let mut data = vec![103];
+        data.extend(borsh::to_vec(&(3u32, false, 5u16, 0u16, 0u8, 0u32)).unwrap());
+        // proof: None or Some(a, b, c), then cpi_context, mint: None
+        let without_proof = [data.clone(), vec![0, 0, 0]].concat();
+        let proof = [vec![1u8], vec![7; 32], vec![8; 64], vec![9; 32]].concat();
+        let with_proof = [data, proof, vec![0, 0]].concat();
+        let program_id = crate::InstructionDecoder::program_id(&CTokenInstructionDecoder);
+
+        let fields = |data: &[u8]| {
+            let mut ix = crate::InstructionLogBuilder::new(program_id)
+                .data(data)
+                .build();
+            ix.decode(&crate::EnhancedLoggingConfig::default());
+            ix.decoded_instruction
+                .unwrap()
+                .fields
+                .into_iter()
+                .filter(|f| f.name.contains("proof"))
+                .map(|f| (f.name, f.value))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            fields(&without_proof),
+            [("has_proof".to_string(), "false".to_string())]
+        );
+        // The proof stays in the decoded instruction; formatter and snapshots hide it
+        let proof_hex = format!(
+            "0x{}{}{}",
+            "07".repeat(32),
+            "08".repeat(64),
+            "09".repeat(32)
+        );
+        assert_eq!(
+            fields(&with_proof),
+            [
+                ("has_proof".to_string(), "true".to_string()),
+                ("proof".to_string(), proof_hex)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_metadata_field_renders_key_and_value() {
+        // MintAction { leaf_index 3, root_index 5, actions: [UpdateMetadataField {
+        //   extension_index 0, field_type 3, key "description", value "Test token" }] }
+        let program_id = crate::InstructionDecoder::program_id(&CTokenInstructionDecoder);
+        let decoded = crate::decode_fixture(
+            &program_id,
+            "67 03000000 00 0500 0000 00 01000000
+             04 00 03 0b000000 6465736372697074696f6e 0a000000 5465737420746f6b656e
+             00 00 00",
+            0,
+        )
+        .unwrap();
+        let action = &decoded
+            .fields
+            .iter()
+            .find(|f| f.name == "Actions (1)")
+            .unwrap()
+            .children[0];
+        assert_eq!(action.name, "[0] UpdateMetadataField");
+        let values: Vec<(&str, &str)> = action
+            .children
+            .iter()
+            .map(|f| (f.name.as_str(), f.value.as_str()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("extension_index", "0"),
+                ("field", "Custom"),
+                ("key", "\"description\""),
+                ("value", "\"Test token\""),
+            ]
+        );
+
+        // Non-UTF-8 values fall back to hex
+        assert_eq!(metadata_bytes(&[0xff, 0x00, 0x1a]), "0xff001a");
+    }
+
+    #[test]
+    fn test_mint_action_with_oversized_action_count_fails_gracefully() {
+        // MintAction claiming u32::MAX actions with none following
+        let program_id = crate::InstructionDecoder::program_id(&CTokenInstructionDecoder);
+        let decoded =
+            crate::decode_fixture(&program_id, "67 03000000 00 0500 0000 00 ffffffff", 0).unwrap();
+        assert_eq!(decoded.name, "MintAction");
+        let error = decoded.fields.iter().find(|f| f.is_decode_error()).unwrap();
+        assert_eq!(
+            error.value,
+            "<decode error at offset 15: collection length 4294967295 exceeds the limit of 10240>"
+        );
+    }
+
+    #[test]
+    fn test_mint_to_compressed_totals_recipients() {
+        let mint_to = |recipients: Vec<([u8; 32], u64)>| {
+            let mut data = vec![103];
+            // leaf_index, prove_by_index, root_index, max_top_up, create_mint: None
+            data.extend(borsh::to_vec(&(3u32, false, 5u16, 0u16, 0u8)).unwrap());
+            // actions: [MintToCompressed { version 1, recipients }]
+            data.extend(borsh::to_vec(&1u32).unwrap());
+            data.extend(borsh::to_vec(&(0u8, 1u8, recipients)).unwrap());
+            // proof, cpi_context, mint: None
+            data.extend([0, 0, 0]);
+            let decoded =
+                crate::InstructionDecoder::decode(&CTokenInstructionDecoder, &data, &[]).unwrap();
+            let actions = decoded
+                .fields
+                .into_iter()
+                .find(|f| f.name == "Actions (1)")
+                .unwrap();
+            actions.children.into_iter().next().unwrap()
+        };
+
+        let action = mint_to(vec![
+            ([1u8; 32], 100),
+            ([2u8; 32], 250),
+            ([3u8; 32], u64::MAX),
+        ]);
+        let names: Vec<&str> = action.children.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "version",
+                "recipient[0]",
+                "recipient[1]",
+                "recipient[2]",
+                "total_minted"
+            ]
+        );
+        let recipient = &action.children[3];
+        assert_eq!(
+            recipient.children[0].value,
+            bs58::encode([3u8; 32]).into_string()
+        );
+        assert_eq!(recipient.children[1].value, u64::MAX.to_string());
+        assert_eq!(
+            action.children[4].value,
+            (350 + u64::MAX as u128).to_string()
+        );
+
+        let action = mint_to(Vec::new());
+        let values: Vec<(&str, &str)> = action
+            .children
+            .iter()
+            .map(|f| (f.name.as_str(), f.value.as_str()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("version", "1"),
+                ("recipients", "none"),
+                ("total_minted", "0")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mint_action_resolves_read_only_address_trees() {
+        use solana_pubkey::Pubkey;
+
+        let mut data = vec![103];
+        // leaf_index, prove_by_index, root_index, max_top_up, create_mint: None, no actions
+        data.extend(borsh::to_vec(&(3u32, false, 5u16, 0u16, 0u8, 0u32)).unwrap());
+        // proof: None, cpi_context: Some(read mode, trees at packed[1] and packed[5])
+        data.push(0);
+        data.push(1);
+        data.extend(borsh::to_vec(&(false, false, 0u8, 0u8, 0u8, 0u8, 0u8)).unwrap());
+        data.extend([1, 5, 1, 1]);
+        data.extend([9; 32]);
+        // mint: None
+        data.push(0);
+
+        // 6 LightSystemAccounts + cpi_context_account, then packed accounts
+        let accounts: Vec<AccountMeta> = (0..9)
+            .map(|i| AccountMeta::new_readonly(Pubkey::new_from_array([i; 32]), false))
+            .collect();
+        let decoded =
+            crate::InstructionDecoder::decode(&CTokenInstructionDecoder, &data, &accounts).unwrap();
+        let ctx = decoded
+            .fields
+            .iter()
+            .find(|f| f.name == "CPI Context")
+            .unwrap();
+        let trees = ctx
+            .children
+            .iter()
+            .find(|f| f.name == "read_only_address_trees")
+            .unwrap();
+        let short = |pubkey: Pubkey| {
+            let pubkey = pubkey.to_string();
+            format!("{}…{}", &pubkey[..4], &pubkey[pubkey.len() - 4..])
+        };
+        let tree = short(Pubkey::new_from_array([8; 32]));
+        assert_eq!(
+            trees.value,
+            format!("[{tree}, OUT_OF_BOUNDS(5), {tree}, {tree}]")
+        );
+
+        assert_eq!(format_packed_pubkeys(&[], &accounts, 0), "[]");
+    }
+
+    #[test]
+    fn test_format_transfer2_resolves_account_indices() {
+        // Compressions-only Transfer2: packed accounts start after
+        // [cpi_authority_pda, fee_payer]
+        let mut buf = vec![0, 0, 0, 0, 0, 0, 0, 0];
+        // compressions: Some([Compress 500 { mint: 1, source_or_recipient: 0, authority: 2 }])
+        buf.push(1);
+        buf.extend(borsh::to_vec(&1u32).unwrap());
+        buf.extend(borsh::to_vec(&(0u8, 500u64, 1u8, 0u8, 2u8, 0u8, 0u8, 255u8, 6u8)).unwrap());
+        // proof: None, no input/output tokens, no lamports or TLV
+        buf.push(0);
+        buf.extend(borsh::to_vec(&(0u32, 0u32)).unwrap());
+        buf.extend([0, 0, 0, 0]);
+        let data = CompressedTokenInstructionDataTransfer2::try_from_slice(&buf).unwrap();
+        let accounts: Vec<AccountMeta> = (1..=5)
+            .map(|i| AccountMeta::new(solana_pubkey::Pubkey::new_from_array([i; 32]), false))
+            .collect();
+        let label = |i: usize| {
+            let pubkey = accounts[i].pubkey.to_string();
+            format!(
+                "#{} ({}…{})",
+                i + 1,
+                &pubkey[..4],
+                &pubkey[pubkey.len() - 4..]
+            )
+        };
+
+        let output = format_transfer2(&data, &accounts);
+
+        assert!(output.contains(&format!("    mint: {}\n", label(3))));
+        assert!(output.contains(&format!("    source_or_recipient: {}\n", label(2))));
+        assert!(output.contains(&format!("    authority: {}\n", label(4))));
+        assert!(format_transfer2(&data, &accounts[..3]).contains("    mint: OUT_OF_BOUNDS(1)\n"));
+    }
+
+    #[test]
+    fn test_transfer2_resolves_compression_authority_and_pool() {
+        let mut data = vec![101, 0, 0, 0, 0, 0, 0, 0, 0];
+        // compressions: [Compress 500 { mint: 0, source_or_recipient: 1, authority: 2,
+        // pool_account: 3, pool_index: 1, bump: 254, decimals: 9 }],
+        // then Light Token Compress 7 { mint: 0, source_or_recipient: 1, authority: 2 }
+        data.push(1);
+        data.extend(borsh::to_vec(&2u32).unwrap());
+        data.extend(borsh::to_vec(&(0u8, 500u64, 0u8, 1u8, 2u8, 3u8, 1u8, 254u8, 9u8)).unwrap());
+        data.extend(borsh::to_vec(&(0u8, 7u64, 0u8, 1u8, 2u8, 0u8, 0u8, 0u8, 0u8)).unwrap());
+        // proof: None, no input/output tokens, no lamports or TLV
+        data.push(0);
+        data.extend(borsh::to_vec(&(0u32, 0u32)).unwrap());
+        data.extend([0, 0, 0, 0]);
+        // cpi_authority_pda, fee_payer, then packed accounts
+        let accounts: Vec<AccountMeta> = (1..=6)
+            .map(|i| AccountMeta::new(solana_pubkey::Pubkey::new_from_array([i; 32]), false))
+            .collect();
+
+        let decoded =
+            crate::InstructionDecoder::decode(&CTokenInstructionDecoder, &data, &accounts).unwrap();
+        assert_eq!(decoded.name, "Transfer2");
+        let output = &decoded.fields[0].value;
+
+        let authority = account_at(&accounts, 4).unwrap();
+        assert!(authority.starts_with(&format!("#5 ({}", &accounts[4].pubkey.to_string()[..4])));
+        assert!(output.contains(&format!("    authority: {}\n", authority)));
+        assert!(output.contains(&format!(
+            "    pool_account: {} (pool_index: 1, bump: 254)\n    decimals: 9\n",
+            account_at(&accounts, 5).unwrap()
+        )));
+        // The Light Token compression has no pool
+        assert_eq!(output.matches("pool_account").count(), 1);
+    }
+
+    #[test]
+    fn test_format_transfer2_cpi_context() {
+        // cpi_context: Some { set_context: true, first_set_context: false },
+        // compressions and proof: None
+        let mut buf = vec![0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0];
+        // in_token_data: empty, out_token_data: [{ owner: 1, amount: 100, mint: 2, version: 3 }]
+        buf.extend(borsh::to_vec(&(0u32, 1u32)).unwrap());
+        buf.extend(borsh::to_vec(&(1u8, 100u64, false, 0u8, 2u8, 3u8)).unwrap());
+        // in_lamports, out_lamports, in_tlv, out_tlv: None
+        buf.extend([0, 0, 0, 0]);
+        let data = CompressedTokenInstructionDataTransfer2::try_from_slice(&buf).unwrap();
+        // light_system_program, fee_payer, cpi_authority_pda, cpi_context
+        let accounts: Vec<AccountMeta> = (1..=4)
+            .map(|i| AccountMeta::new(solana_pubkey::Pubkey::new_from_array([i; 32]), false))
+            .collect();
+
+        let output = format_transfer2(&data, &accounts);
+
+        assert!(output.starts_with("[CPI Context Write Mode - packed accounts in CPI context]\n"));
+        let expected = format!(
+            "CPI Context:\n  set_context: true\n  first_set_context: false\n  account: {}\n",
+            account_at(&accounts, 3).unwrap()
+        );
+        assert!(output.ends_with(&expected), "{output}");
+    }
+}
@@ -7,8 +7,83 @@
 //! - `light_compressed_account::pubkey::Pubkey` is replaced with `[u8; 32]`
 //! - Zero-copy derives removed (not needed for decoding)
 //! - Only `BorshDeserialize` and `Debug` derived
+//! - Collection lengths are capped, see [`MAX_COLLECTION_LEN`]
 
-use borsh::BorshDeserialize;
+use borsh::{
+    io::{Error, ErrorKind, Read},
+    BorshDeserialize,
+};
+
+// ============================================================================
+// Bounded Collections
+// ============================================================================
+
+/// Largest collection length prefix the Light types accept: the largest
+/// instruction data a CPI can carry (10 KiB), so no valid collection in it has
+/// more elements
+///
+/// Every `Vec` in these types, including the inner `Vec`s of nested ones, is read
+/// with this limit: a longer length prefix, as found in malformed instruction
+/// data, fails decoding with an `OutOfMemory` error "collection length N exceeds
+/// the limit of M" before any element is read or allocated. Decoders then report
+/// the instruction as partially decoded.
+pub const MAX_COLLECTION_LEN: usize = 10 * 1024;
+
+/// Read a borsh `u32` length prefix, rejecting lengths over [`MAX_COLLECTION_LEN`]
+fn bounded_len<R: Read>(reader: &mut R) -> Result<usize, Error> {
+    let len = u32::deserialize_reader(reader)? as usize;
+    if len > MAX_COLLECTION_LEN {
+        return Err(Error::new(
+            ErrorKind::OutOfMemory,
+            format!(
+                "collection length {} exceeds the limit of {}",
+                len, MAX_COLLECTION_LEN
+            ),
+        ));
+    }
+    Ok(len)
+}
+
+/// Read a borsh `Option` tag, then the value with `read` if present
+fn bounded_option<R: Read, T>(
+    reader: &mut R,
+    read: fn(&mut R) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    match u8::deserialize_reader(reader)? {
+        0 => Ok(None),
+        1 => read(reader).map(Some),
+        flag => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("invalid Option tag: {}", flag),
+        )),
+    }
+}
+
+/// Read a `Vec` like borsh does, rejecting length prefixes over [`MAX_COLLECTION_LEN`]
+fn bounded_vec<R: Read, T: BorshDeserialize>(reader: &mut R) -> Result<Vec<T>, Error> {
+    let len = bounded_len(reader)?;
+    (0..len).map(|_| T::deserialize_reader(reader)).collect()
+}
+
+/// [`bounded_vec`] for a `Vec<Vec<T>>`, bounding the inner `Vec`s as well
+fn bounded_nested_vec<R: Read, T: BorshDeserialize>(reader: &mut R) -> Result<Vec<Vec<T>>, Error> {
+    let len = bounded_len(reader)?;
+    (0..len).map(|_| bounded_vec(reader)).collect()
+}
+
+/// [`bounded_vec`] for an `Option<Vec<T>>`
+fn bounded_option_vec<R: Read, T: BorshDeserialize>(
+    reader: &mut R,
+) -> Result<Option<Vec<T>>, Error> {
+    bounded_option(reader, bounded_vec)
+}
+
+/// [`bounded_nested_vec`] for an `Option<Vec<Vec<T>>>`
+fn bounded_option_nested_vec<R: Read, T: BorshDeserialize>(
+    reader: &mut R,
+) -> Result<Option<Vec<Vec<T>>>, Error> {
+    bounded_option(reader, bounded_nested_vec)
+}
 
 // ============================================================================
 // Core Primitives
@@ -21,6 +96,16 @@ pub struct CompressedProof {
     pub c: [u8; 32],
 }
 
+impl CompressedProof {
+    /// The proof bytes `a`, `b`, `c` concatenated as `0x` hex
+    pub fn to_hex(&self) -> String {
+        format!(
+            "0x{}",
+            crate::core::hex(&[self.a.as_slice(), &self.b, &self.c].concat())
+        )
+    }
+}
+
 impl Default for CompressedProof {
     fn default() -> Self {
         Self {
@@ -53,6 +138,7 @@ pub struct PackedMerkleContext {
 #[derive(BorshDeserialize, Debug, Clone, PartialEq, Default)]
 pub struct CompressedAccountData {
     pub discriminator: [u8; 8],
+    #[borsh(deserialize_with = "bounded_vec")]
     pub data: Vec<u8>,
     pub data_hash: [u8; 32],
 }
@@ -157,6 +243,7 @@ pub struct OutAccountInfo {
     pub data_hash: [u8; 32],
     pub output_merkle_tree_index: u8,
     pub lamports: u64,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub data: Vec<u8>,
 }
 
@@ -174,10 +261,13 @@ pub struct CompressedAccountInfo {
 #[derive(BorshDeserialize, Debug, Clone, PartialEq, Default)]
 pub struct InstructionDataInvoke {
     pub proof: Option<CompressedProof>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub input_compressed_accounts_with_merkle_context:
         Vec<PackedCompressedAccountWithMerkleContext>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub output_compressed_accounts: Vec<OutputCompressedAccountWithPackedContext>,
     pub relay_fee: Option<u64>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub new_address_params: Vec<NewAddressParamsPacked>,
     pub compress_or_decompress_lamports: Option<u64>,
     pub is_compress: bool,
@@ -186,9 +276,12 @@ pub struct InstructionDataInvoke {
 #[derive(BorshDeserialize, Debug, Clone, PartialEq, Default)]
 pub struct InstructionDataInvokeCpi {
     pub proof: Option<CompressedProof>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub new_address_params: Vec<NewAddressParamsPacked>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub input_compressed_accounts_with_merkle_context:
         Vec<PackedCompressedAccountWithMerkleContext>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub output_compressed_accounts: Vec<OutputCompressedAccountWithPackedContext>,
     pub relay_fee: Option<u64>,
     pub compress_or_decompress_lamports: Option<u64>,
@@ -208,10 +301,15 @@ pub struct InstructionDataInvokeCpiWithReadOnly {
     pub with_transaction_hash: bool,
     pub cpi_context: CompressedCpiContext,
     pub proof: Option<CompressedProof>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub new_address_params: Vec<NewAddressParamsAssignedPacked>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub input_compressed_accounts: Vec<InAccount>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub output_compressed_accounts: Vec<OutputCompressedAccountWithPackedContext>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub read_only_addresses: Vec<PackedReadOnlyAddress>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub read_only_accounts: Vec<PackedReadOnlyCompressedAccount>,
 }
 
@@ -227,9 +325,13 @@ pub struct InstructionDataInvokeCpiWithAccountInfo {
     pub with_transaction_hash: bool,
     pub cpi_context: CompressedCpiContext,
     pub proof: Option<CompressedProof>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub new_address_params: Vec<NewAddressParamsAssignedPacked>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub account_infos: Vec<CompressedAccountInfo>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub read_only_addresses: Vec<PackedReadOnlyAddress>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub read_only_accounts: Vec<PackedReadOnlyCompressedAccount>,
 }
 
@@ -293,16 +395,22 @@ pub struct MultiTokenTransferOutputData {
 
 #[derive(BorshDeserialize, Debug, Clone, PartialEq, Eq)]
 pub struct AdditionalMetadata {
+    #[borsh(deserialize_with = "bounded_vec")]
     pub key: Vec<u8>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub value: Vec<u8>,
 }
 
 #[derive(BorshDeserialize, Debug, Clone, PartialEq, Eq)]
 pub struct TokenMetadataInstructionData {
     pub update_authority: Option<[u8; 32]>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub name: Vec<u8>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub symbol: Vec<u8>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub uri: Vec<u8>,
+    #[borsh(deserialize_with = "bounded_option_vec")]
     pub additional_metadata: Option<Vec<AdditionalMetadata>>,
 }
 
@@ -387,13 +495,20 @@ pub struct CompressedTokenInstructionDataTransfer2 {
     pub output_queue: u8,
     pub max_top_up: u16,
     pub cpi_context: Option<Transfer2CpiContext>,
+    #[borsh(deserialize_with = "bounded_option_vec")]
     pub compressions: Option<Vec<Compression>>,
     pub proof: Option<CompressedProof>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub in_token_data: Vec<MultiInputTokenDataWithContext>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub out_token_data: Vec<MultiTokenTransferOutputData>,
+    #[borsh(deserialize_with = "bounded_option_vec")]
     pub in_lamports: Option<Vec<u64>>,
+    #[borsh(deserialize_with = "bounded_option_vec")]
     pub out_lamports: Option<Vec<u64>>,
+    #[borsh(deserialize_with = "bounded_option_nested_vec")]
     pub in_tlv: Option<Vec<Vec<ExtensionInstructionData>>>,
+    #[borsh(deserialize_with = "bounded_option_nested_vec")]
     pub out_tlv: Option<Vec<Vec<ExtensionInstructionData>>>,
 }
 
@@ -431,6 +546,7 @@ pub struct MintInstructionData {
     pub metadata: MintMetadata,
     pub mint_authority: Option<[u8; 32]>,
     pub freeze_authority: Option<[u8; 32]>,
+    #[borsh(deserialize_with = "bounded_option_vec")]
     pub extensions: Option<Vec<ExtensionInstructionData>>,
 }
 
@@ -449,6 +565,7 @@ pub struct Recipient {
 #[derive(BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct MintToCompressedAction {
     pub token_account_version: u8,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub recipients: Vec<Recipient>,
 }
 
@@ -467,7 +584,9 @@ pub struct MintToAction {
 pub struct UpdateMetadataFieldAction {
     pub extension_index: u8,
     pub field_type: u8,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub key: Vec<u8>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub value: Vec<u8>,
 }
 
@@ -480,6 +599,7 @@ pub struct UpdateMetadataAuthorityAction {
 #[derive(BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct RemoveMetadataKeyAction {
     pub extension_index: u8,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub key: Vec<u8>,
     pub idempotent: u8,
 }
@@ -516,8 +636,231 @@ pub struct MintActionCompressedInstructionData {
     pub root_index: u16,
     pub max_top_up: u16,
     pub create_mint: Option<CreateMint>,
+    #[borsh(deserialize_with = "bounded_vec")]
     pub actions: Vec<Action>,
     pub proof: Option<CompressedProof>,
     pub cpi_context: Option<MintActionCpiContext>,
     pub mint: Option<MintInstructionData>,
 }
+
+// ============================================================================
+// Account Compression Types
+// ============================================================================
+
+/// `nullify_leaves` arguments; the vectors are indexed per nullified leaf.
+#[derive(BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub struct NullifyLeavesInstructionData {
+    #[borsh(deserialize_with = "bounded_vec")]
+    pub change_log_indices: Vec<u64>,
+    #[borsh(deserialize_with = "bounded_vec")]
+    pub leaves_queue_indices: Vec<u16>,
+    #[borsh(deserialize_with = "bounded_vec")]
+    pub leaf_indices: Vec<u64>,
+    #[borsh(deserialize_with = "bounded_nested_vec")]
+    pub proofs: Vec<Vec<[u8; 32]>>,
+}
+
+/// `update_address_merkle_tree` arguments.
+#[derive(BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct UpdateAddressMerkleTreeInstructionData {
+    pub changelog_index: u16,
+    pub indexed_changelog_index: u16,
+    /// Index of the address in the queue
+    pub value: u16,
+    pub low_address_index: u64,
+    pub low_address_value: [u8; 32],
+    pub low_address_next_index: u64,
+    pub low_address_next_value: [u8; 32],
+    pub low_address_proof: [[u8; 32]; 16],
+}
+
+/// Proof inputs shared by `batch_nullify`, `batch_append` and `batch_update_address_tree`.
+#[derive(BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InstructionDataBatchProofInputs {
+    pub new_root: [u8; 32],
+    pub compressed_proof: CompressedProof,
+}
+
+/// `migrate_state` arguments.
+#[derive(BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct MigrateLeafParams {
+    pub change_log_index: u64,
+    pub leaf: [u8; 32],
+    pub leaf_index: u64,
+    pub proof: [[u8; 32]; 26],
+}
+
+/// `rollover_batched_state_merkle_tree` arguments.
+#[derive(BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RolloverBatchedStateMerkleTreeInstructionData {
+    pub additional_bytes: u64,
+    pub network_fee: Option<u64>,
+}
+
+/// `rollover_batched_address_merkle_tree` arguments.
+#[derive(BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RolloverBatchedAddressMerkleTreeInstructionData {
+    pub network_fee: Option<u64>,
+}
+
+// ============================================================================
+// Decoding Entry Points
+// ============================================================================
+//
+// Each function decodes the borsh-serialized arguments of one instruction, i.e.
+// the instruction data after its discriminator. All bytes must be consumed.
+
+/// Implement `TryFrom<&[u8]>` as a strict borsh decode
+macro_rules! impl_try_from_slice {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl TryFrom<&[u8]> for $ty {
+                type Error = borsh::io::Error;
+
+                fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+                    Self::try_from_slice(data)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_slice!(
+    InstructionDataInvoke,
+    InstructionDataInvokeCpi,
+    InstructionDataInvokeCpiWithReadOnly,
+    InstructionDataInvokeCpiWithAccountInfo,
+    CompressedTokenInstructionDataTransfer2,
+    MintActionCompressedInstructionData,
+);
+
+/// Decode Light System `Invoke` arguments
+///
+/// `data` starts after the 8-byte discriminator and the 4-byte Anchor length prefix.
+pub fn try_decode_invoke(data: &[u8]) -> Result<InstructionDataInvoke, borsh::io::Error> {
+    InstructionDataInvoke::try_from(data)
+}
+
+/// Decode Light System `InvokeCpi` arguments
+///
+/// `data` starts after the 8-byte discriminator and the 4-byte Anchor length prefix.
+pub fn try_decode_invoke_cpi(data: &[u8]) -> Result<InstructionDataInvokeCpi, borsh::io::Error> {
+    InstructionDataInvokeCpi::try_from(data)
+}
+
+/// Decode Light System `InvokeCpiWithReadOnly` arguments (after the 8-byte discriminator)
+pub fn try_decode_invoke_cpi_with_read_only(
+    data: &[u8],
+) -> Result<InstructionDataInvokeCpiWithReadOnly, borsh::io::Error> {
+    InstructionDataInvokeCpiWithReadOnly::try_from(data)
+}
+
+/// Decode Light System `InvokeCpiWithAccountInfo` arguments (after the 8-byte discriminator)
+pub fn try_decode_invoke_cpi_with_account_info(
+    data: &[u8],
+) -> Result<InstructionDataInvokeCpiWithAccountInfo, borsh::io::Error> {
+    InstructionDataInvokeCpiWithAccountInfo::try_from(data)
+}
+
+/// Decode Light Token `Transfer2` arguments (after the 1-byte discriminator)
+pub fn try_decode_transfer2(
+    data: &[u8],
+) -> Result<CompressedTokenInstructionDataTransfer2, borsh::io::Error> {
+    CompressedTokenInstructionDataTransfer2::try_from(data)
+}
+
+/// Decode Light Token `MintAction` arguments (after the 1-byte discriminator)
+pub fn try_decode_mint_action(
+    data: &[u8],
+) -> Result<MintActionCompressedInstructionData, borsh::io::Error> {
+    MintActionCompressedInstructionData::try_from(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_decode_invoke() {
+        // proof: None, three empty vecs, relay_fee: None, lamports: None, is_compress: false
+        let mut data = vec![0u8];
+        data.extend_from_slice(&[0; 8]);
+        data.push(0);
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            try_decode_invoke(&data).unwrap(),
+            InstructionDataInvoke::default()
+        );
+
+        *data.last_mut().unwrap() = 1;
+        let decoded = InstructionDataInvoke::try_from(data.as_slice()).unwrap();
+        assert!(decoded.is_compress);
+
+        // Truncated and trailing bytes are errors
+        assert!(try_decode_invoke(&data[..data.len() - 1]).is_err());
+        data.push(0);
+        assert!(try_decode_invoke(&data).is_err());
+    }
+
+    #[test]
+    fn test_try_decode_mint_action() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&7u32.to_le_bytes()); // leaf_index
+        data.push(1); // prove_by_index
+        data.extend_from_slice(&3u16.to_le_bytes()); // root_index
+        data.extend_from_slice(&0u16.to_le_bytes()); // max_top_up
+        data.push(0); // create_mint: None
+        data.extend_from_slice(&0u32.to_le_bytes()); // actions: []
+        data.extend_from_slice(&[0, 0, 0]); // proof, cpi_context, mint: None
+        let decoded = try_decode_mint_action(&data).unwrap();
+        assert_eq!(decoded.leaf_index, 7);
+        assert!(decoded.prove_by_index);
+        assert_eq!(decoded.root_index, 3);
+        assert!(decoded.actions.is_empty());
+
+        assert!(try_decode_transfer2(&data).is_err());
+    }
+
+    #[test]
+    fn test_oversized_collection_length_is_rejected() {
+        // proof: None, then an input account count of u32::MAX with no accounts following
+        let mut data = vec![0u8];
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        let err = try_decode_invoke(&data).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "collection length {} exceeds the limit of {}",
+                u32::MAX,
+                MAX_COLLECTION_LEN
+            )
+        );
+
+        // Within the limit, the elements are read as usual
+        let mut data = vec![0u8];
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        assert!(try_decode_invoke(&data)
+            .unwrap_err()
+            .to_string()
+            .contains("Unexpected length of input"));
+
+        // Inner vecs of nested collections are bounded too: one proof claiming u32::MAX nodes
+        let mut data = Vec::new();
+        for _ in 0..3 {
+            data.extend_from_slice(&0u32.to_le_bytes()); // change_log/queue/leaf indices: []
+        }
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        let err = NullifyLeavesInstructionData::try_from_slice(&data).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "collection length {} exceeds the limit of {}",
+                u32::MAX,
+                MAX_COLLECTION_LEN
+            )
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! SPL Memo program instruction decoder.
+//!
+//! The Memo program has a single instruction whose data is the memo text, so
+//! there is no discriminator and the decoder is written by hand.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{DecodedField, DecodedInstruction, InstructionDecoder};
+
+/// SPL Memo v1 program id
+pub const MEMO_V1_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+/// SPL Memo v2 program id
+pub const MEMO_V2_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Decoder for the SPL Memo program, v2 by default.
+///
+/// Every instruction decodes as `Memo` with a `text` field (invalid UTF-8 is
+/// replaced with `U+FFFD`). All accounts are signers the memo is attributed to,
+/// so each one is named `signer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoInstructionDecoder {
+    program_id: Pubkey,
+}
+
+impl MemoInstructionDecoder {
+    /// Decoder for the legacy Memo v1 program
+    pub const fn v1() -> Self {
+        Self {
+            program_id: MEMO_V1_PROGRAM_ID,
+        }
+    }
+
+    /// Decoder for the Memo v2 program
+    pub const fn v2() -> Self {
+        Self {
+            program_id: MEMO_V2_PROGRAM_ID,
+        }
+    }
+}
+
+impl Default for MemoInstructionDecoder {
+    fn default() -> Self {
+        Self::v2()
+    }
+}
+
+impl InstructionDecoder for MemoInstructionDecoder {
+    fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    fn program_name(&self) -> &'static str {
+        if self.program_id == MEMO_V1_PROGRAM_ID {
+            "Memo Program v1"
+        } else {
+            "Memo Program"
+        }
+    }
+
+    fn decode(&self, data: &[u8], accounts: &[AccountMeta]) -> Option<DecodedInstruction> {
+        Some(DecodedInstruction::with_fields_and_accounts(
+            "Memo",
+            vec![DecodedField::new("text", String::from_utf8_lossy(data))],
+            vec!["signer".to_string(); accounts.len()],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memo() {
+        let signer = AccountMeta::new_readonly(Pubkey::new_from_array([1; 32]), true);
+        let decoded = MemoInstructionDecoder::default()
+            .decode("gm ☀️".as_bytes(), &[signer.clone(), signer])
+            .unwrap();
+        assert_eq!(decoded.name, "Memo");
+        assert_eq!(decoded.fields, vec![DecodedField::new("text", "gm ☀️")]);
+        assert_eq!(decoded.account_names, vec!["signer", "signer"]);
+
+        let decoded = MemoInstructionDecoder::v1()
+            .decode(&[b'h', b'i', 0xff], &[])
+            .unwrap();
+        assert_eq!(
+            decoded.fields,
+            vec![DecodedField::new("text", "hi\u{fffd}")]
+        );
+        assert_eq!(
+            MemoInstructionDecoder::v1().program_name(),
+            "Memo Program v1"
+        );
+    }
+}
@@ -3,19 +3,35 @@
 //! This module contains instruction decoders for native Solana programs
 //! that use various discriminator sizes:
 //! - 1-byte: SPL Token, Token 2022, Compute Budget, Light Token (CToken)
-//! - 4-byte: System Program
+//! - 4-byte: System Program, Stake Program, Vote Program
 //! - 8-byte: Anchor programs (Light Registry, Account Compression, Light System)
+//!
+//! The SPL Memo and signature verification precompile decoders have no
+//! discriminator and are written by hand.
 
 // Generic Solana program decoders (always available)
 pub mod compute_budget;
+pub mod memo;
+pub mod precompiles;
 pub mod spl_token;
+pub mod stake;
 pub mod system;
 pub mod token_2022;
+pub mod vote;
 
 pub use compute_budget::ComputeBudgetInstructionDecoder;
+pub use memo::MemoInstructionDecoder;
+pub use precompiles::{Ed25519InstructionDecoder, Secp256k1InstructionDecoder};
 pub use spl_token::SplTokenInstructionDecoder;
+pub use stake::StakeInstructionDecoder;
 pub use system::SystemInstructionDecoder;
 pub use token_2022::Token2022InstructionDecoder;
+pub use vote::VoteInstructionDecoder;
+
+// Catch-all decoder for unknown programs
+pub mod fallback;
+
+pub use fallback::AnchorFallbackDecoder;
 
 // Inlined Light Protocol types for borsh deserialization
 pub mod light_types;
@@ -0,0 +1,217 @@
+//! Ed25519 and Secp256k1 signature verification precompile decoders.
+//!
+//! Both precompiles take a signature count followed by one packed offsets struct
+//! per signature, locating the signature, public key (or Ethereum address) and
+//! message in this or another instruction of the transaction. Only this header
+//! is decoded; signatures are not verified.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{DecodedField, DecodedInstruction, InstructionDecoder};
+
+/// Offsets struct layout: field names and their little-endian widths in bytes
+type OffsetsLayout = [(&'static str, usize); 7];
+
+/// `Ed25519SignatureOffsets`, after a count byte and a padding byte
+const ED25519_OFFSETS: OffsetsLayout = [
+    ("signature_offset", 2),
+    ("signature_instruction_index", 2),
+    ("public_key_offset", 2),
+    ("public_key_instruction_index", 2),
+    ("message_data_offset", 2),
+    ("message_data_size", 2),
+    ("message_instruction_index", 2),
+];
+const ED25519_OFFSETS_START: usize = 2;
+
+/// `SecpSignatureOffsets`, right after the count byte
+const SECP256K1_OFFSETS: OffsetsLayout = [
+    ("signature_offset", 2),
+    ("signature_instruction_index", 1),
+    ("eth_address_offset", 2),
+    ("eth_address_instruction_index", 1),
+    ("message_data_offset", 2),
+    ("message_data_size", 2),
+    ("message_instruction_index", 1),
+];
+const SECP256K1_OFFSETS_START: usize = 1;
+
+/// Ed25519 instruction index referring to the verify instruction itself
+const CURRENT_INSTRUCTION: u64 = u16::MAX as u64;
+
+/// Decode the signature count and offsets into `num_signatures` and one
+/// `signature[i]` group per signature
+///
+/// Truncated data ends the fields with a decode error.
+fn decode_offsets(
+    data: &[u8],
+    start: usize,
+    layout: &OffsetsLayout,
+    current_instruction: Option<u64>,
+) -> DecodedInstruction {
+    let mut fields = Vec::new();
+    match data.first() {
+        Some(count) => {
+            fields.push(DecodedField::new("num_signatures", count.to_string()));
+            let mut offset = start;
+            'signatures: for i in 0..*count as usize {
+                let mut children = Vec::new();
+                for (name, width) in layout {
+                    let Some(bytes) = data.get(offset..offset + width) else {
+                        children.push(DecodedField::decode_error(
+                            *name,
+                            offset.min(data.len()),
+                            "unexpected end of input",
+                        ));
+                        fields.push(DecodedField::with_children(
+                            format!("signature[{}]", i),
+                            children,
+                        ));
+                        break 'signatures;
+                    };
+                    let value = bytes
+                        .iter()
+                        .rev()
+                        .fold(0u64, |value, byte| (value << 8) | u64::from(*byte));
+                    let text = if name.ends_with("_instruction_index")
+                        && Some(value) == current_instruction
+                    {
+                        format!("{} (this instruction)", value)
+                    } else {
+                        value.to_string()
+                    };
+                    children.push(DecodedField::new(*name, text));
+                    offset += width;
+                }
+                fields.push(DecodedField::with_children(
+                    format!("signature[{}]", i),
+                    children,
+                ));
+            }
+        }
+        None => fields.push(DecodedField::decode_error(
+            "num_signatures",
+            0,
+            "unexpected end of input",
+        )),
+    }
+    DecodedInstruction::with_fields_and_accounts("Verify", fields, Vec::new())
+        .with_expected_accounts(0)
+}
+
+/// Decoder for the Ed25519 signature verification precompile.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ed25519InstructionDecoder;
+
+impl InstructionDecoder for Ed25519InstructionDecoder {
+    fn program_id(&self) -> Pubkey {
+        Pubkey::from_str_const("Ed25519SigVerify111111111111111111111111111")
+    }
+
+    fn program_name(&self) -> &'static str {
+        "Ed25519 Program"
+    }
+
+    fn decode(&self, data: &[u8], _accounts: &[AccountMeta]) -> Option<DecodedInstruction> {
+        Some(decode_offsets(
+            data,
+            ED25519_OFFSETS_START,
+            &ED25519_OFFSETS,
+            Some(CURRENT_INSTRUCTION),
+        ))
+    }
+}
+
+/// Decoder for the Secp256k1 (Ethereum address recovery) precompile.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Secp256k1InstructionDecoder;
+
+impl InstructionDecoder for Secp256k1InstructionDecoder {
+    fn program_id(&self) -> Pubkey {
+        Pubkey::from_str_const("KeccakSecp256k11111111111111111111111111111")
+    }
+
+    fn program_name(&self) -> &'static str {
+        "Secp256k1 Program"
+    }
+
+    fn decode(&self, data: &[u8], _accounts: &[AccountMeta]) -> Option<DecodedInstruction> {
+        Some(decode_offsets(
+            data,
+            SECP256K1_OFFSETS_START,
+            &SECP256K1_OFFSETS,
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ed25519_single_signature() {
+        // Header (2 + 14 bytes), then public key at 16, signature at 48, message at 112
+        let offsets: [u16; 7] = [48, u16::MAX, 16, u16::MAX, 112, 5, u16::MAX];
+        let data = [
+            [1u8, 0].as_slice(),
+            &offsets
+                .iter()
+                .flat_map(|offset| offset.to_le_bytes())
+                .collect::<Vec<u8>>(),
+            &[7; 32],
+            &[9; 64],
+            b"hello",
+        ]
+        .concat();
+
+        let decoded = Ed25519InstructionDecoder.decode(&data, &[]).unwrap();
+        assert_eq!(decoded.name, "Verify");
+        assert_eq!(decoded.fields[0], DecodedField::new("num_signatures", "1"));
+        assert_eq!(
+            decoded.fields[1],
+            DecodedField::with_children(
+                "signature[0]",
+                vec![
+                    DecodedField::new("signature_offset", "48"),
+                    DecodedField::new("signature_instruction_index", "65535 (this instruction)"),
+                    DecodedField::new("public_key_offset", "16"),
+                    DecodedField::new("public_key_instruction_index", "65535 (this instruction)"),
+                    DecodedField::new("message_data_offset", "112"),
+                    DecodedField::new("message_data_size", "5"),
+                    DecodedField::new("message_instruction_index", "65535 (this instruction)"),
+                ]
+            )
+        );
+
+        // Offsets cut off after the first two fields
+        let decoded = Ed25519InstructionDecoder.decode(&data[..7], &[]).unwrap();
+        let signature = &decoded.fields[1].children;
+        assert_eq!(signature.len(), 3);
+        assert!(signature[2].is_decode_error());
+    }
+
+    #[test]
+    fn test_secp256k1_offsets() {
+        let data = [1u8, 12, 0, 0, 32, 0, 0, 52, 0, 4, 0, 1];
+        let decoded = Secp256k1InstructionDecoder.decode(&data, &[]).unwrap();
+        let values: Vec<(&str, &str)> = decoded.fields[1]
+            .children
+            .iter()
+            .map(|field| (field.name.as_str(), field.value.as_str()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("signature_offset", "12"),
+                ("signature_instruction_index", "0"),
+                ("eth_address_offset", "32"),
+                ("eth_address_instruction_index", "0"),
+                ("message_data_offset", "52"),
+                ("message_data_size", "4"),
+                ("message_instruction_index", "1"),
+            ]
+        );
+    }
+}
@@ -6,7 +6,49 @@
 // Allow the macro-generated code to reference types from this crate
 extern crate self as light_instruction_decoder;
 
+use borsh::BorshDeserialize;
 use light_instruction_decoder_derive::InstructionDecoder;
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::DecodedField;
+
+/// Authority kinds that `SetAuthority` can change.
+#[derive(BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthorityType {
+    MintTokens,
+    FreezeAccount,
+    AccountOwner,
+    CloseAccount,
+}
+
+/// `SetAuthority` instruction data after the discriminator.
+///
+/// SPL Token packs `new_authority` as a 1-byte tag followed by the pubkey, which
+/// is the borsh `Option` layout.
+#[derive(BorshDeserialize, Debug)]
+pub struct SetAuthorityData {
+    pub authority_type: AuthorityType,
+    pub new_authority: Option<[u8; 32]>,
+}
+
+/// Fields for `SetAuthority`: the authority type by name and the new authority
+/// (`None` when the authority is removed).
+#[cfg(not(target_os = "solana"))]
+pub fn format_set_authority_fields(
+    data: &SetAuthorityData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("authority_type", format!("{:?}", data.authority_type)),
+        DecodedField::new(
+            "new_authority",
+            data.new_authority
+                .map(|key| Pubkey::new_from_array(key).to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ),
+    ]
+}
 
 /// SPL Token program instructions.
 ///
@@ -14,7 +56,7 @@ use light_instruction_decoder_derive::InstructionDecoder;
 /// Each variant's discriminator is its position in this enum (0, 1, 2, ...).
 ///
 /// Note: Complex types (Pubkey, COption<Pubkey>) are not fully parsed;
-/// only primitive fields are extracted.
+/// only primitive fields are extracted, except for `SetAuthority`.
 #[derive(InstructionDecoder)]
 #[instruction_decoder(
     program_id = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
@@ -48,9 +90,12 @@ pub enum SplTokenInstruction {
     Revoke,
 
     /// Set a new authority (index 6)
-    /// Fields: authority_type: u8, new_authority: COption<Pubkey>
-    #[instruction_decoder(account_names = ["account_or_mint", "current_authority"])]
-    SetAuthority { authority_type: u8 },
+    #[instruction_decoder(
+        account_names = ["account_or_mint", "current_authority"],
+        params = SetAuthorityData,
+        fields_formatter = crate::programs::spl_token::format_set_authority_fields
+    )]
+    SetAuthority,
 
     /// Mint new tokens (index 7)
     #[instruction_decoder(account_names = ["mint", "destination", "authority"])]
@@ -128,3 +173,59 @@ pub enum SplTokenInstruction {
     #[instruction_decoder(account_names = ["mint"])]
     UiAmountToAmount,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InstructionDecoder;
+
+    fn accounts(count: u8) -> Vec<AccountMeta> {
+        (0..count)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i + 1; 32]), false))
+            .collect()
+    }
+
+    #[test]
+    fn test_approve() {
+        let data = [[4u8].as_slice(), &1_000u64.to_le_bytes()].concat();
+        let decoded = SplTokenInstructionDecoder
+            .decode(&data, &accounts(3))
+            .unwrap();
+        assert_eq!(decoded.name, "Approve");
+        assert_eq!(decoded.fields, vec![DecodedField::new("amount", "1000")]);
+        assert_eq!(decoded.account_names, vec!["source", "delegate", "owner"]);
+    }
+
+    #[test]
+    fn test_set_authority() {
+        let new_authority = Pubkey::new_from_array([9; 32]);
+        let data = [[6u8, 2, 1].as_slice(), new_authority.as_ref()].concat();
+        let decoded = SplTokenInstructionDecoder
+            .decode(&data, &accounts(2))
+            .unwrap();
+        assert_eq!(decoded.name, "SetAuthority");
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("authority_type", "AccountOwner"),
+                DecodedField::new("new_authority", new_authority.to_string()),
+            ]
+        );
+        assert_eq!(
+            decoded.account_names,
+            vec!["account_or_mint", "current_authority"]
+        );
+
+        // Removing the freeze authority
+        let decoded = SplTokenInstructionDecoder
+            .decode(&[6, 1, 0], &accounts(2))
+            .unwrap();
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("authority_type", "FreezeAccount"),
+                DecodedField::new("new_authority", "None"),
+            ]
+        );
+    }
+}
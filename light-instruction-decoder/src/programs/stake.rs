@@ -0,0 +1,443 @@
+//! Stake program instruction decoder.
+//!
+//! This module provides a macro-derived decoder for the native Stake program,
+//! which uses 4-byte (u32) discriminators followed by bincode-encoded data.
+//!
+//! For the fixed-size fields used here bincode matches borsh, except for enum
+//! tags (u32 instead of u8) and string lengths (u64 instead of u32), which
+//! [`StakeAuthorize`] and [`SeedString`] read by hand.
+
+// Allow the macro-generated code to reference types from this crate
+extern crate self as light_instruction_decoder;
+
+use borsh::{
+    io::{Error, ErrorKind, Read},
+    BorshDeserialize,
+};
+use light_instruction_decoder_derive::InstructionDecoder;
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::DecodedField;
+
+/// Which authority of a stake account an `Authorize*` instruction changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StakeAuthorize {
+    Staker,
+    Withdrawer,
+}
+
+impl BorshDeserialize for StakeAuthorize {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        match u32::deserialize_reader(reader)? {
+            0 => Ok(Self::Staker),
+            1 => Ok(Self::Withdrawer),
+            tag => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid StakeAuthorize tag {}", tag),
+            )),
+        }
+    }
+}
+
+/// Bincode string: u64 length followed by UTF-8 bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedString(pub String);
+
+impl BorshDeserialize for SeedString {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let len = u64::deserialize_reader(reader)?;
+        let mut bytes = Vec::new();
+        reader.take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "unexpected end of input",
+            ));
+        }
+        String::from_utf8(bytes)
+            .map(Self)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+}
+
+/// Staker and withdrawer authorities of a stake account.
+#[derive(BorshDeserialize, Debug)]
+pub struct Authorized {
+    pub staker: [u8; 32],
+    pub withdrawer: [u8; 32],
+}
+
+/// Lockup preventing withdrawals before a timestamp and epoch, unless the custodian signs.
+#[derive(BorshDeserialize, Debug)]
+pub struct Lockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    pub custodian: [u8; 32],
+}
+
+/// `Initialize` instruction data after the discriminator.
+#[derive(BorshDeserialize, Debug)]
+pub struct InitializeData {
+    pub authorized: Authorized,
+    pub lockup: Lockup,
+}
+
+/// `Authorize` instruction data after the discriminator.
+#[derive(BorshDeserialize, Debug)]
+pub struct AuthorizeData {
+    pub new_authority: [u8; 32],
+    pub stake_authorize: StakeAuthorize,
+}
+
+/// `AuthorizeWithSeed` instruction data after the discriminator.
+#[derive(BorshDeserialize, Debug)]
+pub struct AuthorizeWithSeedData {
+    pub new_authority: [u8; 32],
+    pub stake_authorize: StakeAuthorize,
+    pub authority_seed: SeedString,
+    pub authority_owner: [u8; 32],
+}
+
+/// `AuthorizeCheckedWithSeed` instruction data after the discriminator.
+///
+/// The new authority is a signing account instead of data.
+#[derive(BorshDeserialize, Debug)]
+pub struct AuthorizeCheckedWithSeedData {
+    pub stake_authorize: StakeAuthorize,
+    pub authority_seed: SeedString,
+    pub authority_owner: [u8; 32],
+}
+
+/// `SetLockup` instruction data after the discriminator; `None` leaves a value unchanged.
+#[derive(BorshDeserialize, Debug)]
+pub struct LockupArgs {
+    pub unix_timestamp: Option<i64>,
+    pub epoch: Option<u64>,
+    pub custodian: Option<[u8; 32]>,
+}
+
+/// `SetLockupChecked` instruction data after the discriminator.
+///
+/// The new custodian is an optional signing account instead of data.
+#[derive(BorshDeserialize, Debug)]
+pub struct LockupCheckedArgs {
+    pub unix_timestamp: Option<i64>,
+    pub epoch: Option<u64>,
+}
+
+#[cfg(not(target_os = "solana"))]
+fn pubkey(bytes: &[u8; 32]) -> String {
+    Pubkey::new_from_array(*bytes).to_string()
+}
+
+#[cfg(not(target_os = "solana"))]
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| "None".to_string())
+}
+
+/// Fields for `Initialize`: both authorities and the lockup.
+#[cfg(not(target_os = "solana"))]
+pub fn format_initialize_fields(
+    data: &InitializeData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("staker", pubkey(&data.authorized.staker)),
+        DecodedField::new("withdrawer", pubkey(&data.authorized.withdrawer)),
+        DecodedField::with_children(
+            "lockup",
+            vec![
+                DecodedField::new("unix_timestamp", data.lockup.unix_timestamp.to_string()),
+                DecodedField::new("epoch", data.lockup.epoch.to_string()),
+                DecodedField::new("custodian", pubkey(&data.lockup.custodian)),
+            ],
+        ),
+    ]
+}
+
+/// Fields for `Authorize`: the new authority and which authority it replaces.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_fields(
+    data: &AuthorizeData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("new_authority", pubkey(&data.new_authority)),
+        DecodedField::new("stake_authorize", format!("{:?}", data.stake_authorize)),
+    ]
+}
+
+/// Fields for `AuthorizeWithSeed`: as `Authorize`, plus the seed and owner
+/// deriving the current authority from the base account.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_with_seed_fields(
+    data: &AuthorizeWithSeedData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("new_authority", pubkey(&data.new_authority)),
+        DecodedField::new("stake_authorize", format!("{:?}", data.stake_authorize)),
+        DecodedField::new("authority_seed", data.authority_seed.0.clone()),
+        DecodedField::new("authority_owner", pubkey(&data.authority_owner)),
+    ]
+}
+
+/// Fields for `AuthorizeChecked`: which authority the signing new authority replaces.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_checked_fields(
+    data: &StakeAuthorize,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![DecodedField::new("stake_authorize", format!("{:?}", data))]
+}
+
+/// Fields for `AuthorizeCheckedWithSeed`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_checked_with_seed_fields(
+    data: &AuthorizeCheckedWithSeedData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("stake_authorize", format!("{:?}", data.stake_authorize)),
+        DecodedField::new("authority_seed", data.authority_seed.0.clone()),
+        DecodedField::new("authority_owner", pubkey(&data.authority_owner)),
+    ]
+}
+
+/// Fields for `SetLockup`; unchanged values are shown as `None`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_set_lockup_fields(data: &LockupArgs, _accounts: &[AccountMeta]) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("unix_timestamp", optional(data.unix_timestamp)),
+        DecodedField::new("epoch", optional(data.epoch)),
+        DecodedField::new("custodian", optional(data.custodian.as_ref().map(pubkey))),
+    ]
+}
+
+/// Fields for `SetLockupChecked`; unchanged values are shown as `None`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_set_lockup_checked_fields(
+    data: &LockupCheckedArgs,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("unix_timestamp", optional(data.unix_timestamp)),
+        DecodedField::new("epoch", optional(data.epoch)),
+    ]
+}
+
+/// Stake program instructions.
+///
+/// The Stake program uses a 4-byte discriminator (u32 little-endian).
+/// Each variant's discriminator is its position in this enum (0, 1, 2, ...).
+///
+/// Optional trailing accounts (the lockup custodian of `Authorize`,
+/// `AuthorizeWithSeed`, `AuthorizeChecked*` and `Withdraw`, the new custodian of
+/// `SetLockupChecked`) are not named, so leaving them out is not reported.
+#[derive(InstructionDecoder)]
+#[instruction_decoder(
+    program_id = "Stake11111111111111111111111111111111111111",
+    program_name = "Stake Program",
+    discriminator_size = 4
+)]
+pub enum StakeInstruction {
+    /// Initialize a stake account with its authorities and lockup (index 0)
+    #[instruction_decoder(
+        account_names = ["stake_account", "rent_sysvar"],
+        params = InitializeData,
+        fields_formatter = crate::programs::stake::format_initialize_fields
+    )]
+    Initialize,
+
+    /// Change the staker or withdrawer authority (index 1)
+    #[instruction_decoder(
+        account_names = ["stake_account", "clock_sysvar", "authority"],
+        params = AuthorizeData,
+        fields_formatter = crate::programs::stake::format_authorize_fields
+    )]
+    Authorize,
+
+    /// Delegate the stake to a vote account (index 2)
+    #[instruction_decoder(account_names = ["stake_account", "vote_account", "clock_sysvar", "stake_history_sysvar", "stake_config", "stake_authority"])]
+    DelegateStake,
+
+    /// Split lamports into a new stake account (index 3)
+    #[instruction_decoder(account_names = ["stake_account", "split_stake_account", "stake_authority"])]
+    Split { lamports: u64 },
+
+    /// Withdraw unstaked lamports (index 4)
+    #[instruction_decoder(account_names = ["stake_account", "recipient", "clock_sysvar", "stake_history_sysvar", "withdraw_authority"])]
+    Withdraw { lamports: u64 },
+
+    /// Deactivate the stake (index 5)
+    #[instruction_decoder(account_names = ["stake_account", "clock_sysvar", "stake_authority"])]
+    Deactivate,
+
+    /// Change the lockup (index 6)
+    #[instruction_decoder(
+        account_names = ["stake_account", "lockup_or_withdraw_authority"],
+        params = LockupArgs,
+        fields_formatter = crate::programs::stake::format_set_lockup_fields
+    )]
+    SetLockup,
+
+    /// Merge the source stake account into the destination (index 7)
+    #[instruction_decoder(account_names = ["destination_stake_account", "source_stake_account", "clock_sysvar", "stake_history_sysvar", "stake_authority"])]
+    Merge,
+
+    /// Change an authority held by a derived address (index 8)
+    #[instruction_decoder(
+        account_names = ["stake_account", "authority_base", "clock_sysvar"],
+        params = AuthorizeWithSeedData,
+        fields_formatter = crate::programs::stake::format_authorize_with_seed_fields
+    )]
+    AuthorizeWithSeed,
+
+    /// Initialize with the authorities as signing accounts and no lockup (index 9)
+    #[instruction_decoder(account_names = ["stake_account", "rent_sysvar", "stake_authority", "withdraw_authority"])]
+    InitializeChecked,
+
+    /// Change an authority, with the new authority signing (index 10)
+    #[instruction_decoder(
+        account_names = ["stake_account", "clock_sysvar", "authority", "new_authority"],
+        params = StakeAuthorize,
+        fields_formatter = crate::programs::stake::format_authorize_checked_fields
+    )]
+    AuthorizeChecked,
+
+    /// Change an authority held by a derived address, with the new authority signing (index 11)
+    #[instruction_decoder(
+        account_names = ["stake_account", "authority_base", "clock_sysvar", "new_authority"],
+        params = AuthorizeCheckedWithSeedData,
+        fields_formatter = crate::programs::stake::format_authorize_checked_with_seed_fields
+    )]
+    AuthorizeCheckedWithSeed,
+
+    /// Change the lockup, with the new custodian signing (index 12)
+    #[instruction_decoder(
+        account_names = ["stake_account", "lockup_or_withdraw_authority"],
+        params = LockupCheckedArgs,
+        fields_formatter = crate::programs::stake::format_set_lockup_checked_fields
+    )]
+    SetLockupChecked,
+
+    /// Return the minimum delegation as return data (index 13)
+    GetMinimumDelegation,
+
+    /// Deactivate stake delegated to a delinquent vote account (index 14)
+    #[instruction_decoder(account_names = ["stake_account", "delinquent_vote_account", "reference_vote_account"])]
+    DeactivateDelinquent,
+
+    /// Deprecated redelegation to another vote account (index 15)
+    #[instruction_decoder(account_names = ["stake_account", "uninitialized_stake_account", "vote_account", "stake_config", "stake_authority"])]
+    Redelegate,
+
+    /// Move active stake between accounts delegated to the same vote account (index 16)
+    #[instruction_decoder(account_names = ["source_stake_account", "destination_stake_account", "stake_authority"])]
+    MoveStake { lamports: u64 },
+
+    /// Move inactive lamports between stake accounts (index 17)
+    #[instruction_decoder(account_names = ["source_stake_account", "destination_stake_account", "stake_authority"])]
+    MoveLamports { lamports: u64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InstructionDecoder;
+
+    fn accounts(count: u8) -> Vec<AccountMeta> {
+        (0..count)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i + 1; 32]), false))
+            .collect()
+    }
+
+    #[test]
+    fn test_delegate_stake() {
+        let decoded = StakeInstructionDecoder
+            .decode(&2u32.to_le_bytes(), &accounts(6))
+            .unwrap();
+        assert_eq!(decoded.name, "DelegateStake");
+        assert!(decoded.fields.is_empty());
+        assert_eq!(
+            decoded.account_names,
+            vec![
+                "stake_account",
+                "vote_account",
+                "clock_sysvar",
+                "stake_history_sysvar",
+                "stake_config",
+                "stake_authority"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_initialize_lockup_and_authorities() {
+        let staker = Pubkey::new_from_array([7; 32]);
+        let withdrawer = Pubkey::new_from_array([8; 32]);
+        let custodian = Pubkey::new_from_array([9; 32]);
+        let data = [
+            0u32.to_le_bytes().as_slice(),
+            staker.as_ref(),
+            withdrawer.as_ref(),
+            &1_700_000_000i64.to_le_bytes(),
+            &42u64.to_le_bytes(),
+            custodian.as_ref(),
+        ]
+        .concat();
+        let decoded = StakeInstructionDecoder.decode(&data, &accounts(2)).unwrap();
+        assert_eq!(decoded.name, "Initialize");
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("staker", staker.to_string()),
+                DecodedField::new("withdrawer", withdrawer.to_string()),
+                DecodedField::with_children(
+                    "lockup",
+                    vec![
+                        DecodedField::new("unix_timestamp", "1700000000"),
+                        DecodedField::new("epoch", "42"),
+                        DecodedField::new("custodian", custodian.to_string()),
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(decoded.account_names, vec!["stake_account", "rent_sysvar"]);
+    }
+
+    #[test]
+    fn test_authorize_with_seed() {
+        let new_authority = Pubkey::new_from_array([7; 32]);
+        let owner = Pubkey::new_from_array([8; 32]);
+        // bincode: u32 enum tag, u64 string length
+        let data = [
+            8u32.to_le_bytes().as_slice(),
+            new_authority.as_ref(),
+            &1u32.to_le_bytes(),
+            &4u64.to_le_bytes(),
+            b"seed",
+            owner.as_ref(),
+        ]
+        .concat();
+        let decoded = StakeInstructionDecoder.decode(&data, &accounts(3)).unwrap();
+        assert_eq!(decoded.name, "AuthorizeWithSeed");
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("new_authority", new_authority.to_string()),
+                DecodedField::new("stake_authorize", "Withdrawer"),
+                DecodedField::new("authority_seed", "seed"),
+                DecodedField::new("authority_owner", owner.to_string()),
+            ]
+        );
+
+        // Unknown authority kind
+        let data = [1u32.to_le_bytes().as_slice(), &[0; 32], &2u32.to_le_bytes()].concat();
+        let decoded = StakeInstructionDecoder.decode(&data, &accounts(3)).unwrap();
+        assert!(decoded.fields.iter().any(DecodedField::is_decode_error));
+    }
+}
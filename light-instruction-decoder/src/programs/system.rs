@@ -31,6 +31,7 @@ pub enum SystemInstruction {
 
     /// Transfer lamports (index 2)
     /// Data: lamports (u64)
+    /// Accounts: from (writable signer), to (writable)
     #[instruction_decoder(account_names = ["from", "to"])]
     Transfer { lamports: u64 },
 
@@ -51,12 +52,18 @@ pub enum SystemInstruction {
     /// Initialize nonce account (index 6)
     /// Data: authority (Pubkey)
     #[instruction_decoder(account_names = ["nonce_account", "recent_blockhashes_sysvar", "rent_sysvar"])]
-    InitializeNonceAccount,
+    InitializeNonceAccount {
+        #[decoder(display = "pubkey")]
+        authority: [u8; 32],
+    },
 
     /// Authorize nonce account (index 7)
     /// Data: new_authority (Pubkey)
     #[instruction_decoder(account_names = ["nonce_account", "nonce_authority"])]
-    AuthorizeNonceAccount,
+    AuthorizeNonceAccount {
+        #[decoder(display = "pubkey")]
+        new_authority: [u8; 32],
+    },
 
     /// Allocate space for account (index 8)
     /// Data: space (u64)
@@ -78,7 +85,7 @@ pub enum SystemInstruction {
     #[instruction_decoder(account_names = ["funding_account", "base_account", "recipient"])]
     TransferWithSeed { lamports: u64 },
 
-    /// Upgrade nonce account (index 12)
+    /// Upgrade nonce account to the current durable nonce version (index 12)
     #[instruction_decoder(account_names = ["nonce_account"])]
     UpgradeNonceAccount,
 }
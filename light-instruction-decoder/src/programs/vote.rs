@@ -0,0 +1,654 @@
+//! Vote program instruction decoder.
+//!
+//! This module provides a macro-derived decoder for the native Vote program,
+//! which uses 4-byte (u32) discriminators followed by bincode-encoded data.
+//!
+//! Besides the bincode enum tags and string lengths handled as in
+//! [`crate::programs::stake`], vectors carry a u64 length ([`BincodeVec`]) and
+//! the compact vote state updates store lockouts as varint slot offsets from the
+//! root ([`CompactVoteStateUpdate`]).
+
+// Allow the macro-generated code to reference types from this crate
+extern crate self as light_instruction_decoder;
+
+use borsh::{
+    io::{Error, ErrorKind, Read},
+    BorshDeserialize,
+};
+use light_instruction_decoder_derive::InstructionDecoder;
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{programs::stake::SeedString, DecodedField};
+
+/// Which authority of a vote account an `Authorize*` instruction changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteAuthorize {
+    Voter,
+    Withdrawer,
+}
+
+impl BorshDeserialize for VoteAuthorize {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        match u32::deserialize_reader(reader)? {
+            0 => Ok(Self::Voter),
+            1 => Ok(Self::Withdrawer),
+            tag => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid VoteAuthorize tag {}", tag),
+            )),
+        }
+    }
+}
+
+/// Bincode vector: u64 length followed by the elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BincodeVec<T>(pub Vec<T>);
+
+impl<T: BorshDeserialize> BorshDeserialize for BincodeVec<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let len = u64::deserialize_reader(reader)?;
+        // No preallocation: a bogus length fails at the end of the data instead
+        let mut items = Vec::new();
+        for _ in 0..len {
+            items.push(T::deserialize_reader(reader)?);
+        }
+        Ok(Self(items))
+    }
+}
+
+/// Slot with the number of votes stacked on top of it.
+#[derive(BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lockout {
+    pub slot: u64,
+    pub confirmation_count: u32,
+}
+
+/// `InitializeAccount` instruction data after the discriminator.
+#[derive(BorshDeserialize, Debug)]
+pub struct VoteInit {
+    pub node_pubkey: [u8; 32],
+    pub authorized_voter: [u8; 32],
+    pub authorized_withdrawer: [u8; 32],
+    pub commission: u8,
+}
+
+/// `Authorize` instruction data after the discriminator.
+#[derive(BorshDeserialize, Debug)]
+pub struct AuthorizeData {
+    pub new_authority: [u8; 32],
+    pub vote_authorize: VoteAuthorize,
+}
+
+/// `AuthorizeWithSeed` instruction data after the discriminator.
+#[derive(BorshDeserialize, Debug)]
+pub struct AuthorizeWithSeedData {
+    pub vote_authorize: VoteAuthorize,
+    pub authority_owner: [u8; 32],
+    pub authority_seed: SeedString,
+    pub new_authority: [u8; 32],
+}
+
+/// `AuthorizeCheckedWithSeed` instruction data after the discriminator.
+///
+/// The new authority is a signing account instead of data.
+#[derive(BorshDeserialize, Debug)]
+pub struct AuthorizeCheckedWithSeedData {
+    pub vote_authorize: VoteAuthorize,
+    pub authority_owner: [u8; 32],
+    pub authority_seed: SeedString,
+}
+
+/// Legacy `Vote` instruction data: voted slots, the bank hash of the last one
+/// and an optional timestamp.
+#[derive(BorshDeserialize, Debug)]
+pub struct VoteData {
+    pub slots: BincodeVec<u64>,
+    pub hash: [u8; 32],
+    pub timestamp: Option<i64>,
+}
+
+/// `VoteSwitch` instruction data: a vote plus the switching proof hash.
+#[derive(BorshDeserialize, Debug)]
+pub struct VoteSwitchData {
+    pub vote: VoteData,
+    pub proof_hash: [u8; 32],
+}
+
+/// Vote state update: the voter's whole lockout tower.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoteStateUpdate {
+    pub lockouts: Vec<Lockout>,
+    pub root: Option<u64>,
+    pub hash: [u8; 32],
+    pub timestamp: Option<i64>,
+}
+
+impl BorshDeserialize for VoteStateUpdate {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        Ok(Self {
+            lockouts: BincodeVec::deserialize_reader(reader)?.0,
+            root: BorshDeserialize::deserialize_reader(reader)?,
+            hash: BorshDeserialize::deserialize_reader(reader)?,
+            timestamp: BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+
+/// Vote state update in the compact layout of `CompactUpdateVoteState` and `TowerSync`
+///
+/// The root is a u64 (`u64::MAX` for none), followed by a compact-u16 count of
+/// lockouts, each a varint slot offset from the previous slot (the root, or 0)
+/// and a u8 confirmation count, then the hash and timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactVoteStateUpdate(pub VoteStateUpdate);
+
+impl BorshDeserialize for CompactVoteStateUpdate {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let root = u64::deserialize_reader(reader)?;
+        let root = (root != u64::MAX).then_some(root);
+        let count = read_compact_u16(reader)?;
+        let mut slot = root.unwrap_or(0);
+        let mut lockouts = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let offset = read_varint_u64(reader)?;
+            slot = slot.checked_add(offset).ok_or_else(|| {
+                Error::new(ErrorKind::InvalidData, "lockout slot offset overflows")
+            })?;
+            let confirmation_count = u8::deserialize_reader(reader)?;
+            lockouts.push(Lockout {
+                slot,
+                confirmation_count: confirmation_count.into(),
+            });
+        }
+        Ok(Self(VoteStateUpdate {
+            lockouts,
+            root,
+            hash: BorshDeserialize::deserialize_reader(reader)?,
+            timestamp: BorshDeserialize::deserialize_reader(reader)?,
+        }))
+    }
+}
+
+/// Compact-u16 length: 7 bits per byte, high bit set on all but the last byte.
+fn read_compact_u16<R: Read>(reader: &mut R) -> borsh::io::Result<u16> {
+    let mut value: u32 = 0;
+    for i in 0..3 {
+        let byte = u8::deserialize_reader(reader)?;
+        value |= u32::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return u16::try_from(value)
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "compact-u16 overflows"));
+        }
+    }
+    Err(Error::new(ErrorKind::InvalidData, "compact-u16 too long"))
+}
+
+/// LEB128 varint u64: 7 bits per byte, high bit set on all but the last byte.
+fn read_varint_u64<R: Read>(reader: &mut R) -> borsh::io::Result<u64> {
+    let mut value: u64 = 0;
+    for i in 0..10 {
+        let byte = u8::deserialize_reader(reader)?;
+        value |= u64::from(byte & 0x7f)
+            .checked_shl(7 * i)
+            .filter(|bits| bits >> (7 * i) == u64::from(byte & 0x7f))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "varint overflows u64"))?;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::new(ErrorKind::InvalidData, "varint too long"))
+}
+
+/// `UpdateVoteStateSwitch` instruction data.
+#[derive(BorshDeserialize, Debug)]
+pub struct VoteStateUpdateSwitch {
+    pub update: VoteStateUpdate,
+    pub proof_hash: [u8; 32],
+}
+
+/// `CompactUpdateVoteStateSwitch` instruction data.
+#[derive(BorshDeserialize, Debug)]
+pub struct CompactVoteStateUpdateSwitch {
+    pub update: CompactVoteStateUpdate,
+    pub proof_hash: [u8; 32],
+}
+
+/// `TowerSync` instruction data: a compact vote state update plus the block id.
+#[derive(BorshDeserialize, Debug)]
+pub struct TowerSync {
+    pub update: CompactVoteStateUpdate,
+    pub block_id: [u8; 32],
+}
+
+/// `TowerSyncSwitch` instruction data.
+#[derive(BorshDeserialize, Debug)]
+pub struct TowerSyncSwitch {
+    pub tower: TowerSync,
+    pub proof_hash: [u8; 32],
+}
+
+#[cfg(not(target_os = "solana"))]
+fn pubkey(bytes: &[u8; 32]) -> String {
+    Pubkey::new_from_array(*bytes).to_string()
+}
+
+#[cfg(not(target_os = "solana"))]
+fn hash(bytes: &[u8; 32]) -> String {
+    bs58::encode(bytes).into_string()
+}
+
+#[cfg(not(target_os = "solana"))]
+fn timestamp(timestamp: Option<i64>) -> String {
+    timestamp
+        .map(|timestamp| timestamp.to_string())
+        .unwrap_or_else(|| "None".to_string())
+}
+
+#[cfg(not(target_os = "solana"))]
+fn vote_fields(vote: &VoteData) -> Vec<DecodedField> {
+    let slots: Vec<String> = vote.slots.0.iter().map(u64::to_string).collect();
+    vec![
+        DecodedField::new("slots", format!("[{}]", slots.join(", "))),
+        DecodedField::new("hash", hash(&vote.hash)),
+        DecodedField::new("timestamp", timestamp(vote.timestamp)),
+    ]
+}
+
+/// Root, lockouts as `slot (confirmation count)`, hash and timestamp
+#[cfg(not(target_os = "solana"))]
+fn update_fields(update: &VoteStateUpdate) -> Vec<DecodedField> {
+    let lockouts: Vec<String> = update
+        .lockouts
+        .iter()
+        .map(|lockout| format!("{} ({})", lockout.slot, lockout.confirmation_count))
+        .collect();
+    vec![
+        DecodedField::new(
+            "root",
+            update
+                .root
+                .map(|root| root.to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ),
+        DecodedField::new("lockouts", format!("[{}]", lockouts.join(", "))),
+        DecodedField::new("hash", hash(&update.hash)),
+        DecodedField::new("timestamp", timestamp(update.timestamp)),
+    ]
+}
+
+/// Fields for `InitializeAccount`: node identity, authorities and commission.
+#[cfg(not(target_os = "solana"))]
+pub fn format_initialize_account_fields(
+    data: &VoteInit,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("node_pubkey", pubkey(&data.node_pubkey)),
+        DecodedField::new("authorized_voter", pubkey(&data.authorized_voter)),
+        DecodedField::new("authorized_withdrawer", pubkey(&data.authorized_withdrawer)),
+        DecodedField::new("commission", format!("{}%", data.commission)),
+    ]
+}
+
+/// Fields for `Authorize`: the new authority and which authority it replaces.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_fields(
+    data: &AuthorizeData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("new_authority", pubkey(&data.new_authority)),
+        DecodedField::new("vote_authorize", format!("{:?}", data.vote_authorize)),
+    ]
+}
+
+/// Fields for `AuthorizeChecked`: which authority the signing new authority replaces.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_checked_fields(
+    data: &VoteAuthorize,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![DecodedField::new("vote_authorize", format!("{:?}", data))]
+}
+
+/// Fields for `AuthorizeWithSeed`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_with_seed_fields(
+    data: &AuthorizeWithSeedData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("vote_authorize", format!("{:?}", data.vote_authorize)),
+        DecodedField::new("authority_owner", pubkey(&data.authority_owner)),
+        DecodedField::new("authority_seed", data.authority_seed.0.clone()),
+        DecodedField::new("new_authority", pubkey(&data.new_authority)),
+    ]
+}
+
+/// Fields for `AuthorizeCheckedWithSeed`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_authorize_checked_with_seed_fields(
+    data: &AuthorizeCheckedWithSeedData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    vec![
+        DecodedField::new("vote_authorize", format!("{:?}", data.vote_authorize)),
+        DecodedField::new("authority_owner", pubkey(&data.authority_owner)),
+        DecodedField::new("authority_seed", data.authority_seed.0.clone()),
+    ]
+}
+
+/// Fields for `Vote`: voted slots, bank hash and timestamp.
+#[cfg(not(target_os = "solana"))]
+pub fn format_vote_fields(data: &VoteData, _accounts: &[AccountMeta]) -> Vec<DecodedField> {
+    vote_fields(data)
+}
+
+/// Fields for `VoteSwitch`: as `Vote`, plus the switching proof hash.
+#[cfg(not(target_os = "solana"))]
+pub fn format_vote_switch_fields(
+    data: &VoteSwitchData,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    let mut fields = vote_fields(&data.vote);
+    fields.push(DecodedField::new("proof_hash", hash(&data.proof_hash)));
+    fields
+}
+
+/// Fields for `UpdateVoteState`: root, lockouts, hash and timestamp.
+#[cfg(not(target_os = "solana"))]
+pub fn format_update_vote_state_fields(
+    data: &VoteStateUpdate,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    update_fields(data)
+}
+
+/// Fields for `UpdateVoteStateSwitch`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_update_vote_state_switch_fields(
+    data: &VoteStateUpdateSwitch,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    let mut fields = update_fields(&data.update);
+    fields.push(DecodedField::new("proof_hash", hash(&data.proof_hash)));
+    fields
+}
+
+/// Fields for `CompactUpdateVoteState`, with the lockout offsets resolved to slots.
+#[cfg(not(target_os = "solana"))]
+pub fn format_compact_update_vote_state_fields(
+    data: &CompactVoteStateUpdate,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    update_fields(&data.0)
+}
+
+/// Fields for `CompactUpdateVoteStateSwitch`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_compact_update_vote_state_switch_fields(
+    data: &CompactVoteStateUpdateSwitch,
+    _accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    let mut fields = update_fields(&data.update.0);
+    fields.push(DecodedField::new("proof_hash", hash(&data.proof_hash)));
+    fields
+}
+
+/// Fields for `TowerSync`: as `CompactUpdateVoteState`, plus the block id.
+#[cfg(not(target_os = "solana"))]
+pub fn format_tower_sync_fields(data: &TowerSync, _accounts: &[AccountMeta]) -> Vec<DecodedField> {
+    let mut fields = update_fields(&data.update.0);
+    fields.push(DecodedField::new("block_id", hash(&data.block_id)));
+    fields
+}
+
+/// Fields for `TowerSyncSwitch`.
+#[cfg(not(target_os = "solana"))]
+pub fn format_tower_sync_switch_fields(
+    data: &TowerSyncSwitch,
+    accounts: &[AccountMeta],
+) -> Vec<DecodedField> {
+    let mut fields = format_tower_sync_fields(&data.tower, accounts);
+    fields.push(DecodedField::new("proof_hash", hash(&data.proof_hash)));
+    fields
+}
+
+/// Vote program instructions.
+///
+/// The Vote program uses a 4-byte discriminator (u32 little-endian).
+/// Each variant's discriminator is its position in this enum (0, 1, 2, ...).
+#[derive(InstructionDecoder)]
+#[instruction_decoder(
+    program_id = "Vote111111111111111111111111111111111111111",
+    program_name = "Vote Program",
+    discriminator_size = 4
+)]
+pub enum VoteInstruction {
+    /// Initialize a vote account (index 0)
+    #[instruction_decoder(
+        account_names = ["vote_account", "rent_sysvar", "clock_sysvar", "node_identity"],
+        params = VoteInit,
+        fields_formatter = crate::programs::vote::format_initialize_account_fields
+    )]
+    InitializeAccount,
+
+    /// Change the voter or withdrawer authority (index 1)
+    #[instruction_decoder(
+        account_names = ["vote_account", "clock_sysvar", "authority"],
+        params = AuthorizeData,
+        fields_formatter = crate::programs::vote::format_authorize_fields
+    )]
+    Authorize,
+
+    /// Legacy vote on a list of slots (index 2)
+    #[instruction_decoder(
+        account_names = ["vote_account", "slot_hashes_sysvar", "clock_sysvar", "vote_authority"],
+        params = VoteData,
+        fields_formatter = crate::programs::vote::format_vote_fields
+    )]
+    Vote,
+
+    /// Withdraw lamports from the vote account (index 3)
+    #[instruction_decoder(account_names = ["vote_account", "recipient", "withdraw_authority"])]
+    Withdraw { lamports: u64 },
+
+    /// Change the validator identity (index 4)
+    #[instruction_decoder(account_names = ["vote_account", "node_identity", "withdraw_authority"])]
+    UpdateValidatorIdentity,
+
+    /// Change the commission percentage (index 5)
+    #[instruction_decoder(account_names = ["vote_account", "withdraw_authority"])]
+    UpdateCommission { commission: u8 },
+
+    /// Legacy vote with a switching proof (index 6)
+    #[instruction_decoder(
+        account_names = ["vote_account", "slot_hashes_sysvar", "clock_sysvar", "vote_authority"],
+        params = VoteSwitchData,
+        fields_formatter = crate::programs::vote::format_vote_switch_fields
+    )]
+    VoteSwitch,
+
+    /// Change an authority, with the new authority signing (index 7)
+    #[instruction_decoder(
+        account_names = ["vote_account", "clock_sysvar", "authority", "new_authority"],
+        params = VoteAuthorize,
+        fields_formatter = crate::programs::vote::format_authorize_checked_fields
+    )]
+    AuthorizeChecked,
+
+    /// Replace the vote state with a full lockout tower (index 8)
+    #[instruction_decoder(
+        account_names = ["vote_account", "vote_authority"],
+        params = VoteStateUpdate,
+        fields_formatter = crate::programs::vote::format_update_vote_state_fields
+    )]
+    UpdateVoteState,
+
+    /// Vote state update with a switching proof (index 9)
+    #[instruction_decoder(
+        account_names = ["vote_account", "vote_authority"],
+        params = VoteStateUpdateSwitch,
+        fields_formatter = crate::programs::vote::format_update_vote_state_switch_fields
+    )]
+    UpdateVoteStateSwitch,
+
+    /// Change an authority held by a derived address (index 10)
+    #[instruction_decoder(
+        account_names = ["vote_account", "clock_sysvar", "authority_base"],
+        params = AuthorizeWithSeedData,
+        fields_formatter = crate::programs::vote::format_authorize_with_seed_fields
+    )]
+    AuthorizeWithSeed,
+
+    /// Change an authority held by a derived address, with the new authority signing (index 11)
+    #[instruction_decoder(
+        account_names = ["vote_account", "clock_sysvar", "authority_base", "new_authority"],
+        params = AuthorizeCheckedWithSeedData,
+        fields_formatter = crate::programs::vote::format_authorize_checked_with_seed_fields
+    )]
+    AuthorizeCheckedWithSeed,
+
+    /// Vote state update in the compact layout (index 12)
+    #[instruction_decoder(
+        account_names = ["vote_account", "vote_authority"],
+        params = CompactVoteStateUpdate,
+        fields_formatter = crate::programs::vote::format_compact_update_vote_state_fields
+    )]
+    CompactUpdateVoteState,
+
+    /// Compact vote state update with a switching proof (index 13)
+    #[instruction_decoder(
+        account_names = ["vote_account", "vote_authority"],
+        params = CompactVoteStateUpdateSwitch,
+        fields_formatter = crate::programs::vote::format_compact_update_vote_state_switch_fields
+    )]
+    CompactUpdateVoteStateSwitch,
+
+    /// Compact vote state update including the block id (index 14)
+    #[instruction_decoder(
+        account_names = ["vote_account", "vote_authority"],
+        params = TowerSync,
+        fields_formatter = crate::programs::vote::format_tower_sync_fields
+    )]
+    TowerSync,
+
+    /// Tower sync with a switching proof (index 15)
+    #[instruction_decoder(
+        account_names = ["vote_account", "vote_authority"],
+        params = TowerSyncSwitch,
+        fields_formatter = crate::programs::vote::format_tower_sync_switch_fields
+    )]
+    TowerSyncSwitch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InstructionDecoder;
+
+    fn accounts(count: u8) -> Vec<AccountMeta> {
+        (0..count)
+            .map(|i| AccountMeta::new(Pubkey::new_from_array([i + 1; 32]), false))
+            .collect()
+    }
+
+    #[test]
+    fn test_vote() {
+        let bank_hash = [5u8; 32];
+        let data = [
+            2u32.to_le_bytes().as_slice(),
+            &3u64.to_le_bytes(),
+            &100u64.to_le_bytes(),
+            &101u64.to_le_bytes(),
+            &102u64.to_le_bytes(),
+            &bank_hash,
+            &[1],
+            &1_700_000_000i64.to_le_bytes(),
+        ]
+        .concat();
+        let decoded = VoteInstructionDecoder.decode(&data, &accounts(4)).unwrap();
+        assert_eq!(decoded.name, "Vote");
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("slots", "[100, 101, 102]"),
+                DecodedField::new("hash", bs58::encode(bank_hash).into_string()),
+                DecodedField::new("timestamp", "1700000000"),
+            ]
+        );
+        assert_eq!(
+            decoded.account_names,
+            vec![
+                "vote_account",
+                "slot_hashes_sysvar",
+                "clock_sysvar",
+                "vote_authority"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compact_update_vote_state() {
+        let bank_hash = [5u8; 32];
+        // Root 1000, lockouts at 1000 + 1 and + 200 more (two-byte varint), no timestamp
+        let data = [
+            12u32.to_le_bytes().as_slice(),
+            &1_000u64.to_le_bytes(),
+            &[2],
+            &[1, 31],
+            &[0xc8, 0x01, 1],
+            &bank_hash,
+            &[0],
+        ]
+        .concat();
+        let decoded = VoteInstructionDecoder.decode(&data, &accounts(2)).unwrap();
+        assert_eq!(decoded.name, "CompactUpdateVoteState");
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("root", "1000"),
+                DecodedField::new("lockouts", "[1001 (31), 1201 (1)]"),
+                DecodedField::new("hash", bs58::encode(bank_hash).into_string()),
+                DecodedField::new("timestamp", "None"),
+            ]
+        );
+        assert_eq!(
+            decoded.account_names,
+            vec!["vote_account", "vote_authority"]
+        );
+
+        // Truncated before the hash
+        let decoded = VoteInstructionDecoder
+            .decode(&data[..data.len() - 10], &accounts(2))
+            .unwrap();
+        assert!(decoded.fields.iter().any(DecodedField::is_decode_error));
+    }
+
+    #[test]
+    fn test_initialize_account() {
+        let node = Pubkey::new_from_array([7; 32]);
+        let voter = Pubkey::new_from_array([8; 32]);
+        let withdrawer = Pubkey::new_from_array([9; 32]);
+        let data = [
+            0u32.to_le_bytes().as_slice(),
+            node.as_ref(),
+            voter.as_ref(),
+            withdrawer.as_ref(),
+            &[10],
+        ]
+        .concat();
+        let decoded = VoteInstructionDecoder.decode(&data, &accounts(4)).unwrap();
+        assert_eq!(decoded.name, "InitializeAccount");
+        assert_eq!(
+            decoded.fields,
+            vec![
+                DecodedField::new("node_pubkey", node.to_string()),
+                DecodedField::new("authorized_voter", voter.to_string()),
+                DecodedField::new("authorized_withdrawer", withdrawer.to_string()),
+                DecodedField::new("commission", "10%"),
+            ]
+        );
+    }
+}
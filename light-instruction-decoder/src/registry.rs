@@ -1,6 +1,9 @@
 //! Instruction decoder registry for Light Protocol and common Solana programs
 
-use std::collections::HashMap;
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::Arc,
+};
 
 use solana_instruction::AccountMeta;
 use solana_pubkey::Pubkey;
@@ -13,45 +16,206 @@ use crate::{DecodedInstruction, InstructionDecoder};
 
 /// Registry of instruction decoders
 pub struct DecoderRegistry {
-    decoders: HashMap<Pubkey, Box<dyn InstructionDecoder>>,
+    /// Decoders by program ID; aliased program IDs share one decoder
+    decoders: HashMap<Pubkey, Arc<dyn InstructionDecoder>>,
+    /// Program names overriding the decoder's own `program_name`
+    names: HashMap<Pubkey, String>,
+    /// Program IDs currently served by a built-in decoder
+    builtins: HashSet<Pubkey>,
+    /// Decoder for programs without a registered decoder
+    fallback: Option<Box<dyn InstructionDecoder>>,
+    /// Program IDs whose custom decoder was replaced by a later registration
+    conflicts: Vec<Pubkey>,
 }
 
+/// Error returned by [`DecoderRegistry::try_register`] when a custom decoder is
+/// already registered for the program ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoderConflict {
+    /// Program ID both decoders claim
+    pub program_id: Pubkey,
+    /// Name of the program served by the decoder already registered
+    pub existing: String,
+}
+
+impl std::fmt::Display for DecoderConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "a decoder for {} is already registered for program {}",
+            self.existing, self.program_id
+        )
+    }
+}
+
+impl std::error::Error for DecoderConflict {}
+
 impl std::fmt::Debug for DecoderRegistry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DecoderRegistry")
             .field("decoder_count", &self.decoders.len())
             .field("program_ids", &self.decoders.keys().collect::<Vec<_>>())
+            .field("has_fallback", &self.fallback.is_some())
+            .field("conflicts", &self.conflicts)
             .finish()
     }
 }
 
 impl DecoderRegistry {
     /// Create a new registry with built-in decoders
+    ///
+    /// The built-in decoders are:
+    /// - System Program, Stake Program, Vote Program, Compute Budget, SPL Token, Token 2022
+    ///   and SPL Memo (v1 and v2)
+    /// - Ed25519 and Secp256k1 signature verification precompiles
+    /// - Light System Program, Account Compression, Light Token (CToken) and Light Registry
     pub fn new() -> Self {
-        let mut registry = Self {
+        let mut registry = Self::empty();
+        registry.register_builtins();
+        registry
+    }
+
+    /// Create a registry without any decoders
+    pub fn empty() -> Self {
+        Self {
             decoders: HashMap::new(),
-        };
+            names: HashMap::new(),
+            builtins: HashSet::new(),
+            fallback: None,
+            conflicts: Vec::new(),
+        }
+    }
 
-        // Register generic Solana program decoders (always available)
-        registry.register(Box::new(crate::programs::ComputeBudgetInstructionDecoder));
-        registry.register(Box::new(crate::programs::SplTokenInstructionDecoder));
-        registry.register(Box::new(crate::programs::Token2022InstructionDecoder));
-        registry.register(Box::new(crate::programs::SystemInstructionDecoder));
+    /// Register the built-in decoders (see [`DecoderRegistry::new`])
+    ///
+    /// Custom decoders already registered for the same programs are kept.
+    pub fn register_builtins(&mut self) {
+        let builtins: Vec<Box<dyn InstructionDecoder>> = vec![
+            // Generic Solana program decoders
+            Box::new(crate::programs::ComputeBudgetInstructionDecoder),
+            Box::new(crate::programs::SplTokenInstructionDecoder),
+            Box::new(crate::programs::Token2022InstructionDecoder),
+            Box::new(crate::programs::SystemInstructionDecoder),
+            Box::new(crate::programs::StakeInstructionDecoder),
+            Box::new(crate::programs::VoteInstructionDecoder),
+            Box::new(crate::programs::MemoInstructionDecoder::v1()),
+            Box::new(crate::programs::MemoInstructionDecoder::v2()),
+            Box::new(crate::programs::Ed25519InstructionDecoder),
+            Box::new(crate::programs::Secp256k1InstructionDecoder),
+            // Light Protocol decoders
+            Box::new(crate::programs::LightSystemInstructionDecoder),
+            Box::new(crate::programs::AccountCompressionInstructionDecoder),
+            Box::new(crate::programs::CTokenInstructionDecoder),
+            Box::new(crate::programs::RegistryInstructionDecoder),
+        ];
+        for decoder in builtins {
+            let program_id = decoder.program_id();
+            if let Entry::Vacant(entry) = self.decoders.entry(program_id) {
+                entry.insert(Arc::from(decoder));
+                self.builtins.insert(program_id);
+            }
+        }
+    }
+
+    /// Remove the built-in decoders, keeping custom ones
+    pub fn remove_builtins(&mut self) {
+        for program_id in self.builtins.drain() {
+            self.decoders.remove(&program_id);
+        }
+    }
 
-        // Register Light Protocol decoders
-        registry.register(Box::new(crate::programs::LightSystemInstructionDecoder));
-        registry.register(Box::new(
-            crate::programs::AccountCompressionInstructionDecoder,
-        ));
-        registry.register(Box::new(crate::programs::CTokenInstructionDecoder));
-        registry.register(Box::new(crate::programs::RegistryInstructionDecoder));
+    /// Check if any built-in decoder is registered
+    pub fn has_builtins(&self) -> bool {
+        !self.builtins.is_empty()
+    }
 
-        registry
+    /// Check if the decoder for a program ID is a built-in one (not replaced by a custom decoder)
+    pub fn is_builtin(&self, program_id: &Pubkey) -> bool {
+        self.builtins.contains(program_id)
     }
 
     /// Register a custom decoder
-    pub fn register(&mut self, decoder: Box<dyn InstructionDecoder>) {
-        self.decoders.insert(decoder.program_id(), decoder);
+    ///
+    /// Replaces any decoder already registered for the program ID and returns
+    /// whether there was one. Replacing a built-in decoder is the intended way to
+    /// override it; replacing a custom one is recorded in
+    /// [`DecoderRegistry::conflicts`]. Use [`DecoderRegistry::try_register`] to
+    /// reject the latter instead.
+    pub fn register(&mut self, decoder: Box<dyn InstructionDecoder>) -> bool {
+        let program_id = decoder.program_id();
+        self.insert(program_id, Arc::from(decoder))
+    }
+
+    /// Register a custom decoder unless another custom decoder serves its program ID
+    ///
+    /// Built-in decoders are replaced as with [`DecoderRegistry::register`].
+    pub fn try_register(
+        &mut self,
+        decoder: Box<dyn InstructionDecoder>,
+    ) -> Result<(), DecoderConflict> {
+        let program_id = decoder.program_id();
+        if self.has_decoder(&program_id) && !self.builtins.contains(&program_id) {
+            return Err(DecoderConflict {
+                program_id,
+                existing: self
+                    .program_name(&program_id)
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
+        self.insert(program_id, Arc::from(decoder));
+        Ok(())
+    }
+
+    /// Program IDs whose custom decoder was silently replaced by a later registration
+    ///
+    /// Each program ID is listed once, in the order of its first replacement.
+    pub fn conflicts(&self) -> &[Pubkey] {
+        &self.conflicts
+    }
+
+    fn insert(&mut self, program_id: Pubkey, decoder: Arc<dyn InstructionDecoder>) -> bool {
+        let was_builtin = self.builtins.remove(&program_id);
+        self.names.remove(&program_id);
+        let replaced = self.decoders.insert(program_id, decoder).is_some();
+        if replaced && !was_builtin && !self.conflicts.contains(&program_id) {
+            self.conflicts.push(program_id);
+        }
+        replaced
+    }
+
+    /// Register one decoder for several program IDs
+    ///
+    /// The decoder's own `program_id` is ignored. Instructions of each program ID
+    /// report the name given in `name_overrides`, else the decoder's `program_name`.
+    /// Useful for programs sharing an instruction layout, e.g. SPL Token and Token 2022.
+    /// Replaced custom decoders are recorded as with [`DecoderRegistry::register`].
+    pub fn register_aliased(
+        &mut self,
+        program_ids: &[Pubkey],
+        name_overrides: &[(Pubkey, String)],
+        decoder: Box<dyn InstructionDecoder>,
+    ) {
+        let decoder: Arc<dyn InstructionDecoder> = Arc::from(decoder);
+        for program_id in program_ids {
+            self.insert(*program_id, Arc::clone(&decoder));
+        }
+        for (program_id, name) in name_overrides {
+            if program_ids.contains(program_id) {
+                self.names.insert(*program_id, name.clone());
+            }
+        }
+    }
+
+    /// Remove the decoder registered for a program ID
+    ///
+    /// Returns whether there was one. Built-in decoders can be removed too;
+    /// instructions of the program then decode as an unknown program (or with the
+    /// fallback decoder, if set).
+    pub fn unregister(&mut self, program_id: &Pubkey) -> bool {
+        self.builtins.remove(program_id);
+        self.names.remove(program_id);
+        self.decoders.remove(program_id).is_some()
     }
 
     /// Register multiple decoders from a Vec
@@ -61,18 +225,29 @@ impl DecoderRegistry {
         }
     }
 
-    /// Decode an instruction using registered decoders
+    /// Set a decoder for all programs without a registered decoder
+    ///
+    /// The fallback's `program_id` and `program_name` are ignored: instructions it
+    /// decodes keep their "Unknown Program (<id>)" name. See
+    /// [`AnchorFallbackDecoder`](crate::programs::AnchorFallbackDecoder) for a built-in one.
+    pub fn set_fallback(&mut self, decoder: Box<dyn InstructionDecoder>) {
+        self.fallback = Some(decoder);
+    }
+
+    /// Check if a fallback decoder is set
+    pub fn has_fallback(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    /// Decode an instruction using registered decoders, else the fallback decoder
     pub fn decode(
         &self,
         program_id: &Pubkey,
         data: &[u8],
         accounts: &[AccountMeta],
     ) -> Option<(DecodedInstruction, &dyn InstructionDecoder)> {
-        self.decoders.get(program_id).and_then(|decoder| {
-            decoder
-                .decode(data, accounts)
-                .map(|d| (d, decoder.as_ref()))
-        })
+        let decoder = self.get_decoder(program_id).or(self.fallback.as_deref())?;
+        decoder.decode(data, accounts).map(|d| (d, decoder))
     }
 
     /// Get a decoder by program ID
@@ -80,6 +255,22 @@ impl DecoderRegistry {
         self.decoders.get(program_id).map(|d| d.as_ref())
     }
 
+    /// Name of the program served by a registered decoder
+    ///
+    /// Returns the override from [`DecoderRegistry::register_aliased`] if any, else
+    /// the decoder's `program_name`.
+    pub fn program_name(&self, program_id: &Pubkey) -> Option<&str> {
+        match self.names.get(program_id) {
+            Some(name) => Some(name),
+            None => self.get_decoder(program_id).map(|d| d.program_name()),
+        }
+    }
+
+    /// Program IDs of all registered decoders
+    pub fn program_ids(&self) -> impl Iterator<Item = &Pubkey> {
+        self.decoders.keys()
+    }
+
     /// Check if a decoder exists for a program ID
     pub fn has_decoder(&self, program_id: &Pubkey) -> bool {
         self.decoders.contains_key(program_id)
@@ -91,3 +282,167 @@ impl Default for DecoderRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::get_program_name, EnhancedInstructionLog, EnhancedLoggingConfig};
+
+    fn instruction(program_id: Pubkey) -> EnhancedInstructionLog {
+        EnhancedInstructionLog::new(0, program_id, String::new())
+    }
+
+    #[test]
+    fn test_fallback_decoder() {
+        let program_id = Pubkey::new_unique();
+        let config = EnhancedLoggingConfig::default()
+            .with_fallback_decoder(Box::new(crate::AnchorFallbackDecoder));
+        let registry = config.decoder_registry().unwrap();
+        assert!(registry.has_fallback());
+        assert!(!registry.has_decoder(&program_id));
+
+        let mut ix = EnhancedInstructionLog::new(
+            0,
+            program_id,
+            get_program_name(&program_id, Some(registry)),
+        );
+        ix.data = vec![0xaf, 0xaf, 0x6d, 0x1f, 0x0d, 0x98, 0x9b, 0xed, 1, 2];
+        ix.decode(&config);
+        assert_eq!(
+            ix.instruction_name.as_deref(),
+            Some("anchor_disc:0xafaf6d1f0d989bed")
+        );
+        assert_eq!(ix.program_name, format!("Unknown Program ({program_id})"));
+        assert_eq!(
+            ix.decode_warnings(&config),
+            [(
+                crate::DecodeWarningKind::UnknownProgram,
+                format!("no decoder registered for program {program_id}")
+            )]
+        );
+
+        // Programs with a decoder don't use the fallback
+        let mut ix = instruction(Pubkey::default()); // System Program
+        ix.data = vec![0xff; 12];
+        ix.decode(&config);
+        assert_eq!(ix.instruction_name, None);
+    }
+
+    #[test]
+    fn test_register_aliased() {
+        let spl_token = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+        let token_2022 = Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+        let mut registry = DecoderRegistry::empty();
+        registry.register_aliased(
+            &[spl_token, token_2022],
+            &[(token_2022, "Token 2022 (aliased)".to_string())],
+            Box::new(crate::programs::SplTokenInstructionDecoder),
+        );
+        assert_eq!(get_program_name(&spl_token, Some(&registry)), "SPL Token");
+        assert_eq!(
+            get_program_name(&token_2022, Some(&registry)),
+            "Token 2022 (aliased)"
+        );
+
+        let config = EnhancedLoggingConfig::default().with_shared_registry(Arc::new(registry));
+        for (program_id, name) in [
+            (spl_token, "SPL Token"),
+            (token_2022, "Token 2022 (aliased)"),
+        ] {
+            let mut ix = instruction(program_id);
+            ix.accounts = vec![
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), true),
+            ];
+            ix.data = [[3u8].as_slice(), &1_000u64.to_le_bytes()].concat();
+            ix.decode(&config);
+            assert_eq!(ix.instruction_name.as_deref(), Some("Transfer"));
+            assert_eq!(ix.program_name, name);
+        }
+
+        // Re-registering one of the IDs drops its override
+        let mut registry = DecoderRegistry::empty();
+        registry.register_aliased(
+            &[spl_token, token_2022],
+            &[(token_2022, "Token 2022 (aliased)".to_string())],
+            Box::new(crate::programs::SplTokenInstructionDecoder),
+        );
+        registry.register(Box::new(crate::programs::Token2022InstructionDecoder));
+        assert_eq!(registry.program_name(&token_2022), Some("Token 2022"));
+    }
+
+    #[test]
+    fn test_register_reports_replaced_decoders() {
+        let system_program = Pubkey::default();
+        let spl_token = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+        // Overriding a built-in decoder is not a conflict
+        let mut registry = DecoderRegistry::new();
+        assert!(registry.register(Box::new(crate::programs::SystemInstructionDecoder)));
+        assert!(registry.conflicts().is_empty());
+
+        // Replacing a custom decoder is
+        assert!(registry.register(Box::new(crate::programs::SystemInstructionDecoder)));
+        assert!(registry.register(Box::new(crate::programs::SystemInstructionDecoder)));
+        assert_eq!(registry.conflicts(), [system_program]);
+
+        let err = registry
+            .try_register(Box::new(crate::programs::SystemInstructionDecoder))
+            .unwrap_err();
+        assert_eq!(err.program_id, system_program);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "a decoder for System Program is already registered for program {system_program}"
+            )
+        );
+
+        // try_register still replaces built-in decoders
+        assert!(registry
+            .try_register(Box::new(crate::programs::SplTokenInstructionDecoder))
+            .is_ok());
+        assert!(registry
+            .try_register(Box::new(crate::programs::SplTokenInstructionDecoder))
+            .is_err());
+
+        let mut registry = DecoderRegistry::empty();
+        assert!(!registry.register(Box::new(crate::programs::SplTokenInstructionDecoder)));
+        registry.register_aliased(
+            &[spl_token],
+            &[],
+            Box::new(crate::programs::SplTokenInstructionDecoder),
+        );
+        assert_eq!(registry.conflicts(), [spl_token]);
+    }
+
+    #[test]
+    fn test_unregister_decoder() {
+        let program_id = Pubkey::new_unique();
+        let mut registry = DecoderRegistry::empty();
+        registry.register_aliased(
+            &[program_id],
+            &[(program_id, "Custom".to_string())],
+            Box::new(crate::programs::SystemInstructionDecoder),
+        );
+        assert!(registry.has_decoder(&program_id));
+        assert!(!registry.is_builtin(&program_id));
+
+        assert!(registry.unregister(&program_id));
+        assert!(!registry.has_decoder(&program_id));
+        assert_eq!(registry.program_name(&program_id), None);
+        assert_eq!(
+            get_program_name(&program_id, Some(&registry)),
+            format!("Unknown Program ({program_id})")
+        );
+        assert!(!registry.unregister(&program_id));
+
+        // Built-in decoders can be removed as well
+        let mut registry = DecoderRegistry::new();
+        assert!(registry.is_builtin(&Pubkey::default()));
+        assert!(registry.unregister(&Pubkey::default()));
+        assert!(!registry.has_decoder(&Pubkey::default()));
+        assert!(!registry.is_builtin(&Pubkey::default()));
+        assert!(registry.has_builtins());
+    }
+}
@@ -4,49 +4,215 @@
 //! and transaction logging. These types are independent of any test framework
 //! (LiteSVM, etc.) and can be used in standalone tools.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use solana_instruction::AccountMeta;
 use solana_pubkey::Pubkey;
 use solana_signature::Signature;
 
-use crate::{DecodedInstruction, DecoderRegistry, EnhancedLoggingConfig};
+use crate::{
+    log_tree::{flatten_log_tree, parse_program_log_tree, LogNode},
+    AccountCountMismatch, ComputeBudgetInstructionDecoder, DecodedField, DecodedInstruction,
+    DecoderRegistry, EnhancedLoggingConfig, InstructionDecoder, PdaSeeds,
+};
 
 /// Pre and post transaction account state snapshot
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccountStateSnapshot {
     pub lamports_before: u64,
     pub lamports_after: u64,
     pub data_len_before: usize,
     pub data_len_after: usize,
+    /// Owner before the transaction (System Program's default pubkey for accounts
+    /// that did not exist yet)
     pub owner: Pubkey,
+    /// Owner after the transaction, e.g. the program an account was created for
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub owner_after: Pubkey,
+}
+
+/// Bytes of account metadata charged for rent on top of the account data
+const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+/// Default rent rate in lamports per byte-year
+const LAMPORTS_PER_BYTE_YEAR: u64 = 3_480;
+/// Years of rent an account must hold to be rent-exempt
+const EXEMPTION_THRESHOLD_YEARS: u64 = 2;
+
+/// Minimum balance for an account with `data_len` bytes to be rent-exempt,
+/// using the default rent parameters
+pub fn rent_exempt_minimum(data_len: usize) -> u64 {
+    (ACCOUNT_STORAGE_OVERHEAD + data_len as u64)
+        * LAMPORTS_PER_BYTE_YEAR
+        * EXEMPTION_THRESHOLD_YEARS
+}
+
+impl AccountStateSnapshot {
+    /// Rent-exempt minimum balance for the account's data length after the transaction
+    pub fn rent_exempt_minimum(&self) -> u64 {
+        rent_exempt_minimum(self.data_len_after)
+    }
+
+    /// Whether the account is rent-exempt after the transaction
+    ///
+    /// Closed accounts (no lamports left) count as rent-exempt: nothing is left to reap.
+    pub fn is_rent_exempt(&self) -> bool {
+        self.lamports_after == 0 || self.lamports_after >= self.rent_exempt_minimum()
+    }
 }
 
 /// Enhanced transaction log containing all formatting information
-#[derive(Debug, Clone)]
-pub struct EnhancedTransactionLog {
+///
+/// `D` holds the instruction data: owned (`Vec<u8>`) by default, or borrowed
+/// from the transaction (`&[u8]`) as returned by `decode_transaction_borrowed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EnhancedTransactionLog<D = Vec<u8>> {
     pub signature: Signature,
+    /// Slot the transaction executed at (LiteSVM: from the clock sysvar, 0 until warped)
     pub slot: u64,
+    /// Unix timestamp of the clock the transaction executed with, when known
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub block_time: Option<i64>,
     pub status: TransactionStatus,
+    /// Total fee in lamports: `base_fee` plus `prioritization_fee`
     pub fee: u64,
+    /// Signature fee in lamports (5,000 per signature), see [`EnhancedTransactionLog::record_fees`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub base_fee: u64,
+    /// Priority fee in lamports paid for the requested compute unit price
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub prioritization_fee: u64,
     pub compute_used: u64,
     pub compute_total: u64,
-    pub instructions: Vec<EnhancedInstructionLog>,
+    pub instructions: Vec<EnhancedInstructionLog<D>>,
     pub account_changes: Vec<AccountChange>,
     pub program_logs_pretty: String,
     pub light_events: Vec<LightProtocolEvent>,
     /// Pre and post transaction account state snapshots (keyed by pubkey)
     pub account_states: Option<HashMap<Pubkey, AccountStateSnapshot>>,
+    /// Non-fatal problems noticed while decoding (see [`DecodeWarning`])
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub warnings: Vec<DecodeWarning>,
+    /// Accounts identified as registered PDAs (see [`EnhancedTransactionLog::label_pdas`])
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pdas: HashMap<Pubkey, PdaMatch>,
+    /// Index (0-based, as reported by the runtime) of the top-level instruction that
+    /// failed, from `InstructionError(index, _)`; snapshots render it 1-based like
+    /// [`EnhancedInstructionLog::location`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub failed_instruction_index: Option<usize>,
+    /// Compute unit price in micro-lamports, from `SetComputeUnitPrice`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compute_unit_price: Option<u64>,
+    /// Compute unit limit, from `SetComputeUnitLimit`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compute_unit_limit: Option<u32>,
+    /// Caller-supplied annotation such as "setup" or "attack attempt", shown in the
+    /// header (see `TransactionLogger::send_transaction_labeled`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: Option<String>,
+    /// Signatures the message header requires (`num_required_signatures`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub signatures_required: usize,
+    /// Signatures the transaction carries
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub signatures_provided: usize,
+    /// Whether the transaction carries exactly the signatures its message requires;
+    /// false flags a missing (or extra) signer, see [`EnhancedTransactionLog::record_signature_count`]
+    #[cfg_attr(feature = "serde", serde(default = "signature_count_ok_default"))]
+    pub signature_count_ok: bool,
+    /// Set when the transaction advances a durable nonce, whose value then takes
+    /// the place of the recent blockhash (see [`EnhancedTransactionLog::detect_durable_nonce`])
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub durable_nonce: Option<DurableNonce>,
+    /// Size of the serialized message in bytes, see [`EnhancedTransactionLog::transaction_size`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub total_message_size: Option<usize>,
+}
+
+#[cfg(feature = "serde")]
+fn signature_count_ok_default() -> bool {
+    true
 }
 
 impl EnhancedTransactionLog {
     /// Create a new empty transaction log with basic info
     pub fn new(signature: Signature, slot: u64) -> Self {
+        Self::empty(signature, slot)
+    }
+
+    /// Serialize to a compact binary blob (bincode) for caching decoded transactions
+    ///
+    /// Requires the `serde` feature, which also pulls in `bincode`.
+    #[cfg(all(feature = "serde", not(target_os = "solana")))]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    /// Deserialize a log previously produced by [`EnhancedTransactionLog::to_bytes`]
+    #[cfg(all(feature = "serde", not(target_os = "solana")))]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+
+    /// Decode every instruction (including inner instructions) again with `config`
+    ///
+    /// Uses the captured instruction data and accounts, so decoders registered after
+    /// the transaction ran apply without re-running it. Program names, decoded
+    /// instructions, decode warnings and PDA labels are replaced; everything read from
+    /// the execution result (status, compute units, logs) is kept.
+    pub fn redecode(&mut self, config: &EnhancedLoggingConfig) {
+        fn walk(
+            instructions: &mut [EnhancedInstructionLog],
+            path: &mut Vec<usize>,
+            config: &EnhancedLoggingConfig,
+            warnings: &mut Vec<DecodeWarning>,
+        ) {
+            for (i, instruction) in instructions.iter_mut().enumerate() {
+                path.push(i);
+                instruction.program_name =
+                    get_program_name(&instruction.program_id, config.decoder_registry());
+                instruction.instruction_name = None;
+                instruction.decoded_instruction = None;
+                instruction.account_count_mismatch = None;
+                instruction.discriminator = None;
+                instruction.decode(config);
+                warnings.extend(instruction.decode_warnings(config).into_iter().map(
+                    |(kind, message)| DecodeWarning::new(path.clone(), message).with_kind(kind),
+                ));
+                walk(&mut instruction.inner_instructions, path, config, warnings);
+                path.pop();
+            }
+        }
+
+        self.warnings.retain(|warning| !warning.kind.is_decode());
+        walk(
+            &mut self.instructions,
+            &mut Vec::new(),
+            config,
+            &mut self.warnings,
+        );
+        // Stable: other warnings stay ahead of decode warnings for the same instruction
+        self.warnings.sort_by(|a, b| a.path.cmp(&b.path));
+        self.pdas.clear();
+        self.label_pdas(&config.pda_seeds);
+        config.notify_instructions(&self.instructions);
+    }
+}
+
+impl<D: AsRef<[u8]>> EnhancedTransactionLog<D> {
+    pub(crate) fn empty(signature: Signature, slot: u64) -> Self {
         Self {
             signature,
             slot,
+            block_time: None,
             status: TransactionStatus::Unknown,
             fee: 0,
+            base_fee: 0,
+            prioritization_fee: 0,
             compute_used: 0,
             compute_total: 1_400_000,
             instructions: Vec::new(),
@@ -54,16 +220,699 @@ impl EnhancedTransactionLog {
             program_logs_pretty: String::new(),
             light_events: Vec::new(),
             account_states: None,
+            warnings: Vec::new(),
+            pdas: HashMap::new(),
+            failed_instruction_index: None,
+            compute_unit_price: None,
+            compute_unit_limit: None,
+            label: None,
+            signatures_required: 0,
+            signatures_provided: 0,
+            signature_count_ok: true,
+            durable_nonce: None,
+            total_message_size: None,
+        }
+    }
+
+    /// Copy borrowed instruction data into an owned log (e.g. for formatting)
+    pub fn into_owned(self) -> EnhancedTransactionLog {
+        EnhancedTransactionLog {
+            signature: self.signature,
+            slot: self.slot,
+            block_time: self.block_time,
+            status: self.status,
+            fee: self.fee,
+            base_fee: self.base_fee,
+            prioritization_fee: self.prioritization_fee,
+            compute_used: self.compute_used,
+            compute_total: self.compute_total,
+            instructions: self
+                .instructions
+                .into_iter()
+                .map(EnhancedInstructionLog::into_owned)
+                .collect(),
+            account_changes: self.account_changes,
+            program_logs_pretty: self.program_logs_pretty,
+            light_events: self.light_events,
+            account_states: self.account_states,
+            warnings: self.warnings,
+            pdas: self.pdas,
+            failed_instruction_index: self.failed_instruction_index,
+            compute_unit_price: self.compute_unit_price,
+            compute_unit_limit: self.compute_unit_limit,
+            label: self.label,
+            signatures_required: self.signatures_required,
+            signatures_provided: self.signatures_provided,
+            signature_count_ok: self.signature_count_ok,
+            durable_nonce: self.durable_nonce,
+            total_message_size: self.total_message_size,
+        }
+    }
+
+    /// Set the fee from the signature count and the Compute Budget instructions
+    ///
+    /// The base fee is 5,000 lamports per signature; the prioritization fee is
+    /// [`EnhancedTransactionLog::priority_fee`], so call this after
+    /// [`EnhancedTransactionLog::read_compute_budget`].
+    pub fn record_fees(&mut self, signatures: usize) {
+        self.base_fee = signatures as u64 * LAMPORTS_PER_SIGNATURE;
+        self.prioritization_fee = self.priority_fee().unwrap_or(0);
+        self.fee = self.base_fee.saturating_add(self.prioritization_fee);
+    }
+
+    /// Record the required and provided signature counts, warning when they differ
+    ///
+    /// A transaction with fewer signatures than `num_required_signatures` usually
+    /// means a required signer was not added when signing.
+    pub fn record_signature_count(&mut self, required: usize, provided: usize) {
+        self.signatures_required = required;
+        self.signatures_provided = provided;
+        self.signature_count_ok = required == provided;
+        if !self.signature_count_ok {
+            self.warnings.push(
+                DecodeWarning::new(
+                    Vec::new(),
+                    format!(
+                        "message requires {} signature(s), transaction has {}",
+                        required, provided
+                    ),
+                )
+                .with_kind(DecodeWarningKind::SignatureCount),
+            );
         }
     }
+
+    /// Size of the serialized transaction in bytes: the message plus its signatures
+    ///
+    /// Counts the signatures the message requires (or the ones provided, if more), so
+    /// an unsigned message previewed with `decode_message` gets its signed size.
+    /// None when the message size is unknown (logs not decoded from a message).
+    pub fn transaction_size(&self) -> Option<usize> {
+        let signatures = self.signatures_required.max(self.signatures_provided);
+        self.total_message_size
+            .map(|message| compact_u16_len(signatures) + signatures * 64 + message)
+    }
+
+    /// Whether the transaction size is within 10% of (or over) [`PACKET_DATA_SIZE`]
+    pub fn near_packet_limit(&self) -> bool {
+        self.transaction_size()
+            .is_some_and(|size| size * 100 >= PACKET_DATA_SIZE * PACKET_SIZE_WARNING_PERCENT)
+    }
+
+    /// Record the serialized message size, warning when the transaction gets close
+    /// to the packet limit
+    ///
+    /// Call after the signature counts are known (see
+    /// [`EnhancedTransactionLog::record_signature_count`]).
+    pub fn record_message_size(&mut self, total_message_size: usize) {
+        self.total_message_size = Some(total_message_size);
+        if let (true, Some(size)) = (self.near_packet_limit(), self.transaction_size()) {
+            let relation = if size > PACKET_DATA_SIZE {
+                "exceeds"
+            } else {
+                "is close to"
+            };
+            self.warnings.push(
+                DecodeWarning::new(
+                    Vec::new(),
+                    format!(
+                        "transaction is {} bytes, which {} the {}-byte packet limit",
+                        size, relation, PACKET_DATA_SIZE
+                    ),
+                )
+                .with_kind(DecodeWarningKind::TransactionSize),
+            );
+        }
+    }
+
+    /// Record the durable nonce if the first instruction is System `AdvanceNonceAccount`
+    ///
+    /// Such a transaction carries the nonce stored in the nonce account in its
+    /// recent blockhash field, so `recent_blockhash` is recorded as the nonce
+    /// along with the nonce account and authority.
+    pub fn detect_durable_nonce(&mut self, recent_blockhash: impl ToString) {
+        let Some(first) = self.instructions.first() else {
+            return;
+        };
+        let advances_nonce = first.program_id == Pubkey::default()
+            && first
+                .data
+                .as_ref()
+                .starts_with(&ADVANCE_NONCE_DISCRIMINATOR);
+        if let (true, Some(nonce_account), Some(nonce_authority)) = (
+            advances_nonce,
+            first.accounts.first(),
+            first.accounts.get(2),
+        ) {
+            self.durable_nonce = Some(DurableNonce {
+                nonce: recent_blockhash.to_string(),
+                nonce_account: nonce_account.pubkey,
+                nonce_authority: nonce_authority.pubkey,
+            });
+        }
+    }
+
+    /// Record which accounts of the instruction tree match a known PDA derivation
+    pub fn label_pdas(&mut self, known: &[PdaSeeds]) {
+        fn walk<D>(
+            instructions: &[EnhancedInstructionLog<D>],
+            known: &[PdaSeeds],
+            pdas: &mut HashMap<Pubkey, PdaMatch>,
+        ) {
+            for instruction in instructions {
+                for account in &instruction.accounts {
+                    if let Some((seeds, bump)) = known
+                        .iter()
+                        .find_map(|seeds| Some((seeds, seeds.matches(&account.pubkey)?)))
+                    {
+                        pdas.insert(
+                            account.pubkey,
+                            PdaMatch {
+                                label: seeds.label.clone(),
+                                bump,
+                            },
+                        );
+                    }
+                }
+                walk(&instruction.inner_instructions, known, pdas);
+            }
+        }
+
+        if !known.is_empty() {
+            walk(&self.instructions, known, &mut self.pdas);
+        }
+    }
+
+    /// Aggregate statistics over the full instruction tree (top-level + inner)
+    pub fn stats(&self) -> TransactionStats {
+        fn walk<D>(
+            instructions: &[EnhancedInstructionLog<D>],
+            depth: usize,
+            stats: &mut TransactionStats,
+            programs: &mut HashSet<Pubkey>,
+        ) {
+            for instruction in instructions {
+                stats.total_instructions += 1;
+                stats.max_depth = stats.max_depth.max(depth);
+                programs.insert(instruction.program_id);
+                walk(&instruction.inner_instructions, depth + 1, stats, programs);
+            }
+        }
+
+        let mut stats = TransactionStats::default();
+        let mut programs = HashSet::new();
+        walk(&self.instructions, 0, &mut stats, &mut programs);
+        stats.distinct_programs = programs.len();
+        stats
+    }
+
+    /// Every account with the instructions it appears in, in order of first appearance
+    ///
+    /// Walks top-level and inner instructions in execution order. Use
+    /// [`AccountRoles::has_inconsistent_access`] to find accounts passed as signer or
+    /// writable to some instructions but not others. Logs decoded from a transaction
+    /// resolve privileges from the message, so mismatches only show up in logs built
+    /// by hand or with instruction-level account metas.
+    pub fn account_roles(&self) -> Vec<AccountRoles> {
+        fn walk<D: AsRef<[u8]>>(
+            instructions: &[EnhancedInstructionLog<D>],
+            parent: &[usize],
+            roles: &mut Vec<AccountRoles>,
+            positions: &mut HashMap<Pubkey, usize>,
+        ) {
+            for (position, instruction) in instructions.iter().enumerate() {
+                let mut path = parent.to_vec();
+                path.push(position);
+                for (i, account) in instruction.accounts.iter().enumerate() {
+                    let usage = AccountUsage {
+                        path: path.clone(),
+                        role: instruction.account_role(i).map(str::to_string),
+                        is_signer: account.is_signer,
+                        is_writable: account.is_writable,
+                    };
+                    // Index into `roles` by pubkey, keeping first-appearance order
+                    match positions.get(&account.pubkey) {
+                        Some(&index) => roles[index].usages.push(usage),
+                        None => {
+                            positions.insert(account.pubkey, roles.len());
+                            roles.push(AccountRoles {
+                                pubkey: account.pubkey,
+                                usages: vec![usage],
+                            });
+                        }
+                    }
+                }
+                walk(&instruction.inner_instructions, &path, roles, positions);
+            }
+        }
+
+        let mut roles = Vec::new();
+        walk(&self.instructions, &[], &mut roles, &mut HashMap::new());
+        roles
+    }
+
+    /// Every pubkey the transaction references, in sorted order
+    ///
+    /// Collects the program ids and accounts of all instructions (top-level and
+    /// inner), the accounts in `account_changes` and the keys of `account_states`.
+    /// Useful as an account watch list or to seed a label registry.
+    pub fn all_pubkeys(&self) -> BTreeSet<Pubkey> {
+        fn walk<D>(instructions: &[EnhancedInstructionLog<D>], pubkeys: &mut BTreeSet<Pubkey>) {
+            for instruction in instructions {
+                pubkeys.insert(instruction.program_id);
+                pubkeys.extend(instruction.accounts.iter().map(|account| account.pubkey));
+                walk(&instruction.inner_instructions, pubkeys);
+            }
+        }
+
+        let mut pubkeys = BTreeSet::new();
+        walk(&self.instructions, &mut pubkeys);
+        pubkeys.extend(self.account_changes.iter().map(|change| change.pubkey));
+        if let Some(states) = &self.account_states {
+            pubkeys.extend(states.keys());
+        }
+        pubkeys
+    }
+
+    /// Fill in each instruction's `compute_consumed` from the runtime's program logs
+    ///
+    /// The logs are parsed with [`parse_program_log_tree`]; each invocation's
+    /// consumed units are attributed to the instruction it ran. Invocations are
+    /// matched to the instruction tree in order by program id and depth.
+    /// Instructions without an invoke line (precompiles) or a consumed line
+    /// (builtin programs) keep `None`.
+    pub fn attribute_compute_units(&mut self, logs: &[String]) {
+        fn assign<'a, D>(
+            instructions: &mut [EnhancedInstructionLog<D>],
+            invocations: &mut std::iter::Peekable<impl Iterator<Item = &'a LogNode>>,
+        ) {
+            for instruction in instructions {
+                if let Some(node) = invocations.peek() {
+                    if node.program_id == instruction.program_id.to_string()
+                        && node.depth == instruction.depth
+                    {
+                        instruction.compute_consumed = node.consumed;
+                        invocations.next();
+                    }
+                }
+                assign(&mut instruction.inner_instructions, invocations);
+            }
+        }
+
+        let tree = parse_program_log_tree(logs);
+        assign(
+            &mut self.instructions,
+            &mut flatten_log_tree(&tree).into_iter().peekable(),
+        );
+    }
+
+    /// Read the compute unit price and limit from top-level Compute Budget instructions
+    ///
+    /// An explicit limit also becomes `compute_total`.
+    pub fn read_compute_budget(&mut self) {
+        let compute_budget = ComputeBudgetInstructionDecoder.program_id();
+        for instruction in &self.instructions {
+            if instruction.program_id != compute_budget {
+                continue;
+            }
+            match instruction.data.as_ref().split_first() {
+                Some((2, units)) => {
+                    if let Ok(units) = <[u8; 4]>::try_from(units) {
+                        self.compute_unit_limit = Some(u32::from_le_bytes(units));
+                    }
+                }
+                Some((3, micro_lamports)) => {
+                    if let Ok(micro_lamports) = <[u8; 8]>::try_from(micro_lamports) {
+                        self.compute_unit_price = Some(u64::from_le_bytes(micro_lamports));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(limit) = self.compute_unit_limit {
+            self.compute_total = limit as u64;
+        }
+    }
+
+    /// Priority fee in lamports: compute unit price times limit, rounded up
+    ///
+    /// Without `SetComputeUnitLimit` the runtime default limit applies: 200,000 CU
+    /// per non-Compute-Budget instruction, at most 1,400,000. `None` without a price.
+    pub fn priority_fee(&self) -> Option<u64> {
+        let price = self.compute_unit_price?;
+        let limit = self.compute_unit_limit.map_or_else(
+            || {
+                let compute_budget = ComputeBudgetInstructionDecoder.program_id();
+                let instructions = self
+                    .instructions
+                    .iter()
+                    .filter(|ix| ix.program_id != compute_budget)
+                    .count() as u64;
+                (instructions * 200_000).min(1_400_000)
+            },
+            u64::from,
+        );
+        let fee = (price as u128 * limit as u128).div_ceil(1_000_000);
+        Some(fee.try_into().unwrap_or(u64::MAX))
+    }
+
+    /// Compute units used as a percentage of the `SetComputeUnitLimit` limit
+    ///
+    /// `None` without an explicit limit (see [`EnhancedTransactionLog::read_compute_budget`]),
+    /// where `compute_total` is only the runtime default.
+    pub fn compute_utilization(&self) -> Option<f64> {
+        let limit = self.compute_unit_limit.filter(|&limit| limit > 0)?;
+        Some(self.compute_used as f64 * 100.0 / limit as f64)
+    }
+
+    /// Whether the transaction used all of its `SetComputeUnitLimit` limit
+    ///
+    /// A transaction that reaches its limit runs out of compute and fails.
+    pub fn compute_limit_reached(&self) -> bool {
+        self.compute_unit_limit
+            .is_some_and(|limit| self.compute_used >= limit as u64)
+    }
+
+    /// Net change in account data size over the transaction, in bytes
+    ///
+    /// Sum of `data_len_after - data_len_before` over all captured account
+    /// states; positive when space was allocated, `0` without account states.
+    pub fn net_data_len_change(&self) -> i64 {
+        self.account_states.as_ref().map_or(0, |states| {
+            states
+                .values()
+                .map(|state| state.data_len_after as i64 - state.data_len_before as i64)
+                .sum()
+        })
+    }
+
+    /// Compute units consumed by the transaction
+    pub fn compute_used(&self) -> u64 {
+        self.compute_used
+    }
+
+    /// Compute units consumed by `program_id`, including the CPIs it makes
+    ///
+    /// Sums the `compute_consumed` (see [`EnhancedTransactionLog::attribute_compute_units`])
+    /// of every invocation of the program that is not nested in another invocation
+    /// of it, so recursive CPIs are not counted twice. Invocations without a
+    /// consumed log line count as 0.
+    pub fn program_compute_units(&self, program_id: &Pubkey) -> u64 {
+        fn walk<D>(instructions: &[EnhancedInstructionLog<D>], program_id: &Pubkey) -> u64 {
+            instructions
+                .iter()
+                .map(|instruction| {
+                    if instruction.program_id == *program_id {
+                        instruction.compute_consumed.unwrap_or(0)
+                    } else {
+                        walk(&instruction.inner_instructions, program_id)
+                    }
+                })
+                .sum()
+        }
+        walk(&self.instructions, program_id)
+    }
+
+    /// Panic if the transaction consumed more than `max` compute units
+    #[track_caller]
+    pub fn assert_compute_under(&self, max: u64) {
+        assert_compute_budget("transaction", self.compute_used, max);
+    }
+
+    /// Panic if `program_id` consumed more than `max` compute units
+    ///
+    /// See [`EnhancedTransactionLog::program_compute_units`] for how units are attributed.
+    #[track_caller]
+    pub fn assert_program_compute_under(&self, program_id: &Pubkey, max: u64) {
+        fn find<'a, D>(
+            instructions: &'a [EnhancedInstructionLog<D>],
+            program_id: &Pubkey,
+        ) -> Option<&'a str> {
+            instructions.iter().find_map(|instruction| {
+                if instruction.program_id == *program_id {
+                    Some(instruction.program_name.as_str())
+                } else {
+                    find(&instruction.inner_instructions, program_id)
+                }
+            })
+        }
+        let subject = match find(&self.instructions, program_id) {
+            Some(name) => format!("{name} ({program_id})"),
+            None => program_id.to_string(),
+        };
+        assert_compute_budget(&subject, self.program_compute_units(program_id), max);
+    }
+
+    /// Heuristic "main" program of the transaction, for labeling and grouping
+    ///
+    /// Only top-level instructions are considered. Compute Budget and Associated
+    /// Token Account instructions are setup noise and are skipped unless nothing
+    /// else is left. Among the remaining instructions the one with the most
+    /// decoded fields wins; ties (including undecoded instructions) go to the
+    /// later instruction, so a plain transaction resolves to its last instruction.
+    pub fn primary_program(&self) -> Option<(Pubkey, &str)> {
+        let field_count = |ix: &&EnhancedInstructionLog<D>| {
+            ix.decoded_instruction
+                .as_ref()
+                .map_or(0, |decoded| decoded.fields.len())
+        };
+        self.instructions
+            .iter()
+            .filter(|ix| !is_setup_program(&ix.program_id))
+            .max_by_key(field_count)
+            .or_else(|| self.instructions.last())
+            .map(|ix| (ix.program_id, ix.program_name.as_str()))
+    }
+}
+
+/// Programs whose top-level instructions only prepare a transaction
+static SETUP_PROGRAMS: &[&str] = &[
+    "ComputeBudget111111111111111111111111111111",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+];
+
+fn is_setup_program(program_id: &Pubkey) -> bool {
+    let program_id = program_id.to_string();
+    SETUP_PROGRAMS.contains(&program_id.as_str())
+}
+
+/// Whether `program_id` is a token program whose `amount` fields are token base
+/// units, i.e. one of the SPL Token, Token-2022 or Light Token decoders' programs
+fn is_token_program(program_id: &Pubkey) -> bool {
+    [
+        crate::programs::SplTokenInstructionDecoder.program_id(),
+        crate::programs::Token2022InstructionDecoder.program_id(),
+        crate::programs::CTokenInstructionDecoder.program_id(),
+    ]
+    .contains(program_id)
+}
+
+/// Render token base units with `decimals` fractional digits (`1000000`, 6 -> `1.000000`)
+fn format_token_amount(amount: u64, decimals: u8) -> String {
+    let digits = format!("{:0>width$}", amount, width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// What a [`DecodeWarning`] is about
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DecodeWarningKind {
+    /// No decoder is registered for the instruction's program
+    UnknownProgram,
+    /// The program's decoder could not decode the instruction data
+    DecodeFailed,
+    /// Some fields of the decoded instruction failed to decode
+    PartiallyDecoded,
+    /// Fewer accounts than the decoded instruction expects
+    AccountCountMismatch,
+    /// A program id or account index outside the transaction's account keys
+    UnresolvedIndex,
+    /// Signature count differs from the one the message requires
+    SignatureCount,
+    /// Transaction close to or over the packet size limit
+    TransactionSize,
+    /// CPIs in the program logs that weren't recorded as inner instructions
+    UnrecordedCpi,
+    /// Any other problem, e.g. warnings added by hand
+    #[default]
+    Other,
+}
+
+impl DecodeWarningKind {
+    /// Whether the warning comes from decoding the instruction, i.e. from
+    /// [`EnhancedInstructionLog::decode_warnings`], and is replaced by
+    /// [`EnhancedTransactionLog::redecode`]
+    pub fn is_decode(self) -> bool {
+        matches!(
+            self,
+            Self::UnknownProgram
+                | Self::DecodeFailed
+                | Self::PartiallyDecoded
+                | Self::AccountCountMismatch
+        )
+    }
+}
+
+/// Non-fatal problem noticed while decoding a transaction
+///
+/// Decoding never fails; fallbacks such as unknown programs, undecodable
+/// instruction data or unresolvable account indices are recorded as warnings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DecodeWarning {
+    /// Position of the affected instruction: top-level index followed by inner
+    /// instruction indices (0-based). Empty for transaction-level warnings.
+    pub path: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: DecodeWarningKind,
+    pub message: String,
+}
+
+impl DecodeWarning {
+    /// Warning of kind [`DecodeWarningKind::Other`], see [`DecodeWarning::with_kind`]
+    pub fn new(path: Vec<usize>, message: impl Into<String>) -> Self {
+        Self {
+            path,
+            kind: DecodeWarningKind::Other,
+            message: message.into(),
+        }
+    }
+
+    /// Set what the warning is about
+    pub fn with_kind(mut self, kind: DecodeWarningKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Human-readable location, e.g. "instruction #1.2" (1-based)
+    pub fn location(&self) -> String {
+        if self.path.is_empty() {
+            return "transaction".to_string();
+        }
+        format!("instruction {}", instruction_location(&self.path))
+    }
+}
+
+/// 1-based position of the instruction at `path`, e.g. "#1.3.2" for `[0, 2, 1]`
+///
+/// The one way instructions are named in warnings, account roles, snapshots,
+/// diffs and formatted headers.
+pub(crate) fn instruction_location(path: &[usize]) -> String {
+    let position: Vec<String> = path.iter().map(|i| (i + 1).to_string()).collect();
+    format!("#{}", position.join("."))
+}
+
+/// Base fee the runtime charges per transaction signature
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Maximum size of a serialized transaction (IPv6 MTU minus headers)
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Transactions at or above this percentage of [`PACKET_DATA_SIZE`] get a warning
+const PACKET_SIZE_WARNING_PERCENT: usize = 90;
+
+/// Bytes of the compact-u16 length prefix the wire format uses for `len`
+pub(crate) fn compact_u16_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+/// System program `AdvanceNonceAccount` instruction index (u32 LE)
+const ADVANCE_NONCE_DISCRIMINATOR: [u8; 4] = [4, 0, 0, 0];
+
+/// Durable nonce a transaction was built with
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DurableNonce {
+    /// Nonce value (base58), carried in the message's recent blockhash field
+    pub nonce: String,
+    pub nonce_account: Pubkey,
+    pub nonce_authority: Pubkey,
+}
+
+/// Account identified as a known PDA (registered via [`PdaSeeds`])
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PdaMatch {
+    pub label: String,
+    pub bump: u8,
+}
+
+/// Panic with the units over budget if `used` exceeds `max`
+#[track_caller]
+pub(crate) fn assert_compute_budget(subject: &str, used: u64, max: u64) {
+    assert!(
+        used <= max,
+        "compute budget exceeded: {subject} used {used} CU, budget is {max} CU ({} CU over)",
+        used - max
+    );
+}
+
+/// Aggregate invoke statistics for a transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransactionStats {
+    /// Deepest CPI level reached (0 = only top-level instructions)
+    pub max_depth: usize,
+    /// Number of instructions including all inner instructions
+    pub total_instructions: usize,
+    /// Number of distinct programs invoked anywhere in the transaction
+    pub distinct_programs: usize,
+}
+
+/// One appearance of an account in an instruction, see [`EnhancedTransactionLog::account_roles`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountUsage {
+    /// Position of the instruction, as in [`DecodeWarning::path`]
+    pub path: Vec<usize>,
+    /// Account name from the instruction's decoder, if any
+    pub role: Option<String>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountUsage {
+    /// Human-readable instruction position, e.g. "#1.2" (1-based)
+    pub fn location(&self) -> String {
+        instruction_location(&self.path)
+    }
+}
+
+/// All appearances of one account across a transaction's instructions
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountRoles {
+    pub pubkey: Pubkey,
+    pub usages: Vec<AccountUsage>,
+}
+
+impl AccountRoles {
+    /// Whether the account's signer or writable flag differs between instructions
+    pub fn has_inconsistent_access(&self) -> bool {
+        self.usages.windows(2).any(|pair| {
+            pair[0].is_signer != pair[1].is_signer || pair[0].is_writable != pair[1].is_writable
+        })
+    }
 }
 
 /// Transaction execution status
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransactionStatus {
     Success,
     Failed(String),
     Unknown,
+    /// Decoded from a message that was not sent (see `litesvm::decode_message`)
+    NotExecuted,
 }
 
 impl TransactionStatus {
@@ -72,42 +921,102 @@ impl TransactionStatus {
             TransactionStatus::Success => "Success".to_string(),
             TransactionStatus::Failed(err) => format!("Failed: {}", err),
             TransactionStatus::Unknown => "Unknown".to_string(),
+            TransactionStatus::NotExecuted => "Not executed".to_string(),
         }
     }
 }
 
 /// Enhanced instruction log with hierarchy and parsing
-#[derive(Debug, Clone)]
-pub struct EnhancedInstructionLog {
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EnhancedInstructionLog<D = Vec<u8>> {
     pub index: usize,
     pub program_id: Pubkey,
     pub program_name: String,
     pub instruction_name: Option<String>,
     pub accounts: Vec<AccountMeta>,
-    pub data: Vec<u8>,
+    pub data: D,
     /// Decoded instruction from custom decoder (if available)
     pub decoded_instruction: Option<DecodedInstruction>,
-    pub inner_instructions: Vec<EnhancedInstructionLog>,
+    pub inner_instructions: Vec<EnhancedInstructionLog<D>>,
     pub compute_consumed: Option<u64>,
+    /// False for the instruction a failed transaction stopped at
     pub success: bool,
     pub depth: usize,
+    /// Set when fewer accounts were provided than the decoded instruction expects
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub account_count_mismatch: Option<AccountCountMismatch>,
+    /// Leading data bytes the matching decoder selected the instruction by, or the
+    /// first (up to) 8 bytes when no decoder matched
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub discriminator: Option<Vec<u8>>,
+    /// Position in the instruction tree, as in [`DecodeWarning::path`]; see
+    /// [`Self::location`] for its rendering. Empty until the instruction is placed
+    /// in a transaction
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub path: Vec<usize>,
+    /// Bytes this instruction takes up in the serialized message: program id index,
+    /// account indices and data with their length prefixes. None for inner instructions
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub instruction_size_bytes: Option<usize>,
 }
 
 impl EnhancedInstructionLog {
     /// Create a new instruction log
     pub fn new(index: usize, program_id: Pubkey, program_name: String) -> Self {
+        Self::with_data(index, program_id, program_name, Vec::new())
+    }
+}
+
+impl<D: AsRef<[u8]>> EnhancedInstructionLog<D> {
+    /// Create a new instruction log holding `data`
+    pub fn with_data(index: usize, program_id: Pubkey, program_name: String, data: D) -> Self {
         Self {
             index,
             program_id,
             program_name,
             instruction_name: None,
             accounts: Vec::new(),
-            data: Vec::new(),
+            data,
             decoded_instruction: None,
             inner_instructions: Vec::new(),
             compute_consumed: None,
             success: true,
             depth: 0,
+            account_count_mismatch: None,
+            discriminator: None,
+            path: Vec::new(),
+            instruction_size_bytes: None,
+        }
+    }
+
+    /// Human-readable instruction position, e.g. "#1.3.2" (1-based)
+    pub fn location(&self) -> String {
+        instruction_location(&self.path)
+    }
+
+    /// Copy borrowed instruction data (including inner instructions) into an owned log
+    pub fn into_owned(self) -> EnhancedInstructionLog {
+        EnhancedInstructionLog {
+            index: self.index,
+            program_id: self.program_id,
+            program_name: self.program_name,
+            instruction_name: self.instruction_name,
+            accounts: self.accounts,
+            data: self.data.as_ref().to_vec(),
+            decoded_instruction: self.decoded_instruction,
+            inner_instructions: self
+                .inner_instructions
+                .into_iter()
+                .map(EnhancedInstructionLog::into_owned)
+                .collect(),
+            compute_consumed: self.compute_consumed,
+            success: self.success,
+            depth: self.depth,
+            account_count_mismatch: self.account_count_mismatch,
+            discriminator: self.discriminator,
+            path: self.path,
+            instruction_size_bytes: self.instruction_size_bytes,
         }
     }
 
@@ -119,21 +1028,147 @@ impl EnhancedInstructionLog {
 
         // Try the decoder registry (includes custom decoders)
         if let Some(registry) = config.decoder_registry() {
-            if let Some((decoded, decoder)) =
-                registry.decode(&self.program_id, &self.data, &self.accounts)
-            {
+            let data = self.data.as_ref();
+            let decoded = registry.decode(&self.program_id, data, &self.accounts);
+            self.discriminator = match &decoded {
+                Some((_, decoder)) => decoder
+                    .discriminator_size()
+                    .and_then(|size| data.get(..size))
+                    .map(<[u8]>::to_vec),
+                // Best guess for comparing against expected (Anchor) discriminators
+                None => Some(data.get(..8).unwrap_or(data))
+                    .filter(|prefix| !prefix.is_empty())
+                    .map(<[u8]>::to_vec),
+            };
+            if let Some((decoded, decoder)) = decoded {
                 self.instruction_name = Some(decoded.name.clone());
+                self.account_count_mismatch = decoder.validate(&decoded, &self.accounts);
                 self.decoded_instruction = Some(decoded);
-                self.program_name = decoder.program_name().to_string();
+                self.add_amount_ui(config);
+                // A fallback decoder doesn't know the program
+                if let Some(name) = registry.program_name(&self.program_id) {
+                    self.program_name = name.to_string();
+                }
             }
         }
     }
 
+    /// Insert `amount_ui` after a token instruction's `amount` field
+    ///
+    /// Decimals come from the instruction's own `decimals` field (checked
+    /// variants) or from [`EnhancedLoggingConfig::mint_decimals`] for the account
+    /// the decoder labels as the mint (`mint`, or `cmint` for Light Token).
+    /// Instructions without a mint account, like an unchecked `Transfer`, are left
+    /// without a UI amount.
+    fn add_amount_ui(&mut self, config: &EnhancedLoggingConfig) {
+        if !is_token_program(&self.program_id) {
+            return;
+        }
+        let Some(decoded) = self.decoded_instruction.as_ref() else {
+            return;
+        };
+        let Some(position) = decoded.fields.iter().position(|f| f.name == "amount") else {
+            return;
+        };
+        let Ok(amount) = decoded.fields[position].value.parse::<u64>() else {
+            return;
+        };
+        let decimals = decoded
+            .fields
+            .iter()
+            .find(|f| f.name == "decimals")
+            .and_then(|f| f.value.parse::<u8>().ok())
+            .or_else(|| {
+                self.accounts
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| {
+                        matches!(self.account_role(*index), Some("mint" | "cmint"))
+                    })
+                    .find_map(|(_, account)| config.mint_decimals.get(&account.pubkey).copied())
+            });
+        let (Some(decimals), Some(decoded)) = (decimals, self.decoded_instruction.as_mut()) else {
+            return;
+        };
+        decoded.fields.insert(
+            position + 1,
+            DecodedField::new("amount_ui", format_token_amount(amount, decimals)),
+        );
+    }
+
+    /// Semantic role of the account at `index`, as labeled by the decoder (e.g. "mint")
+    ///
+    /// Joins `decoded_instruction.account_names` to `accounts` by position. Empty
+    /// names (used by resolvers for "no label") yield `None`.
+    pub fn account_role(&self, index: usize) -> Option<&str> {
+        self.decoded_instruction
+            .as_ref()?
+            .account_names
+            .get(index)
+            .map(String::as_str)
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Problems with decoding this instruction, as kinds and messages for
+    /// [`DecodeWarning`]s
+    pub fn decode_warnings(
+        &self,
+        config: &EnhancedLoggingConfig,
+    ) -> Vec<(DecodeWarningKind, String)> {
+        let mut warnings = Vec::new();
+        if !config.decode_light_instructions {
+            return warnings;
+        }
+
+        match config.decoder_registry() {
+            // Also noted when a fallback decoder handled the instruction
+            Some(registry) if !registry.has_decoder(&self.program_id) => warnings.push((
+                DecodeWarningKind::UnknownProgram,
+                format!("no decoder registered for program {}", self.program_id),
+            )),
+            Some(_) if self.decoded_instruction.is_none() => warnings.push((
+                DecodeWarningKind::DecodeFailed,
+                format!(
+                    "{} decoder failed to decode {} bytes of instruction data",
+                    self.program_name,
+                    self.data.as_ref().len()
+                ),
+            )),
+            _ => {}
+        }
+        if let Some(decoded) = &self.decoded_instruction {
+            for field in decoded.fields.iter().filter(|f| f.is_decode_error()) {
+                let field_name = if field.name.is_empty() {
+                    String::new()
+                } else {
+                    format!("{} ", field.name)
+                };
+                warnings.push((
+                    DecodeWarningKind::PartiallyDecoded,
+                    format!(
+                        "{} partially decoded: {}{}",
+                        decoded.name, field_name, field.value
+                    ),
+                ));
+            }
+        }
+        if let Some(mismatch) = self.account_count_mismatch {
+            warnings.push((
+                DecodeWarningKind::AccountCountMismatch,
+                format!(
+                    "expected {} accounts, got {}",
+                    mismatch.expected, mismatch.actual
+                ),
+            ));
+        }
+        warnings
+    }
+
     /// Find parent instruction at target depth for nesting
     pub fn find_parent_for_instruction(
-        instructions: &mut [EnhancedInstructionLog],
+        instructions: &mut [EnhancedInstructionLog<D>],
         target_depth: usize,
-    ) -> Option<&mut EnhancedInstructionLog> {
+    ) -> Option<&mut EnhancedInstructionLog<D>> {
         for instruction in instructions.iter_mut().rev() {
             if instruction.depth == target_depth {
                 return Some(instruction);
@@ -148,8 +1183,67 @@ impl EnhancedInstructionLog {
     }
 }
 
+/// One-line summary: status, compute units, fee and the top-level instructions
+///
+/// `{:?}` keeps the full detail.
+impl<D> std::fmt::Display for EnhancedTransactionLog<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(label) = &self.label {
+            write!(f, "[{}] ", label)?;
+        }
+        write!(
+            f,
+            "{} | {}/{} CU | fee {} lamports | {} instruction{}",
+            self.status.text(),
+            self.compute_used,
+            self.compute_total,
+            self.fee,
+            self.instructions.len(),
+            if self.instructions.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        )?;
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { "; " };
+            write!(f, "{}#{} {}", separator, instruction.index + 1, instruction)?;
+        }
+        Ok(())
+    }
+}
+
+/// One-line summary: program, instruction name, compute units, inner count and failure
+///
+/// `{:?}` keeps the full detail.
+impl<D> std::fmt::Display for EnhancedInstructionLog<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}",
+            self.program_name,
+            self.instruction_name.as_deref().unwrap_or("Unknown")
+        )?;
+        let mut details = Vec::new();
+        if let Some(units) = self.compute_consumed {
+            details.push(format!("{} CU", units));
+        }
+        if !self.inner_instructions.is_empty() {
+            details.push(format!("{} inner", self.inner_instructions.len()));
+        }
+        if !self.success {
+            details.push("failed".to_string());
+        }
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 /// Account state changes during transaction
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccountChange {
     pub pubkey: Pubkey,
     pub account_type: String,
@@ -166,6 +1260,7 @@ pub struct AccountChange {
 
 /// Account access pattern during transaction
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AccountAccess {
     Readonly,
     Writable,
@@ -189,7 +1284,8 @@ impl AccountAccess {
 }
 
 /// Light Protocol specific events
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LightProtocolEvent {
     pub event_type: String,
     pub compressed_accounts: Vec<CompressedAccountInfo>,
@@ -198,7 +1294,8 @@ pub struct LightProtocolEvent {
 }
 
 /// Compressed account information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CompressedAccountInfo {
     pub hash: String,
     pub owner: Pubkey,
@@ -208,7 +1305,8 @@ pub struct CompressedAccountInfo {
 }
 
 /// Merkle tree state change
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MerkleTreeChange {
     pub tree_pubkey: Pubkey,
     pub tree_type: String,
@@ -216,14 +1314,73 @@ pub struct MerkleTreeChange {
     pub leaf_index: u64,
 }
 
+/// Standard sysvar addresses mapped to human-readable labels
+static SYSVARS: &[(&str, &str)] = &[
+    (
+        "Sysvar1111111111111111111111111111111111111",
+        "Sysvar Owner",
+    ),
+    (
+        "SysvarC1ock11111111111111111111111111111111",
+        "Clock Sysvar",
+    ),
+    (
+        "SysvarEpochRewards1111111111111111111111111",
+        "Epoch Rewards Sysvar",
+    ),
+    (
+        "SysvarEpochSchedu1e111111111111111111111111",
+        "Epoch Schedule Sysvar",
+    ),
+    ("SysvarFees111111111111111111111111111111111", "Fees Sysvar"),
+    (
+        "Sysvar1nstructions1111111111111111111111111",
+        "Instructions Sysvar",
+    ),
+    (
+        "SysvarLastRestartS1ot1111111111111111111111",
+        "Last Restart Slot Sysvar",
+    ),
+    (
+        "SysvarRecentB1ockHashes11111111111111111111",
+        "Recent Blockhashes Sysvar",
+    ),
+    ("SysvarRent111111111111111111111111111111111", "Rent Sysvar"),
+    (
+        "SysvarRewards111111111111111111111111111111",
+        "Rewards Sysvar",
+    ),
+    (
+        "SysvarS1otHashes111111111111111111111111111",
+        "Slot Hashes Sysvar",
+    ),
+    (
+        "SysvarS1otHistory11111111111111111111111111",
+        "Slot History Sysvar",
+    ),
+    (
+        "SysvarStakeHistory1111111111111111111111111",
+        "Stake History Sysvar",
+    ),
+];
+
+/// Get the label of a standard sysvar (e.g. "Rent Sysvar"), if `pubkey` is one
+pub fn sysvar_name(pubkey: &Pubkey) -> Option<&'static str> {
+    let pubkey_str = pubkey.to_string();
+    SYSVARS
+        .iter()
+        .find(|(addr, _)| *addr == pubkey_str)
+        .map(|(_, name)| *name)
+}
+
 /// Get human-readable program name from pubkey
 ///
 /// First consults the decoder registry if provided, then falls back to hardcoded mappings.
 pub fn get_program_name(program_id: &Pubkey, registry: Option<&DecoderRegistry>) -> String {
     // First try to get the name from the decoder registry
     if let Some(reg) = registry {
-        if let Some(decoder) = reg.get_decoder(program_id) {
-            return decoder.program_name().to_string();
+        if let Some(name) = reg.program_name(program_id) {
+            return name.to_string();
         }
     }
 
@@ -237,3 +1394,633 @@ pub fn get_program_name(program_id: &Pubkey, registry: Option<&DecoderRegistry>)
         _ => format!("Unknown Program ({})", program_id),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecodedField;
+
+    fn instruction(program_id: Pubkey, depth: usize) -> EnhancedInstructionLog {
+        let mut ix = EnhancedInstructionLog::new(0, program_id, String::new());
+        ix.depth = depth;
+        ix
+    }
+
+    #[test]
+    fn test_rent_exempt_minimum() {
+        assert_eq!(rent_exempt_minimum(0), 890_880);
+        // SPL token account
+        assert_eq!(rent_exempt_minimum(165), 2_039_280);
+
+        let mut state = AccountStateSnapshot {
+            lamports_after: 2_039_280,
+            data_len_after: 165,
+            ..Default::default()
+        };
+        assert!(state.is_rent_exempt());
+        state.lamports_after -= 1;
+        assert!(!state.is_rent_exempt());
+        // Closed accounts are not reaped
+        state.lamports_after = 0;
+        assert!(state.is_rent_exempt());
+    }
+
+    #[test]
+    fn test_sysvar_name() {
+        for (addr, name) in SYSVARS {
+            let pubkey: Pubkey = addr.parse().unwrap();
+            assert_eq!(sysvar_name(&pubkey), Some(*name));
+        }
+        assert_eq!(sysvar_name(&Pubkey::default()), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_to_bytes_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let config = EnhancedLoggingConfig::default();
+
+        let mut ix = instruction(Pubkey::default(), 0); // System Program
+        ix.accounts = vec![
+            AccountMeta::new(account, true),
+            AccountMeta::new(program_id, false),
+        ];
+        // System Transfer: discriminator 2 + lamports
+        ix.data = [2u32.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat();
+        ix.decode(&config);
+        assert!(ix.decoded_instruction.is_some());
+        ix.inner_instructions.push(instruction(program_id, 1));
+
+        let mut log = EnhancedTransactionLog::new(Signature::from([7u8; 64]), 42);
+        log.status = TransactionStatus::Failed("custom program error: 0x1".to_string());
+        log.fee = 5_000;
+        log.compute_used = 1_234;
+        log.instructions.push(ix);
+        log.account_states = Some(HashMap::from([(
+            account,
+            AccountStateSnapshot {
+                lamports_before: 10,
+                lamports_after: 5,
+                data_len_before: 0,
+                data_len_after: 8,
+                owner: program_id,
+                owner_after: program_id,
+            },
+        )]));
+
+        let bytes = log.to_bytes().unwrap();
+        assert_eq!(EnhancedTransactionLog::from_bytes(&bytes).unwrap(), log);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        let log = EnhancedTransactionLog::new(Signature::default(), 0);
+        assert_eq!(log.stats(), TransactionStats::default());
+    }
+
+    #[test]
+    fn test_stats_walks_inner_instructions() {
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+
+        let mut cpi = instruction(program_b, 1);
+        cpi.inner_instructions.push(instruction(program_a, 2));
+        let mut outer = instruction(program_a, 0);
+        outer.inner_instructions.push(cpi);
+
+        let mut log = EnhancedTransactionLog::new(Signature::default(), 0);
+        log.instructions.push(outer);
+        log.instructions.push(instruction(program_b, 0));
+
+        assert_eq!(
+            log.stats(),
+            TransactionStats {
+                max_depth: 2,
+                total_instructions: 4,
+                distinct_programs: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_all_pubkeys() {
+        let payer = Pubkey::new_from_array([1; 32]);
+        let recipient = Pubkey::new_from_array([2; 32]);
+        let watched = Pubkey::new_from_array([3; 32]);
+        let program = Pubkey::new_from_array([4; 32]);
+        let log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(program)
+                    .account(payer, true, true)
+                    .inner(
+                        crate::InstructionLogBuilder::new(Pubkey::default())
+                            .name("Transfer")
+                            .account(payer, true, true)
+                            .account(recipient, false, true),
+                    ),
+            )
+            .account_state(watched, AccountStateSnapshot::default())
+            .build();
+
+        let pubkeys = log.all_pubkeys();
+        assert!(pubkeys.contains(&payer) && pubkeys.contains(&recipient));
+        assert_eq!(
+            pubkeys.into_iter().collect::<Vec<_>>(),
+            [Pubkey::default(), payer, recipient, watched, program]
+        );
+        assert!(EnhancedTransactionLog::new(Signature::default(), 0)
+            .all_pubkeys()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_primary_program() {
+        let compute_budget: Pubkey = "ComputeBudget111111111111111111111111111111"
+            .parse()
+            .unwrap();
+        let ata: Pubkey = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+            .parse()
+            .unwrap();
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+
+        let mut log = EnhancedTransactionLog::new(Signature::default(), 0);
+        assert_eq!(log.primary_program(), None);
+
+        log.instructions.push(instruction(compute_budget, 0));
+        assert_eq!(
+            log.primary_program().map(|(id, _)| id),
+            Some(compute_budget)
+        );
+
+        log.instructions.push(instruction(ata, 0));
+        let mut decoded = instruction(program_a, 0);
+        decoded.decoded_instruction = Some(DecodedInstruction::with_fields_and_accounts(
+            "Deposit",
+            vec![crate::DecodedField::new("amount", "1")],
+            Vec::new(),
+        ));
+        log.instructions.push(decoded);
+        log.instructions.push(instruction(program_b, 0));
+        // More decoded fields beats position
+        assert_eq!(log.primary_program().map(|(id, _)| id), Some(program_a));
+
+        log.instructions.remove(2);
+        // Ties go to the later instruction, skipping setup programs
+        assert_eq!(log.primary_program().map(|(id, _)| id), Some(program_b));
+    }
+
+    #[test]
+    fn test_decode_warnings() {
+        let config = EnhancedLoggingConfig::default();
+
+        let mut unknown = instruction(Pubkey::new_unique(), 0);
+        unknown.decode(&config);
+        assert_eq!(
+            unknown.decode_warnings(&config),
+            [(
+                DecodeWarningKind::UnknownProgram,
+                format!("no decoder registered for program {}", unknown.program_id)
+            )]
+        );
+
+        // System Program with a truncated Transfer payload
+        let mut truncated = instruction(Pubkey::default(), 0);
+        truncated.program_name = "System Program".to_string();
+        truncated.data = vec![2, 0, 0];
+        truncated.decode(&config);
+        assert_eq!(
+            truncated.decode_warnings(&config),
+            [(
+                DecodeWarningKind::DecodeFailed,
+                "System Program decoder failed to decode 3 bytes of instruction data".to_string()
+            )]
+        );
+
+        // Transfer whose lamports are cut off after 3 bytes
+        let mut partial = instruction(Pubkey::default(), 0);
+        partial.accounts = vec![
+            AccountMeta::new(Pubkey::new_unique(), true),
+            AccountMeta::new(Pubkey::new_unique(), false),
+        ];
+        partial.data = [2u32.to_le_bytes().as_slice(), &[1, 2, 3]].concat();
+        partial.decode(&config);
+        let decoded = partial.decoded_instruction.as_ref().unwrap();
+        assert_eq!(
+            decoded.fields,
+            [DecodedField::decode_error(
+                "lamports",
+                4,
+                "unexpected end of input"
+            )]
+        );
+        assert_eq!(
+            partial.decode_warnings(&config),
+            [(
+                DecodeWarningKind::PartiallyDecoded,
+                "Transfer partially decoded: lamports <decode error at offset 4: unexpected end of input>".to_string()
+            )]
+        );
+
+        let warning = DecodeWarning::new(vec![0, 2], "message");
+        assert_eq!(warning.location(), "instruction #1.3");
+        assert_eq!(
+            DecodeWarning::new(vec![], "message").location(),
+            "transaction"
+        );
+    }
+
+    #[test]
+    fn test_account_role() {
+        let config = EnhancedLoggingConfig::default();
+        let mut ix = instruction(Pubkey::default(), 0); // System Program
+        ix.accounts = vec![
+            AccountMeta::new(Pubkey::new_unique(), true),
+            AccountMeta::new(Pubkey::new_unique(), false),
+        ];
+        assert_eq!(ix.account_role(0), None);
+
+        ix.data = [2u32.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat();
+        ix.decode(&config);
+        assert_eq!(ix.account_role(0), Some("from"));
+        assert_eq!(ix.account_role(1), Some("to"));
+        assert_eq!(ix.account_role(2), None);
+    }
+
+    #[test]
+    fn test_borrowed_instruction_into_owned() {
+        let config = EnhancedLoggingConfig::default();
+        let data = [2u32.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat();
+        let inner_data = [3u8; 4];
+        let mut ix = EnhancedInstructionLog::with_data(
+            0,
+            Pubkey::default(),
+            "System Program".to_string(),
+            data.as_slice(),
+        );
+        ix.accounts = vec![
+            AccountMeta::new(Pubkey::new_unique(), true),
+            AccountMeta::new(Pubkey::new_unique(), false),
+        ];
+        ix.decode(&config);
+        ix.inner_instructions
+            .push(EnhancedInstructionLog::with_data(
+                0,
+                Pubkey::default(),
+                "System Program".to_string(),
+                inner_data.as_slice(),
+            ));
+        assert_eq!(ix.instruction_name.as_deref(), Some("Transfer"));
+
+        let owned = ix.into_owned();
+        assert_eq!(owned.data, data);
+        assert_eq!(owned.inner_instructions[0].data, inner_data);
+        assert_eq!(owned.account_role(1), Some("to"));
+    }
+
+    #[test]
+    fn test_attribute_compute_units() {
+        let outer = Pubkey::new_unique();
+        let inner = Pubkey::new_unique();
+        let precompile = Pubkey::new_unique();
+        let mut log = EnhancedTransactionLog::new(Signature::default(), 0);
+        log.instructions.push(instruction(precompile, 0));
+        let mut cpi = instruction(outer, 0);
+        cpi.inner_instructions = vec![instruction(inner, 1), instruction(Pubkey::default(), 1)];
+        log.instructions.push(cpi);
+        log.instructions.push(instruction(Pubkey::default(), 0));
+
+        let logs = [
+            format!("Program {outer} invoke [1]"),
+            "Program log: Instruction: Initialize".to_string(),
+            format!("Program {inner} invoke [2]"),
+            format!("Program {inner} consumed 1200 of 190000 compute units"),
+            format!("Program {inner} success"),
+            "Program 11111111111111111111111111111111 invoke [2]".to_string(),
+            "Program 11111111111111111111111111111111 success".to_string(),
+            format!("Program {outer} consumed 4413 of 200000 compute units"),
+            format!("Program {outer} success"),
+            "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+            "Program 11111111111111111111111111111111 success".to_string(),
+        ];
+        log.attribute_compute_units(&logs);
+
+        assert_eq!(log.instructions[0].compute_consumed, None);
+        assert_eq!(log.instructions[1].compute_consumed, Some(4413));
+        assert_eq!(
+            log.instructions[1].inner_instructions[0].compute_consumed,
+            Some(1200)
+        );
+        assert_eq!(
+            log.instructions[1].inner_instructions[1].compute_consumed,
+            None
+        );
+        assert_eq!(log.instructions[2].compute_consumed, None);
+
+        let config = EnhancedLoggingConfig::default();
+        let output = crate::TransactionFormatter::new(&config).format_plain(&log, 1);
+        assert!(output.contains(&format!("{outer} () (cu: 4413)")));
+        assert!(output.contains(&format!("{inner} () (cu: 1200)")));
+    }
+
+    #[test]
+    fn test_program_compute_budget() {
+        let program = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let log = crate::TransactionLogBuilder::new()
+            .compute(9_000, 200_000)
+            .instruction(
+                crate::InstructionLogBuilder::new(program)
+                    .program_name("Counter")
+                    .compute_consumed(5_000)
+                    // Recursive CPI, already included in the outer invocation
+                    .inner(crate::InstructionLogBuilder::new(program).compute_consumed(1_000)),
+            )
+            .instruction(
+                crate::InstructionLogBuilder::new(other)
+                    .compute_consumed(3_000)
+                    .inner(crate::InstructionLogBuilder::new(program).compute_consumed(500)),
+            )
+            .build();
+
+        assert_eq!(log.compute_used(), 9_000);
+        assert_eq!(log.program_compute_units(&program), 5_500);
+        assert_eq!(log.program_compute_units(&other), 3_000);
+        assert_eq!(log.program_compute_units(&Pubkey::new_unique()), 0);
+        log.assert_compute_under(9_000);
+        log.assert_program_compute_under(&program, 50_000);
+
+        let err = std::panic::catch_unwind(|| log.assert_program_compute_under(&program, 5_000))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            &format!(
+                "compute budget exceeded: Counter ({program}) used 5500 CU, budget is 5000 CU (500 CU over)"
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "compute budget exceeded: transaction used 9000 CU, budget is 8000 CU (1000 CU over)"
+    )]
+    fn test_assert_compute_under_panics_over_budget() {
+        crate::TransactionLogBuilder::new()
+            .compute(9_000, 200_000)
+            .build()
+            .assert_compute_under(8_000);
+    }
+
+    #[test]
+    fn test_display_summaries() {
+        use crate::{InstructionLogBuilder, TransactionLogBuilder};
+
+        let log = TransactionLogBuilder::new()
+            .status(TransactionStatus::Success)
+            .label("setup")
+            .fee(5_000)
+            .compute(4_413, 200_000)
+            .instruction(
+                InstructionLogBuilder::new(Pubkey::new_unique())
+                    .program_name("Counter")
+                    .name("Increment")
+                    .compute_consumed(4_263)
+                    .inner(InstructionLogBuilder::new(Pubkey::default()).name("Transfer")),
+            )
+            .instruction(
+                InstructionLogBuilder::new(Pubkey::new_unique())
+                    .program_name("Memo")
+                    .failed(),
+            )
+            .build();
+        assert_eq!(
+            log.to_string(),
+            "[setup] Success | 4413/200000 CU | fee 5000 lamports | 2 instructions: \
+             #1 Counter: Increment (4263 CU, 1 inner); #2 Memo: Unknown (failed)"
+        );
+        assert_eq!(
+            log.instructions[0].inner_instructions[0].to_string(),
+            "System Program: Transfer"
+        );
+        assert!(format!("{:?}", log).contains("program_logs_pretty"));
+    }
+
+    #[test]
+    fn test_redecode() {
+        let transfer = [2u32.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat();
+        let mut log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(Pubkey::new_from_array([7; 32]))
+                    .program_name("Counter")
+                    .inner(
+                        crate::InstructionLogBuilder::new(Pubkey::default())
+                            .data(transfer)
+                            .account(Pubkey::new_unique(), true, true)
+                            .account(Pubkey::new_unique(), false, true),
+                    ),
+            )
+            .build();
+        log.warnings.push(
+            DecodeWarning::new(vec![0], "account index 9 out of range for 3 account keys")
+                .with_kind(DecodeWarningKind::UnresolvedIndex),
+        );
+        // Kept even though its message reads like a decode warning
+        log.warnings.push(DecodeWarning::new(
+            vec![0],
+            "expected 5 accounts, got 2 (checked by hand)",
+        ));
+
+        // Decoded without the built-in decoders first
+        let config = EnhancedLoggingConfig::default().with_builtin_decoders(false);
+        log.redecode(&config);
+        let inner = &log.instructions[0].inner_instructions[0];
+        assert_eq!(inner.instruction_name, None);
+        assert_eq!(log.warnings.len(), 4);
+
+        log.redecode(&EnhancedLoggingConfig::default());
+        assert_eq!(
+            log.instructions[0].program_name,
+            format!("Unknown Program ({})", Pubkey::new_from_array([7; 32]))
+        );
+        let inner = &log.instructions[0].inner_instructions[0];
+        assert_eq!(inner.program_name, "System Program");
+        assert_eq!(inner.instruction_name.as_deref(), Some("Transfer"));
+        let messages: Vec<&str> = log.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "account index 9 out of range for 3 account keys".to_string(),
+                "expected 5 accounts, got 2 (checked by hand)".to_string(),
+                format!(
+                    "no decoder registered for program {}",
+                    Pubkey::new_from_array([7; 32])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instruction_discriminator() {
+        let config = EnhancedLoggingConfig::default();
+        let transfer = [2u32.to_le_bytes().as_slice(), &1_000u64.to_le_bytes()].concat();
+        let mut ix = crate::InstructionLogBuilder::new(Pubkey::default())
+            .data(transfer)
+            .build();
+        ix.decode(&config);
+        assert_eq!(ix.discriminator, Some(vec![2, 0, 0, 0]));
+
+        // Unknown program: best-guess 8-byte prefix
+        let mut ix = crate::InstructionLogBuilder::new(Pubkey::new_unique())
+            .data((1..=10).collect::<Vec<u8>>())
+            .build();
+        ix.decode(&config);
+        assert_eq!(ix.discriminator, Some((1..=8).collect()));
+
+        let mut ix = crate::InstructionLogBuilder::new(Pubkey::new_unique()).build();
+        ix.decode(&config);
+        assert_eq!(ix.discriminator, None);
+    }
+
+    #[test]
+    fn test_mint_decimals_render_amount_ui() {
+        let token_program = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+        let mint = Pubkey::new_from_array([5; 32]);
+        let token_ix = |discriminator: u8, accounts: &[Pubkey]| {
+            let mut builder = crate::InstructionLogBuilder::new(token_program)
+                .data([[discriminator].as_slice(), &1_000_000u64.to_le_bytes()].concat());
+            for account in accounts {
+                builder = builder.account(*account, false, true);
+            }
+            builder.build()
+        };
+        let field = |ix: &EnhancedInstructionLog, name: &str| {
+            ix.decoded_instruction
+                .as_ref()
+                .unwrap()
+                .fields
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| f.value.clone())
+        };
+        let (source, destination, authority) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([3; 32]),
+        );
+
+        // Unchecked Transfer has no mint account, even if a registered mint is passed along
+        let mut ix = token_ix(3, &[source, destination, authority, mint]);
+        ix.decode(&EnhancedLoggingConfig::default().with_mint_decimals(mint, 6));
+        assert_eq!(field(&ix, "amount_ui"), None);
+
+        // MintTo names the mint; decimals only render once registered
+        let mint_to = [mint, destination, authority];
+        let mut ix = token_ix(7, &mint_to);
+        ix.decode(&EnhancedLoggingConfig::default());
+        assert_eq!(field(&ix, "amount_ui"), None);
+
+        let config = EnhancedLoggingConfig::default().with_mint_decimals(mint, 6);
+        let mut ix = token_ix(7, &mint_to);
+        ix.decode(&config);
+        let names: Vec<&str> = ix
+            .decoded_instruction
+            .as_ref()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, ["amount", "amount_ui"]);
+        assert_eq!(field(&ix, "amount_ui").as_deref(), Some("1.000000"));
+
+        // A registered mint in a non-mint slot is ignored
+        let mut ix = token_ix(7, &[source, mint, authority]);
+        ix.decode(&config);
+        assert_eq!(field(&ix, "amount_ui"), None);
+
+        // Burn names the mint as its second account
+        let mut ix = token_ix(8, &[source, mint, authority]);
+        ix.decode(&config.clone().with_mint_decimals(mint, 2));
+        assert_eq!(field(&ix, "amount_ui").as_deref(), Some("10000.00"));
+
+        // TransferChecked: amount, then its own decimals, which take precedence
+        let mut ix = crate::InstructionLogBuilder::new(token_program)
+            .data([[12u8].as_slice(), &1_000_000u64.to_le_bytes(), &[3]].concat())
+            .account(source, false, true)
+            .account(mint, false, false)
+            .account(destination, false, true)
+            .account(authority, true, false)
+            .build();
+        ix.decode(&config);
+        assert_eq!(ix.account_role(1), Some("mint"));
+        assert_eq!(field(&ix, "amount_ui").as_deref(), Some("1000.000"));
+        assert_eq!(field(&ix, "decimals").as_deref(), Some("3"));
+
+        assert_eq!(format_token_amount(5, 9), "0.000000005");
+        assert_eq!(format_token_amount(42, 0), "42");
+    }
+
+    #[test]
+    fn test_compute_budget_priority_fee() {
+        let compute_budget = ComputeBudgetInstructionDecoder.program_id();
+        let budget_ix = |data: Vec<u8>| {
+            let mut ix = instruction(compute_budget, 0);
+            ix.data = data;
+            ix
+        };
+        let mut log = EnhancedTransactionLog::new(Signature::default(), 0);
+        log.instructions.push(budget_ix(
+            [[3u8].as_slice(), &25_000u64.to_le_bytes()].concat(),
+        ));
+        log.instructions.push(instruction(Pubkey::default(), 0));
+        log.read_compute_budget();
+        assert_eq!(log.compute_unit_price, Some(25_000));
+        assert_eq!(log.compute_unit_limit, None);
+        // Default limit: 200,000 CU for the one non-Compute-Budget instruction
+        assert_eq!(log.priority_fee(), Some(5_000));
+
+        log.instructions.push(budget_ix(
+            [[2u8].as_slice(), &300_001u32.to_le_bytes()].concat(),
+        ));
+        log.read_compute_budget();
+        assert_eq!(log.compute_unit_limit, Some(300_001));
+        assert_eq!(log.compute_total, 300_001);
+        assert_eq!(log.priority_fee(), Some(7_501));
+
+        let mut config = EnhancedLoggingConfig::default();
+        config.use_colors = false;
+        let output = crate::TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("/300001 CU (0.0% of limit) | Priority: 25000 µlam/CU"));
+
+        log.compute_unit_price = None;
+        assert_eq!(log.priority_fee(), None);
+    }
+
+    #[test]
+    fn test_compute_utilization() {
+        let compute_budget = ComputeBudgetInstructionDecoder.program_id();
+        let mut log = crate::TransactionLogBuilder::new()
+            .instruction(
+                crate::InstructionLogBuilder::new(compute_budget)
+                    .data([[2u8].as_slice(), &200_000u32.to_le_bytes()].concat()),
+            )
+            .build();
+        log.compute_used = 1_234;
+        assert_eq!(log.compute_utilization(), None);
+
+        log.read_compute_budget();
+        assert_eq!(log.compute_utilization(), Some(0.617));
+        assert!(!log.compute_limit_reached());
+
+        let mut config = EnhancedLoggingConfig::default();
+        config.use_colors = false;
+        let output = crate::TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("Compute Used: 1234/200000 CU (0.6% of limit)\n"));
+
+        log.compute_used = 200_000;
+        assert!(log.compute_limit_reached());
+        let output = crate::TransactionFormatter::new(&config).format(&log, 1);
+        assert!(output.contains("200000/200000 CU (100.0% of limit, limit reached)\n"));
+    }
+}
@@ -1,10 +1,19 @@
 //! Test utilities -- thin re-exports from `light_instruction_decoder::litesvm`.
 
 pub use light_instruction_decoder::litesvm::{
-    capture_account_states, create_logging_callback, decode_transaction,
-    decode_transaction_snapshot, format_transaction, strip_ansi_codes,
-    transaction_log_to_snapshot, write_to_log_file, AccountSnapshot, AccountStates, FieldSnapshot,
-    InstructionSnapshot, TransactionLogger, TransactionSnapshot,
+    capture_account_states, capture_clock, create_logging_callback, create_tracing_callback,
+    decode_base64_transaction, decode_legacy_transaction, decode_message, decode_transaction,
+    decode_session, decode_transaction_borrowed, decode_transaction_with_loaded_addresses,
+    decode_transactions, format_batch, format_transaction, format_transaction_plain,
+    load_lookup_table_addresses, parse_program_log_tree, strip_ansi_codes, write_to_log_file,
+    AccountStates, LogNode, TransactionLogger, TransactionRecord,
+};
+#[cfg(feature = "serde")]
+pub use light_instruction_decoder::litesvm::{
+    decode_transaction_snapshot, decode_transaction_snapshot_with_logs,
+    transaction_log_to_snapshot, transaction_log_to_snapshot_with_logs, AccountSnapshot,
+    AccountStateDiffSnapshot, FailedInstructionSnapshot, FieldSnapshot, InstructionSnapshot,
+    TransactionSnapshot,
 };
 
 pub use light_instruction_decoder::EnhancedLoggingConfig as Config;
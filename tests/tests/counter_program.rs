@@ -1,8 +1,11 @@
 use instruction_decoder_tests::{
-    capture_account_states, decode_transaction, decode_transaction_snapshot, format_transaction,
+    capture_account_states, decode_transaction, decode_transaction_snapshot,
+    decode_transaction_with_loaded_addresses, format_transaction, load_lookup_table_addresses,
     strip_ansi_codes, LiteSVM,
 };
-use light_instruction_decoder::EnhancedLoggingConfig;
+use light_instruction_decoder::{
+    anchor_instruction_discriminator as anchor_discriminator, EnhancedLoggingConfig,
+};
 use solana_keypair::{keypair_from_seed, Keypair};
 use solana_message::Message;
 use solana_native_token::LAMPORTS_PER_SOL;
@@ -38,17 +41,6 @@ fn anchor_ix(
     solana_instruction::Instruction::new_with_bytes(*program_id, &ix_data, accounts)
 }
 
-/// Compute Anchor discriminator: sha256("global:<name>")[..8]
-fn anchor_discriminator(name: &str) -> [u8; 8] {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    hasher.update(format!("global:{name}").as_bytes());
-    let hash = hasher.finalize();
-    let mut disc = [0u8; 8];
-    disc.copy_from_slice(&hash[..8]);
-    disc
-}
-
 #[test]
 fn test_decode_initialize() {
     let (mut svm, payer) = setup();
@@ -94,11 +86,99 @@ fn test_decode_initialize() {
     let log = decode_transaction(
         &versioned_tx, &result, &config, Some(&pre_states), Some(&post_states),
     );
+    // Compute from the "consumed" log line; the System Program CPI logs none
+    assert_eq!(log.instructions[0].compute_consumed, Some(4413));
+    assert_eq!(log.instructions[0].inner_instructions[0].compute_consumed, None);
     let formatted = format_transaction(&log, &config, 1);
     let stripped = strip_ansi_codes(&formatted);
     insta::assert_snapshot!("counter_initialize_table", stripped);
 }
 
+#[test]
+fn test_decode_v0_cpi_with_lookup_table_account() {
+    let (mut svm, payer) = setup();
+    let counter = deterministic_keypair(14);
+    let system_program = solana_pubkey::pubkey!("11111111111111111111111111111111");
+
+    // Lookup table holding the System Program, which Initialize invokes via CPI
+    let table = deterministic_keypair(30).pubkey();
+    let mut table_data = vec![0u8; 56];
+    table_data[..4].copy_from_slice(&1u32.to_le_bytes()); // ProgramState::LookupTable
+    table_data[4..12].copy_from_slice(&u64::MAX.to_le_bytes()); // deactivation_slot: active
+    table_data[20] = 1; // last_extended_slot_start_index: first address usable
+    table_data.extend_from_slice(system_program.as_ref());
+    svm.set_account(
+        table,
+        solana_account::Account {
+            lamports: LAMPORTS_PER_SOL,
+            data: table_data,
+            owner: solana_pubkey::pubkey!("AddressLookupTab1e1111111111111111111111111"),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+
+    let init_ix = anchor_ix(
+        &COUNTER_PROGRAM_ID,
+        &anchor_discriminator("initialize"),
+        &[],
+        vec![
+            solana_instruction::AccountMeta::new(counter.pubkey(), true),
+            solana_instruction::AccountMeta::new(payer.pubkey(), true),
+            solana_instruction::AccountMeta::new_readonly(system_program, false),
+        ],
+    );
+    let msg = solana_message::v0::Message::try_compile(
+        &payer.pubkey(),
+        &[init_ix],
+        &[solana_message::AddressLookupTableAccount {
+            key: table,
+            addresses: vec![system_program],
+        }],
+        svm.latest_blockhash(),
+    )
+    .unwrap();
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::try_new(
+        solana_message::VersionedMessage::V0(msg),
+        &[&payer, &counter],
+    )
+    .unwrap();
+    assert!(!versioned_tx.message.static_account_keys().contains(&system_program));
+
+    let loaded_addresses = load_lookup_table_addresses(&svm, &versioned_tx.message);
+    assert_eq!(loaded_addresses.readonly, vec![system_program]);
+    let pre_states = capture_account_states(&svm, &versioned_tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+    assert!(result.is_ok(), "{result:?}");
+    let post_states = capture_account_states(&svm, &versioned_tx);
+
+    let config = EnhancedLoggingConfig::debug()
+        .with_decoders(vec![Box::new(counter::CounterInstructionDecoder)]);
+    let log = decode_transaction_with_loaded_addresses(
+        &versioned_tx, &result, &config, Some(&pre_states), Some(&post_states), &loaded_addresses,
+    );
+
+    assert!(
+        !log.warnings.iter().any(|w| w.message.contains("out of range")),
+        "{:?}",
+        log.warnings
+    );
+    let initialize = &log.instructions[0];
+    assert_eq!(initialize.instruction_name.as_deref(), Some("Initialize"));
+    assert_eq!(initialize.accounts[2].pubkey, system_program);
+    let cpi = &initialize.inner_instructions[0];
+    assert_eq!(cpi.program_id, system_program);
+    assert_eq!(cpi.program_name, "System Program");
+    assert_eq!(cpi.instruction_name.as_deref(), Some("CreateAccount"));
+    assert_eq!(cpi.accounts[1].pubkey, counter.pubkey());
+
+    // Without the loaded addresses the CPI's program id is out of range
+    let log = decode_transaction(&versioned_tx, &result, &config, None, None);
+    assert_eq!(log.instructions[0].inner_instructions[0].program_id, Pubkey::default());
+    assert!(log.warnings.iter().any(|w| w.message.contains("out of range")));
+}
+
 #[test]
 fn test_decode_increment_and_set() {
     let (mut svm, payer) = setup();
@@ -1,20 +1,14 @@
-use light_instruction_decoder::{DecoderRegistry, InstructionDecoder};
-use sha2::{Digest, Sha256};
+use light_instruction_decoder::{
+    anchor_instruction_discriminator as anchor_discriminator, decode_fixture_with, DecodedField,
+    DecoderRegistry, InstructionDecoder,
+};
+use light_instruction_decoder_derive::InstructionDecoder;
 use solana_instruction::AccountMeta;
 use solana_pubkey::Pubkey;
 
 const COUNTER_PROGRAM_ID: Pubkey =
     solana_pubkey::pubkey!("Counter111111111111111111111111111111111111");
 
-fn anchor_discriminator(name: &str) -> [u8; 8] {
-    let mut hasher = Sha256::new();
-    hasher.update(format!("global:{name}").as_bytes());
-    let hash = hasher.finalize();
-    let mut disc = [0u8; 8];
-    disc.copy_from_slice(&hash[..8]);
-    disc
-}
-
 fn make_accounts(names: &[&str]) -> Vec<AccountMeta> {
     names
         .iter()
@@ -53,6 +47,29 @@ fn test_decoder_decodes_initialize() {
     );
 }
 
+#[test]
+fn test_decoder_reports_missing_accounts() {
+    let decoder = counter::CounterInstructionDecoder;
+    let disc = anchor_discriminator("initialize");
+
+    let decoded = decoder
+        .decode(
+            &disc,
+            &make_accounts(&["counter", "authority", "system_program"]),
+        )
+        .unwrap();
+    assert_eq!(decoded.expected_accounts(), Some(3));
+
+    let accounts = make_accounts(&["counter", "authority"]);
+    let mismatch = decoder.validate(&decoded, &accounts).unwrap();
+    assert_eq!(mismatch.expected, 3);
+    assert_eq!(mismatch.actual, 2);
+
+    // Extra trailing accounts are treated as remaining accounts
+    let accounts = make_accounts(&["counter", "authority", "system_program", "extra"]);
+    assert!(decoder.validate(&decoded, &accounts).is_none());
+}
+
 #[test]
 fn test_decoder_decodes_set_with_params() {
     let decoder = counter::CounterInstructionDecoder;
@@ -72,6 +89,136 @@ fn test_decoder_decodes_set_with_params() {
     assert!(decoded.fields.iter().any(|f| f.name == "value" && f.value == "42"));
 }
 
+#[test]
+fn test_decoder_decodes_hex_fixture() {
+    let mut registry = DecoderRegistry::new();
+    registry.register(Box::new(counter::CounterInstructionDecoder));
+
+    // set(42), as captured from a transaction
+    let decoded = decode_fixture_with(
+        &registry,
+        &COUNTER_PROGRAM_ID,
+        "c63335f1741d7ec2 2a00000000000000",
+        2,
+    )
+    .unwrap();
+    assert_eq!(decoded.name, "Set");
+    assert_eq!(decoded.account_names, vec!["counter", "authority"]);
+    assert!(decoded
+        .fields
+        .iter()
+        .any(|f| f.name == "value" && f.value == "42"));
+}
+
+#[test]
+fn test_decoder_applies_field_display() {
+    let decoder = counter::CounterInstructionDecoder;
+    let referrer = Pubkey::new_from_array([1; 32]);
+
+    let mut data = anchor_discriminator("step").to_vec();
+    data.push(1); // StepDirection::Down
+    data.extend_from_slice(&1_500_000u64.to_le_bytes());
+    data.extend_from_slice(&referrer.to_bytes());
+
+    let accounts = make_accounts(&["counter", "authority"]);
+    let decoded = decoder.decode(&data, &accounts).unwrap();
+    assert_eq!(decoded.name, "Step");
+
+    let field = |name: &str| {
+        decoded
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| f.value.clone())
+    };
+    assert_eq!(field("direction").as_deref(), Some("Down"));
+    assert_eq!(field("amount").as_deref(), Some("1.500000"));
+    assert_eq!(field("referrer"), Some(referrer.to_string()));
+}
+
+#[test]
+fn test_decoder_renders_enum_args_by_variant_name() {
+    let decoder = counter::CounterInstructionDecoder;
+    let accounts = make_accounts(&["counter", "authority"]);
+
+    let mut data = anchor_discriminator("adjust").to_vec();
+    data.push(0); // Adjustment::Reset
+    let decoded = decoder.decode(&data, &accounts).unwrap();
+    assert_eq!(decoded.name, "Adjust");
+    assert_eq!(decoded.fields[0].name, "adjustment");
+    assert_eq!(decoded.fields[0].value, "Reset");
+    assert!(decoded.fields[0].children.is_empty());
+
+    let mut data = anchor_discriminator("adjust").to_vec();
+    data.push(1); // Adjustment::Add
+    data.extend_from_slice(&5u64.to_le_bytes());
+    let decoded = decoder.decode(&data, &accounts).unwrap();
+    assert_eq!(decoded.fields[0].value, "Add");
+    assert_eq!(
+        decoded.fields[0].children,
+        vec![DecodedField::new("0", "5")]
+    );
+
+    let mut data = anchor_discriminator("adjust").to_vec();
+    data.push(2); // Adjustment::Scale
+    data.extend_from_slice(&3u64.to_le_bytes());
+    data.extend_from_slice(&4u64.to_le_bytes());
+    let decoded = decoder.decode(&data, &accounts).unwrap();
+    assert_eq!(decoded.fields[0].value, "Scale");
+    assert_eq!(
+        decoded.fields[0].children,
+        vec![
+            DecodedField::new("numerator", "3"),
+            DecodedField::new("denominator", "4"),
+        ]
+    );
+
+    // `v1::Adjustment` resolves to its own enum, not the root `Adjustment`
+    let mut data = anchor_discriminator("adjust_v1").to_vec();
+    data.push(1); // v1::Adjustment::Add
+    data.extend_from_slice(&7u64.to_le_bytes());
+    let decoded = decoder.decode(&data, &accounts).unwrap();
+    assert_eq!(decoded.name, "AdjustV1");
+    assert_eq!(decoded.fields[0].value, "Add");
+    assert_eq!(
+        decoded.fields[0].children,
+        vec![DecodedField::new("0", "7")]
+    );
+}
+
+#[test]
+fn test_decoder_keeps_fields_before_truncation() {
+    let decoder = counter::CounterInstructionDecoder;
+
+    // `referrer` is cut off after 10 of its 32 bytes
+    let mut data = anchor_discriminator("step").to_vec();
+    data.push(0); // StepDirection::Up
+    data.extend_from_slice(&2_000_000u64.to_le_bytes());
+    data.extend_from_slice(&[1; 10]);
+
+    let accounts = make_accounts(&["counter", "authority"]);
+    let decoded = decoder.decode(&data, &accounts).unwrap();
+    assert_eq!(decoded.name, "Step");
+
+    let fields: Vec<(&str, &str)> = decoded
+        .fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.value.as_str()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("direction", "Up"),
+            ("amount", "2.000000"),
+            (
+                "referrer",
+                "<decode error at offset 17: unexpected end of input>"
+            ),
+        ]
+    );
+    assert!(decoded.fields[2].is_decode_error());
+}
+
 #[test]
 fn test_decoder_returns_none_for_unknown() {
     let decoder = counter::CounterInstructionDecoder;
@@ -118,3 +265,74 @@ fn test_discriminators_match_anchor() {
         );
     }
 }
+
+/// Shank-style program: discriminators are the 1-byte variant index
+#[derive(InstructionDecoder)]
+#[instruction_decoder(
+    program_id = "11111111111111111111111111111111",
+    program_name = "Shank Example",
+    discriminator = "shank_u8"
+)]
+pub enum ShankExampleInstruction {
+    #[instruction_decoder(account_names = ["config", "authority"])]
+    Initialize,
+    #[instruction_decoder(account_names = ["config"])]
+    SetValue { value: u64 },
+}
+
+#[test]
+fn test_shank_u8_discriminator_scheme() {
+    let decoder = ShankExampleInstructionDecoder;
+
+    let decoded = decoder
+        .decode(&[0], &make_accounts(&["config", "authority"]))
+        .unwrap();
+    assert_eq!(decoded.name, "Initialize");
+    assert_eq!(decoded.account_names, vec!["config", "authority"]);
+
+    let data = [[1u8].as_slice(), &42u64.to_le_bytes()].concat();
+    let decoded = decoder.decode(&data, &make_accounts(&["config"])).unwrap();
+    assert_eq!(decoded.name, "SetValue");
+    assert_eq!(decoded.fields[0].name, "value");
+    assert_eq!(decoded.fields[0].value, "42");
+
+    assert!(decoder.decode(&[2], &[]).is_none());
+    // An Anchor discriminator is not recognized
+    assert!(decoder.decode(&anchor_discriminator("initialize"), &[]).is_none());
+}
+
+/// Anchor-style program using the default discriminator scheme
+#[derive(InstructionDecoder)]
+#[instruction_decoder(
+    program_id = "11111111111111111111111111111111",
+    program_name = "Anchor Example"
+)]
+pub enum AnchorExampleInstruction {
+    Initialize,
+    CreateRecord,
+    CloseRecordAccount,
+}
+
+/// The derive macro keeps its own copy of the Anchor discriminator hash; it must
+/// agree with the runtime crate's
+#[test]
+fn test_derived_discriminators_match_runtime() {
+    let decoder = AnchorExampleInstructionDecoder;
+    assert_eq!(decoder.discriminator_size(), Some(8));
+
+    for (ix_name, expected_name) in [
+        ("initialize", "Initialize"),
+        ("create_record", "CreateRecord"),
+        ("close_record_account", "CloseRecordAccount"),
+    ] {
+        let disc = anchor_discriminator(ix_name);
+        let decoded = decoder.decode(&disc, &[]).unwrap_or_else(|| {
+            panic!("derived decoder should recognize discriminator for '{ix_name}'")
+        });
+        assert_eq!(decoded.name, expected_name);
+
+        let mut altered = disc;
+        altered[7] ^= 1;
+        assert!(decoder.decode(&altered, &[]).is_none());
+    }
+}
@@ -1,11 +1,18 @@
+use std::sync::{Arc, Mutex};
+
 use instruction_decoder_tests::{
-    capture_account_states, decode_transaction, format_transaction, strip_ansi_codes,
-    TransactionLogger, LiteSVM,
+    capture_account_states, capture_clock, create_tracing_callback, decode_session,
+    decode_transaction, decode_transactions, format_batch, format_transaction, strip_ansi_codes,
+    LiteSVM, TransactionLogger,
+};
+use light_instruction_decoder::{
+    format_html, EnhancedLoggingConfig, IndentStyle, InstructionDecoder, InstructionLogBuilder,
+    LogVerbosity, SignatureDisplay, TransactionFormatter, TransactionLogBuilder, TransactionStatus,
 };
-use light_instruction_decoder::EnhancedLoggingConfig;
 use solana_keypair::{keypair_from_seed, Keypair};
 use solana_message::Message;
 use solana_native_token::LAMPORTS_PER_SOL;
+use solana_pubkey::Pubkey;
 use solana_signer::Signer;
 use solana_system_interface::instruction as system_instruction;
 use solana_transaction::Transaction;
@@ -21,6 +28,96 @@ fn setup() -> (LiteSVM, Keypair) {
     (svm, payer)
 }
 
+#[test]
+fn test_transaction_logger_assert_all_succeeded() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let logger = TransactionLogger::new(EnhancedLoggingConfig::default());
+
+    let mut send = |lamports: u64| {
+        let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), lamports);
+        let msg = Message::new(&[ix], Some(&payer.pubkey()));
+        let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+        svm.expire_blockhash();
+        logger.send_transaction(&mut svm, tx)
+    };
+
+    assert!(send(LAMPORTS_PER_SOL).is_ok());
+    assert!(send(2 * LAMPORTS_PER_SOL).is_ok());
+    logger.assert_all_succeeded();
+
+    // Transfer more than the payer owns
+    assert!(send(100 * LAMPORTS_PER_SOL).is_err());
+    let records = logger.records();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[2].tx_number, 3);
+    assert_eq!(records[2].primary_program.as_deref(), Some("System Program"));
+    assert!(records[0].formatted.is_none());
+    assert!(records[2].formatted.is_some());
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        logger.assert_all_succeeded()
+    }))
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("1 of 3 transactions failed:\n  #3 System Program: Failed: "));
+    assert!(message.contains("First failed transaction (#3):"));
+    assert!(message.contains("Transaction #3"));
+}
+
+#[test]
+fn test_transaction_logger_labels_transactions() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let logger = TransactionLogger::new(EnhancedLoggingConfig::default());
+
+    let mut send = |lamports: u64, label: &str| {
+        let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), lamports);
+        let msg = Message::new(&[ix], Some(&payer.pubkey()));
+        let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+        svm.expire_blockhash();
+        logger.send_transaction_labeled(&mut svm, tx, label)
+    };
+
+    assert!(send(LAMPORTS_PER_SOL, "setup").is_ok());
+    assert!(send(100 * LAMPORTS_PER_SOL, "overdraft").is_err());
+    let records = logger.records();
+    assert_eq!(records[0].label.as_deref(), Some("setup"));
+    assert_eq!(records[1].label.as_deref(), Some("overdraft"));
+    let formatted = strip_ansi_codes(records[1].formatted.as_ref().unwrap());
+    assert!(formatted.contains("│ ▶ overdraft\n"));
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        logger.assert_all_succeeded()
+    }))
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("1 of 2 transactions failed:\n  #2 (overdraft) System Program: "));
+}
+
+#[test]
+fn test_transaction_logger_records_clock_slot() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    svm.warp_to_slot(100);
+    let (slot, unix_timestamp) = capture_clock(&svm);
+    assert_eq!(slot, 100);
+
+    let logger = TransactionLogger::new(EnhancedLoggingConfig::default());
+    // Transfer more than the payer owns so the formatted block is kept
+    let ix = system_instruction::transfer(
+        &payer.pubkey(),
+        &recipient.pubkey(),
+        100 * LAMPORTS_PER_SOL,
+    );
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    assert!(logger.send_transaction(&mut svm, tx).is_err());
+
+    let formatted = strip_ansi_codes(logger.records()[0].formatted.as_ref().unwrap());
+    assert!(formatted.contains(&format!("Slot: 100 | Block Time: {unix_timestamp} |")));
+}
+
 #[test]
 fn test_transaction_logger_transfer() {
     let (mut svm, payer) = setup();
@@ -82,13 +179,106 @@ fn test_transaction_logger_multiple_transactions() {
             LAMPORTS_PER_SOL / (i + 1),
         );
         let msg = Message::new(&[ix], Some(&payer.pubkey()));
+        // Legacy transactions are accepted directly
         let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
-        let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
-        let result = logger.send_transaction(&mut svm, versioned_tx);
+        let result = logger.send_transaction(&mut svm, tx);
         assert!(result.is_ok());
     }
 }
 
+#[test]
+fn test_transaction_logger_reports_unmatched_decoders() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+
+    let config = EnhancedLoggingConfig::debug()
+        .with_decoders(vec![Box::new(counter::CounterInstructionDecoder)]);
+    let logger = TransactionLogger::new(config.clone()).with_diagnostics();
+    let logger_without_diagnostics = TransactionLogger::new(config);
+
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    assert!(logger.send_transaction(&mut svm, versioned_tx).is_ok());
+
+    // Only the custom counter decoder is reported; built-in decoders are ignored
+    assert_eq!(
+        logger.unmatched_decoders(),
+        vec![counter::CounterInstructionDecoder.program_id()]
+    );
+    assert!(logger_without_diagnostics.unmatched_decoders().is_empty());
+}
+
+#[test]
+fn test_decode_and_format_batch() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+
+    let mut txs = Vec::new();
+    for i in 0..2 {
+        let ix = system_instruction::transfer(
+            &payer.pubkey(),
+            &recipient.pubkey(),
+            LAMPORTS_PER_SOL / (i + 1),
+        );
+        let msg = Message::new(&[ix], Some(&payer.pubkey()));
+        let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+        let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+        let result = svm.send_transaction(versioned_tx.clone());
+        txs.push((versioned_tx, result));
+    }
+
+    let config = EnhancedLoggingConfig::debug();
+    let logs = decode_transactions(&txs, &config);
+    assert_eq!(logs.len(), 2);
+
+    let stripped = strip_ansi_codes(&format_batch(&logs, &config));
+    assert!(stripped.contains("Transaction #1"));
+    assert!(stripped.contains("Transaction #2"));
+    assert!(stripped.contains("Batch Summary (2 transactions):"));
+    assert!(stripped.contains("Status: 2 succeeded | 0 failed"));
+    assert!(stripped.contains("Total Compute Used: 300 CU | Total Fees: 0.000010 SOL"));
+}
+
+#[test]
+fn test_decode_session_looks_up_results_in_history() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+
+    let mut txs = Vec::new();
+    for i in 0..3 {
+        let ix = system_instruction::transfer(
+            &payer.pubkey(),
+            &recipient.pubkey(),
+            LAMPORTS_PER_SOL / (i + 1),
+        );
+        let msg = Message::new(&[ix], Some(&payer.pubkey()));
+        let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+        txs.push(solana_transaction::versioned::VersionedTransaction::from(tx));
+    }
+    // The last transaction is never sent
+    for tx in &txs[..2] {
+        assert!(svm.send_transaction(tx.clone()).is_ok());
+    }
+
+    let config = EnhancedLoggingConfig::debug();
+    let logs = decode_session(&svm, &txs, &config);
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[0].status, TransactionStatus::Success);
+    assert_eq!(logs[0].compute_used, 150);
+    assert_eq!(logs[1].status, TransactionStatus::Success);
+    assert_eq!(logs[2].status, TransactionStatus::NotExecuted);
+    assert_eq!(logs[2].signature, txs[2].signatures[0]);
+    assert_eq!(
+        logs[2].instructions[0].instruction_name.as_deref(),
+        Some("Transfer")
+    );
+
+    let stripped = strip_ansi_codes(&format_batch(&logs, &config));
+    assert!(stripped.contains("Batch Summary (3 transactions):"));
+}
+
 #[test]
 fn test_account_state_capture_shows_lamport_changes() {
     let (mut svm, payer) = setup();
@@ -184,3 +374,128 @@ fn test_strip_ansi_codes() {
     let plain = "hello world";
     assert_eq!(strip_ansi_codes(plain), "hello world");
 }
+
+/// Minimal subscriber that records the level of every emitted event.
+struct LevelRecorder(Arc<Mutex<Vec<tracing::Level>>>);
+
+impl tracing::Subscriber for LevelRecorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        tracing::span::Id::from_u64(1)
+    }
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+    fn event(&self, event: &tracing::Event<'_>) {
+        self.0.lock().unwrap().push(*event.metadata().level());
+    }
+    fn enter(&self, _: &tracing::span::Id) {}
+    fn exit(&self, _: &tracing::span::Id) {}
+}
+
+#[test]
+fn test_tracing_callback_levels() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let callback = create_tracing_callback(EnhancedLoggingConfig::debug());
+    let levels = Arc::new(Mutex::new(Vec::new()));
+
+    // Successful transfer: one instruction event + summary, all INFO
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+    assert!(result.is_ok());
+    tracing::subscriber::with_default(LevelRecorder(levels.clone()), || {
+        callback(&versioned_tx, &result, &svm)
+    });
+    assert_eq!(
+        *levels.lock().unwrap(),
+        vec![tracing::Level::INFO, tracing::Level::INFO]
+    );
+
+    // Transfer more than the payer owns: summary event is WARN
+    levels.lock().unwrap().clear();
+    let ix = system_instruction::transfer(
+        &payer.pubkey(),
+        &recipient.pubkey(),
+        100 * LAMPORTS_PER_SOL,
+    );
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+    assert!(result.is_err());
+    tracing::subscriber::with_default(LevelRecorder(levels.clone()), || {
+        callback(&versioned_tx, &result, &svm)
+    });
+    assert_eq!(levels.lock().unwrap().last(), Some(&tracing::Level::WARN));
+}
+
+#[test]
+fn test_format_html_snapshot() {
+    let mut config = EnhancedLoggingConfig::default();
+    config.signature_display = SignatureDisplay::Hidden;
+    let payer = Pubkey::new_from_array([1; 32]);
+    let log = TransactionLogBuilder::new()
+        .status(TransactionStatus::Success)
+        .label("memo <test> & \"quotes\"")
+        .fee(5_000)
+        .compute(1_234, 200_000)
+        .instruction(
+            InstructionLogBuilder::new(Pubkey::new_from_array([9; 32]))
+                .program_name("Memo")
+                .name("Memo")
+                .field("text", "<img src=x onerror='alert(1)'>")
+                .account(payer, true, true)
+                .account(Pubkey::new_from_array([2; 32]), false, false)
+                .inner(
+                    InstructionLogBuilder::new(Pubkey::default())
+                        .name("Transfer")
+                        .field("lamports", "1000")
+                        .account(payer, true, true)
+                        .account(Pubkey::new_from_array([3; 32]), false, true),
+                ),
+        )
+        .build();
+
+    let html = format_html(&log, &config);
+    // A fragment only: no document structure, and no unescaped user strings
+    assert!(html.starts_with("<table ") && html.ends_with("</table>\n"));
+    assert!(!html.contains("<html") && !html.contains("<head") && !html.contains("<img"));
+    insta::assert_snapshot!("format_html", html);
+}
+
+#[test]
+fn test_tree_indent_snapshot() {
+    let mut config = EnhancedLoggingConfig::default()
+        .with_indent(IndentStyle::Tree)
+        .with_signature_display(SignatureDisplay::Hidden);
+    config.use_colors = false;
+    config.verbosity = LogVerbosity::Detailed;
+    let program = |seed: u8, name: &str| {
+        InstructionLogBuilder::new(Pubkey::new_from_array([seed; 32]))
+            .program_name(name)
+            .name("Run")
+    };
+    // Router -> Vault -> (Token, Oracle), Router -> Logger, then a second top-level Memo
+    let log = TransactionLogBuilder::new()
+        .status(TransactionStatus::Success)
+        .instruction(
+            program(7, "Router")
+                .inner(
+                    program(8, "Vault")
+                        .field("amount", "100")
+                        .inner(program(9, "Token"))
+                        .inner(program(10, "Oracle")),
+                )
+                .inner(program(11, "Logger")),
+        )
+        .instruction(program(12, "Memo").field("text", "done"))
+        .build();
+
+    let formatted = TransactionFormatter::new(&config).format(&log, 1);
+    insta::assert_snapshot!("tree_indent", formatted);
+}
@@ -1,8 +1,10 @@
 use instruction_decoder_tests::{
-    capture_account_states, decode_transaction, decode_transaction_snapshot, format_transaction,
-    strip_ansi_codes, LiteSVM,
+    capture_account_states, decode_legacy_transaction, decode_message, decode_transaction,
+    decode_transaction_borrowed, decode_transaction_snapshot,
+    decode_transaction_snapshot_with_logs, format_transaction, strip_ansi_codes, LiteSVM,
 };
-use light_instruction_decoder::EnhancedLoggingConfig;
+use light_instruction_decoder::{DecoderRegistry, EnhancedLoggingConfig, TransactionStatus};
+use solana_instruction::Instruction;
 use solana_keypair::{keypair_from_seed, Keypair};
 use solana_message::Message;
 use solana_native_token::LAMPORTS_PER_SOL;
@@ -46,6 +48,13 @@ fn test_decode_transfer() {
         snapshot.instructions[0].instruction_name.as_deref(),
         Some("Transfer")
     );
+    let accounts = &snapshot.instructions[0].accounts;
+    assert_eq!(accounts[0].pubkey, payer.pubkey().to_string());
+    assert_eq!(accounts[0].role.as_deref(), Some("from"));
+    assert!(accounts[0].is_signer && accounts[0].is_writable);
+    assert_eq!(accounts[1].pubkey, recipient.pubkey().to_string());
+    assert_eq!(accounts[1].role.as_deref(), Some("to"));
+    assert!(!accounts[1].is_signer && accounts[1].is_writable);
 
     insta::assert_json_snapshot!("transfer", snapshot);
 
@@ -54,9 +63,170 @@ fn test_decode_transfer() {
     );
     let formatted = format_transaction(&log, &config, 1);
     let stripped = strip_ansi_codes(&formatted);
+    // Sender and recipient are labeled by role next to their pubkeys
+    let row = |pubkey: String| {
+        stripped
+            .lines()
+            .find(|line| line.contains(&pubkey))
+            .unwrap()
+            .to_string()
+    };
+    assert!(row(payer.pubkey().to_string()).contains("| signer+writable | from |"));
+    assert!(row(recipient.pubkey().to_string()).contains("| writable        | to   |"));
     insta::assert_snapshot!("transfer_table", stripped);
 }
 
+#[test]
+fn test_transfer_snapshot_with_logs() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+
+    let config = EnhancedLoggingConfig::debug();
+    let snapshot = decode_transaction_snapshot(&versioned_tx, &result, &config, None, None);
+    assert!(snapshot.program_logs.is_empty());
+
+    let snapshot =
+        decode_transaction_snapshot_with_logs(&versioned_tx, &result, &config, None, None, true);
+    let system_program = "11111111111111111111111111111111";
+    assert!(snapshot
+        .program_logs
+        .iter()
+        .any(|line| line.contains(&format!("Program {system_program} invoke [1]"))));
+    assert!(snapshot
+        .program_logs
+        .iter()
+        .any(|line| line.contains(&format!("Program {system_program} success"))));
+    assert!(snapshot
+        .program_logs
+        .iter()
+        .all(|line| !line.contains('\x1b')));
+}
+
+#[test]
+fn test_transfer_snapshot_with_log_tree() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+
+    let config = EnhancedLoggingConfig::debug().with_log_tree();
+    let snapshot = decode_transaction_snapshot(&versioned_tx, &result, &config, None, None);
+    let tree = snapshot.program_log_tree.unwrap();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0].program_id, "11111111111111111111111111111111");
+    assert_eq!((tree[0].depth, tree[0].success), (0, Some(true)));
+    assert!(tree[0].children.is_empty());
+}
+
+#[test]
+fn test_transfer_stays_within_compute_budget() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+
+    let config = EnhancedLoggingConfig::debug();
+    let snapshot = decode_transaction_snapshot(&versioned_tx, &result, &config, None, None);
+    assert!(snapshot.compute_used() > 0);
+    snapshot.assert_compute_under(10_000);
+
+    let log = decode_transaction(&versioned_tx, &result, &config, None, None);
+    assert_eq!(log.compute_used(), snapshot.compute_used());
+    log.assert_compute_under(10_000);
+    // Builtin programs log no consumed units, so nothing is attributed to them
+    log.assert_program_compute_under(&solana_pubkey::Pubkey::default(), 0);
+}
+
+#[test]
+fn test_decode_transaction_borrowed() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+
+    let config = EnhancedLoggingConfig::debug();
+    let borrowed = decode_transaction_borrowed(&versioned_tx, &result, &config);
+    // Instruction data points into the transaction instead of being copied
+    assert!(std::ptr::eq(
+        borrowed.instructions[0].data,
+        versioned_tx.message.instructions()[0].data.as_slice()
+    ));
+    assert_eq!(
+        borrowed.instructions[0].instruction_name.as_deref(),
+        Some("Transfer")
+    );
+
+    let owned = decode_transaction(&versioned_tx, &result, &config, None, None);
+    assert_eq!(borrowed.into_owned(), owned);
+}
+
+#[test]
+fn test_decode_message() {
+    let payer = deterministic_keypair(1);
+    let recipient = deterministic_keypair(2);
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let message = solana_message::VersionedMessage::Legacy(msg);
+
+    let config = EnhancedLoggingConfig::debug();
+    let log = decode_message(&message, &config);
+
+    assert_eq!(log.status, TransactionStatus::NotExecuted);
+    assert_eq!((log.fee, log.compute_used), (0, 0));
+    assert_eq!(log.instructions.len(), 1);
+    assert_eq!(log.instructions[0].instruction_name.as_deref(), Some("Transfer"));
+    assert_eq!(log.instructions[0].accounts[0].pubkey, payer.pubkey());
+    assert!(log.instructions[0].inner_instructions.is_empty());
+
+    let formatted = strip_ansi_codes(&format_transaction(&log, &config, 1));
+    assert!(formatted.contains("Status: Not executed"));
+    assert!(!formatted.contains("Compute Used:"));
+}
+
+#[test]
+fn test_failed_instruction_index() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let ok_ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let failing_ix =
+        system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), 100 * LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ok_ix, failing_ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let versioned_tx = solana_transaction::versioned::VersionedTransaction::from(tx);
+    let result = svm.send_transaction(versioned_tx.clone());
+    assert!(result.is_err());
+
+    let config = EnhancedLoggingConfig::debug();
+    let log = decode_transaction(&versioned_tx, &result, &config, None, None);
+    assert_eq!(log.failed_instruction_index, Some(1));
+    assert!(log.instructions[0].success);
+    assert!(!log.instructions[1].success);
+
+    let formatted = strip_ansi_codes(&format_transaction(&log, &config, 1));
+    assert_eq!(formatted.matches("✗ FAILED HERE").count(), 1);
+    assert!(formatted.contains("(System Program) - Transfer ✗ FAILED HERE"));
+
+    let snapshot = decode_transaction_snapshot(&versioned_tx, &result, &config, None, None);
+    let failed = snapshot.failed_instruction.unwrap();
+    assert_eq!(failed.path, "#2");
+    assert_eq!(failed.instruction_name.as_deref(), Some("Transfer"));
+    assert!(snapshot.status.starts_with("Failed: InstructionError(1, "));
+}
+
 #[test]
 fn test_decode_create_account() {
     let (mut svm, payer) = setup();
@@ -136,7 +306,139 @@ fn test_decode_allocate_and_assign() {
     let log = decode_transaction(
         &versioned_tx, &result, &config, Some(&pre_states), Some(&post_states),
     );
+    assert_eq!(log.net_data_len_change(), 200);
     let formatted = format_transaction(&log, &config, 1);
     let stripped = strip_ansi_codes(&formatted);
     insta::assert_snapshot!("allocate_and_assign_table", stripped);
 }
+
+#[test]
+fn test_decode_legacy_transfer() {
+    let (mut svm, payer) = setup();
+    let recipient = deterministic_keypair(2);
+    let ix = system_instruction::transfer(&payer.pubkey(), &recipient.pubkey(), LAMPORTS_PER_SOL);
+    let msg = Message::new(&[ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+
+    let result = svm.send_transaction(tx.clone());
+
+    let config = EnhancedLoggingConfig::debug();
+    let log = decode_legacy_transaction(&tx, &result, &config, None, None);
+
+    assert_eq!(log.signature, tx.signatures[0]);
+    assert_eq!(log.instructions.len(), 1);
+    assert_eq!(
+        log.instructions[0].instruction_name.as_deref(),
+        Some("Transfer")
+    );
+}
+
+#[test]
+fn test_decode_advance_and_withdraw_nonce() {
+    let (mut svm, payer) = setup();
+    let nonce = deterministic_keypair(7);
+    let recipient = deterministic_keypair(8);
+
+    let create_ixs = system_instruction::create_nonce_account(
+        &payer.pubkey(),
+        &nonce.pubkey(),
+        &payer.pubkey(),
+        LAMPORTS_PER_SOL,
+    );
+    let msg = Message::new(&create_ixs, Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer, &nonce], msg, svm.latest_blockhash());
+    svm.send_transaction(tx).unwrap();
+    // A nonce can only be advanced once the blockhash it stores has expired
+    svm.expire_blockhash();
+
+    let advance_ix = system_instruction::advance_nonce_account(&nonce.pubkey(), &payer.pubkey());
+    let withdraw_ix = system_instruction::withdraw_nonce_account(
+        &nonce.pubkey(),
+        &payer.pubkey(),
+        &recipient.pubkey(),
+        LAMPORTS_PER_SOL / 2,
+    );
+    let msg = Message::new(&[advance_ix, withdraw_ix], Some(&payer.pubkey()));
+    let tx = Transaction::new(&[&payer], msg, svm.latest_blockhash());
+    let result = svm.send_transaction(tx.clone());
+
+    let config = EnhancedLoggingConfig::debug();
+    let log = decode_legacy_transaction(&tx, &result, &config, None, None);
+
+    assert!(result.is_ok());
+    assert_eq!(log.instructions.len(), 2);
+
+    let advance = log.instructions[0].decoded_instruction.as_ref().unwrap();
+    assert_eq!(advance.name, "AdvanceNonceAccount");
+    assert_eq!(
+        advance.account_names,
+        [
+            "nonce_account",
+            "recent_blockhashes_sysvar",
+            "nonce_authority"
+        ]
+    );
+
+    let withdraw = log.instructions[1].decoded_instruction.as_ref().unwrap();
+    assert_eq!(withdraw.name, "WithdrawNonceAccount");
+    assert_eq!(withdraw.fields[0].name, "lamports");
+    assert_eq!(withdraw.fields[0].value, (LAMPORTS_PER_SOL / 2).to_string());
+    assert_eq!(
+        withdraw.account_names,
+        [
+            "nonce_account",
+            "recipient",
+            "recent_blockhashes_sysvar",
+            "rent_sysvar",
+            "nonce_authority"
+        ]
+    );
+}
+
+#[test]
+fn test_decode_initialize_and_authorize_nonce_authority() {
+    let payer = deterministic_keypair(1);
+    let nonce = deterministic_keypair(7);
+    let new_authority = deterministic_keypair(9);
+    let mut ixs = system_instruction::create_nonce_account(
+        &payer.pubkey(),
+        &nonce.pubkey(),
+        &payer.pubkey(),
+        LAMPORTS_PER_SOL,
+    );
+    ixs.push(system_instruction::authorize_nonce_account(
+        &nonce.pubkey(),
+        &payer.pubkey(),
+        &new_authority.pubkey(),
+    ));
+    ixs.push(system_instruction::upgrade_nonce_account(nonce.pubkey()));
+
+    let registry = DecoderRegistry::new();
+    let decode = |ix: &Instruction| {
+        registry
+            .decode(&ix.program_id, &ix.data, &ix.accounts)
+            .unwrap()
+            .0
+    };
+
+    let initialize = decode(&ixs[1]);
+    assert_eq!(initialize.name, "InitializeNonceAccount");
+    assert_eq!(initialize.fields[0].name, "authority");
+    assert_eq!(initialize.fields[0].value, payer.pubkey().to_string());
+
+    let authorize = decode(&ixs[2]);
+    assert_eq!(authorize.name, "AuthorizeNonceAccount");
+    assert_eq!(authorize.fields[0].name, "new_authority");
+    assert_eq!(
+        authorize.fields[0].value,
+        new_authority.pubkey().to_string()
+    );
+    assert_eq!(
+        authorize.account_names,
+        ["nonce_account", "nonce_authority"]
+    );
+
+    let upgrade = decode(&ixs[3]);
+    assert_eq!(upgrade.name, "UpgradeNonceAccount");
+    assert_eq!(upgrade.account_names, ["nonce_account"]);
+}